@@ -60,6 +60,14 @@ pub struct Cli {
     #[clap(long, value_parser, action = clap::ArgAction::Set, default_value_t = false)]
     pub json_schema_export: bool,
 
+    /// Maps the prelude's tdate/time to validated DateTime/EpochTime types instead of a tagged tstr/number (adds a chrono dependency)
+    #[clap(long, value_parser, action = clap::ArgAction::Set, default_value_t = false)]
+    pub typed_date_time: bool,
+
+    /// Represents arrays with an exact number of elements e.g. [3*3 uint] as fixed-size [T; N] instead of a Vec
+    #[clap(long, value_parser, action = clap::ArgAction::Set, default_value_t = false)]
+    pub fixed_size_arrays: bool,
+
     /// Generates a npm package.json along with build scripts
     #[clap(long, value_parser, action = clap::ArgAction::Set, default_value_t = false)]
     pub package_json: bool,
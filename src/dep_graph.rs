@@ -75,7 +75,15 @@ fn find_references<'a>(cddl_rule: &'a Rule<'a>) -> (&'a Identifier, Vec<&'a Iden
 }
 
 fn find_refs_type1<'a>(refs: &mut Vec<&'a Identifier<'a>>, type1: &'a Type1<'a>) {
-    match &type1.type2 {
+    find_refs_type2(refs, &type1.type2);
+    // control operators can refer to other rules too e.g. uint .bits flags
+    if let Some(operator) = &type1.operator {
+        find_refs_type2(refs, &operator.type2);
+    }
+}
+
+fn find_refs_type2<'a>(refs: &mut Vec<&'a Identifier<'a>>, type2: &'a Type2<'a>) {
+    match type2 {
         Type2::Typename {
             ident,
             generic_args,
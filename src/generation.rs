@@ -8,9 +8,11 @@ use std::process::{Command, Stdio};
 
 use crate::intermediate::{
     AliasIdent, CBOREncodingOperation, CDDLIdent, ConceptualRustType, EnumVariant, EnumVariantData,
-    FixedValue, IntermediateTypes, ModuleScope, Primitive, Representation, RustField, RustIdent,
-    RustRecord, RustStructCBORLen, RustStructConfig, RustStructType, RustType,
-    RustTypeSerializeConfig, ToWasmBoundaryOperations, VariantIdent, ROOT_SCOPE,
+    FixedValue, FloatBounds, IntermediateTypes, MapKey, ModuleScope, Primitive, Representation,
+    RustField, RustIdent, RustRecord, RustStructCBORLen, RustStructConfig, RustStructType,
+    RustType, RustTypeSerializeConfig, ToWasmBoundaryOperations, VariantIdent, BIG_FLOAT_IDENT,
+    BIG_INT_IDENT, BIG_UINT_IDENT, CBOR_VALUE_IDENT, DATE_TIME_IDENT, DECIMAL_FRACTION_IDENT,
+    EPOCH_TIME_IDENT, ROOT_SCOPE, TAGGED_VALUE_IDENT,
 };
 use crate::utils::{cbor_type_code_str, convert_to_snake_case};
 
@@ -220,6 +222,15 @@ impl<'a> DeserializeConfig<'a> {
         self
     }
 
+    /// for deserializing a record field, using its @custom_deserialize function if it has one
+    fn for_field(var_name: &'a str, field: &RustField, in_embedded: bool) -> Self {
+        let config = Self::new(var_name).in_embedded(in_embedded);
+        match &field.rule_metadata.custom_deserialize {
+            Some(custom_deserialize) => config.custom_deserialize(custom_deserialize.clone()),
+            None => config,
+        }
+    }
+
     fn pass_read_len(&self) -> String {
         if let Some(overload) = &self.read_len_overload {
             // the ONLY way to have a name overload is if we have a local variable (e.g. arrays)
@@ -268,6 +279,7 @@ impl<'a> EncodingVarIsCopy for SerializingRustType<'a> {
     fn encoding_var_is_copy(&self, types: &IntermediateTypes) -> bool {
         match self {
             Self::EncodingOperation(CBOREncodingOperation::CBORBytes, _) => false,
+            Self::EncodingOperation(CBOREncodingOperation::CBORSeqBytes, _) => false,
             Self::EncodingOperation(CBOREncodingOperation::Tagged(_), _) => true,
             Self::Root(ty, _cfg) => ty.encoding_var_is_copy(types),
         }
@@ -277,9 +289,15 @@ impl<'a> EncodingVarIsCopy for SerializingRustType<'a> {
 impl EncodingVarIsCopy for FixedValue {
     fn encoding_var_is_copy(&self, _types: &IntermediateTypes) -> bool {
         match self {
-            // bool / null have no encoding var
-            Self::Bool(_) | Self::Nint(_) | Self::Null | Self::Float(_) | Self::Uint(_) => true,
-            Self::Text(_) => false,
+            // bool / null / undefined / simple values have no encoding var
+            Self::Bool(_)
+            | Self::Nint(_)
+            | Self::Null
+            | Self::Undefined
+            | Self::Simple(_)
+            | Self::Float(_)
+            | Self::Uint(_) => true,
+            Self::Text(_) | Self::Bytes(_) => false,
         }
     }
 }
@@ -295,8 +313,10 @@ impl EncodingVarIsCopy for ConceptualRustType {
             Self::Primitive(p) => match p {
                 // bool has no encoding var
                 Primitive::Bool
+                | Primitive::Float
                 | Primitive::F64
                 | Primitive::F32
+                | Primitive::F16
                 | Primitive::I8
                 | Primitive::I16
                 | Primitive::I32
@@ -525,12 +545,16 @@ impl GenerationScope {
                     {
                         // wasm-bindgen doesn't support const or static vars so we must do a function
                         let (ty, val) = match constant {
-                            FixedValue::Null => panic!("null constants not supported"),
+                            FixedValue::Null | FixedValue::Undefined => {
+                                panic!("null / undefined constants not supported")
+                            }
                             FixedValue::Bool(b) => ("bool", b.to_string()),
+                            FixedValue::Simple(n) => ("u8", n.to_string()),
                             FixedValue::Nint(i) => ("i32", i.to_string()),
                             FixedValue::Uint(u) => ("u32", u.to_string()),
                             FixedValue::Float(f) => ("f64", f.to_string()),
                             FixedValue::Text(s) => ("String", format!("\"{s}\".to_owned()")),
+                            FixedValue::Bytes(bytes) => ("Vec<u8>", format!("vec!{bytes:?}")),
                         };
                         self.wasm(types, ident)
                             .new_fn(convert_to_snake_case(ident.as_ref()))
@@ -685,13 +709,24 @@ impl GenerationScope {
                         rust_struct.config(),
                         cli,
                     ),
-                    RustStructType::Wrapper { wrapped, min_max } => match rust_struct.tag() {
+                    RustStructType::Wrapper {
+                        wrapped,
+                        min_max,
+                        regexp,
+                        bits,
+                        excluded,
+                        fixed_len,
+                    } => match rust_struct.tag() {
                         Some(tag) => generate_wrapper_struct(
                             self,
                             types,
                             rust_ident,
                             &wrapped.clone().tag(tag),
                             *min_max,
+                            *fixed_len,
+                            regexp.as_deref(),
+                            bits.as_deref(),
+                            excluded.as_deref(),
                             rust_struct.config(),
                             cli,
                         ),
@@ -701,18 +736,51 @@ impl GenerationScope {
                             rust_ident,
                             wrapped,
                             *min_max,
+                            *fixed_len,
+                            regexp.as_deref(),
+                            bits.as_deref(),
+                            excluded.as_deref(),
                             rust_struct.config(),
                             cli,
                         ),
                     },
                     RustStructType::Extern => {
-                        #[allow(clippy::single_match)]
                         match rust_ident.to_string().as_ref() {
                             "Int" => {
                                 if types.is_referenced(rust_ident) {
                                     generate_int(self, types, cli)
                                 }
                             }
+                            // a user-defined extern type could share the name of a static one
+                            // if the latter was never needed (see register_static_externs())
+                            CBOR_VALUE_IDENT if types.is_static_extern(rust_ident) => {
+                                // TaggedValue's wrapper exposes its value as a CBORValue
+                                if cbor_value_used(types) {
+                                    generate_cbor_value(self, types, cli)
+                                }
+                            }
+                            TAGGED_VALUE_IDENT if types.is_static_extern(rust_ident) => {
+                                if types.is_referenced(rust_ident) {
+                                    generate_tagged_value(self, types, cli)
+                                }
+                            }
+                            BIG_UINT_IDENT
+                            | BIG_INT_IDENT
+                            | DECIMAL_FRACTION_IDENT
+                            | BIG_FLOAT_IDENT
+                                if types.is_static_extern(rust_ident) =>
+                            {
+                                if types.is_referenced(rust_ident) {
+                                    generate_big_int(self, types, rust_ident, cli)
+                                }
+                            }
+                            DATE_TIME_IDENT | EPOCH_TIME_IDENT
+                                if types.is_static_extern(rust_ident) =>
+                            {
+                                if types.is_referenced(rust_ident) {
+                                    generate_date_time(self, types, rust_ident, cli)
+                                }
+                            }
                             _ => (), /* user-specified external types */
                         }
                     }
@@ -750,7 +818,9 @@ impl GenerationScope {
                 // The is_referenced check is for things like Int which are included by default
                 // in order for the CDDL to parse but might not be used.
                 // However, we need to export other root types from the user's spec
-                if !is_typedef && (rust_ident.as_ref() != "Int" || types.is_referenced(rust_ident))
+                if !is_typedef
+                    && (!(rust_ident.as_ref() == "Int" || types.is_static_extern(rust_ident))
+                        || types.is_referenced(rust_ident))
                 {
                     main_lines_by_file
                         .entry(types.scope(rust_ident).clone())
@@ -793,11 +863,40 @@ impl GenerationScope {
         }
 
         // declare modules (root lib specific)
+        let cbor_value_used = cbor_value_used(types);
         if cli.export_static_files() {
             self.rust_lib().raw("pub mod error;");
             if cli.preserve_encodings {
                 self.rust_lib().raw("pub mod ordered_hash_map;");
             }
+            if cbor_value_used {
+                self.rust_lib().raw("pub mod cbor_value;");
+            }
+            if big_int_used(types) {
+                self.rust_lib().raw("pub mod big_int;");
+            }
+            if date_time_used(types) {
+                self.rust_lib().raw("pub mod date_time;");
+            }
+        }
+        if cbor_value_used {
+            // so it can be referred to like any other root-level type
+            self.rust_lib().raw(&format!(
+                "pub use {}::cbor_value::{{CBORSpecial, CBORValue, TaggedValue}};",
+                cli.common_import_rust()
+            ));
+        }
+        if big_int_used(types) {
+            self.rust_lib().raw(&format!(
+                "pub use {}::big_int::{{BigFloat, BigInt, BigUInt, DecimalFraction}};",
+                cli.common_import_rust()
+            ));
+        }
+        if date_time_used(types) {
+            self.rust_lib().raw(&format!(
+                "pub use {}::date_time::{{DateTime, EpochTime}};",
+                cli.common_import_rust()
+            ));
         }
         if cli.preserve_encodings {
             self.rust_lib().raw("extern crate derivative;");
@@ -848,6 +947,11 @@ impl GenerationScope {
                         format!("{}::serialization", cli.common_import_rust()),
                         "StringEncoding",
                         None,
+                    )
+                    .push_import(
+                        format!("{}::serialization", cli.common_import_rust()),
+                        "FloatEncoding",
+                        None,
                     );
             }
         }
@@ -867,6 +971,11 @@ impl GenerationScope {
                         format!("{}::serialization", cli.common_import_rust()),
                         "StringEncoding",
                         None,
+                    )
+                    .push_import(
+                        format!("{}::serialization", cli.common_import_rust()),
+                        "FloatEncoding",
+                        None,
                     );
             }
         }
@@ -1195,7 +1304,9 @@ impl GenerationScope {
         if cli.json_schema_export {
             rust_cargo_toml.push_str("schemars = \"0.8.8\"\n");
         }
+        let cbor_value_used = cbor_value_used(types);
         if export_raw_bytes_encoding_trait
+            || (cbor_value_used && cli.json_serde_derives)
             || types
                 .rust_structs()
                 .iter()
@@ -1212,6 +1323,26 @@ impl GenerationScope {
         {
             rust_cargo_toml.push_str("hex = \"0.4.3\"\n");
         }
+        if types.rust_structs().values().any(|rust_struct| {
+            matches!(
+                rust_struct.variant(),
+                RustStructType::Wrapper {
+                    regexp: Some(_),
+                    ..
+                }
+            )
+        }) {
+            rust_cargo_toml.push_str("once_cell = \"1.18.0\"\n");
+            rust_cargo_toml.push_str("regex = \"1.9\"\n");
+        }
+        if big_int_used(types) {
+            rust_cargo_toml.push_str("num-bigint = \"0.4\"\n");
+        }
+        if date_time_used(types) {
+            rust_cargo_toml.push_str(
+                "chrono = { version = \"0.4.31\", default-features = false, features = [\"std\"] }\n",
+            );
+        }
         if cli.wasm
             && types
                 .rust_structs()
@@ -1254,6 +1385,95 @@ impl GenerationScope {
                     rustfmt_generated_string(&ordered_hash_map_rs)?.as_ref(),
                 )?;
             }
+
+            // cbor_value.rs
+            if cbor_value_used {
+                let mut cbor_value_paths = vec![cli.static_dir.join("cbor_value.rs")];
+                if cli.preserve_encodings {
+                    cbor_value_paths.push(cli.static_dir.join("cbor_value_preserve.rs"));
+                    if cli.canonical_form {
+                        cbor_value_paths.push(
+                            cli.static_dir
+                                .join("cbor_value_preserve_force_canonical.rs"),
+                        );
+                    } else {
+                        cbor_value_paths.push(
+                            cli.static_dir
+                                .join("cbor_value_preserve_non_force_canonical.rs"),
+                        );
+                    }
+                } else {
+                    cbor_value_paths.push(cli.static_dir.join("cbor_value_non_preserve.rs"));
+                }
+                if cli.json_serde_derives {
+                    cbor_value_paths.push(cli.static_dir.join("cbor_value_json.rs"));
+                }
+                if cli.json_schema_export {
+                    cbor_value_paths.push(cli.static_dir.join("cbor_value_schemars.rs"));
+                }
+                std::fs::write(
+                    rust_dir.join("rust/src/cbor_value.rs"),
+                    rustfmt_generated_string(&concat_files(&cbor_value_paths)?)?.as_ref(),
+                )?;
+            }
+
+            // big_int.rs
+            if big_int_used(types) {
+                let mut big_int_paths = vec![cli.static_dir.join("big_int.rs")];
+                if cli.preserve_encodings {
+                    big_int_paths.push(cli.static_dir.join("big_int_preserve.rs"));
+                    if cli.canonical_form {
+                        big_int_paths
+                            .push(cli.static_dir.join("big_int_preserve_force_canonical.rs"));
+                    } else {
+                        big_int_paths.push(
+                            cli.static_dir
+                                .join("big_int_preserve_non_force_canonical.rs"),
+                        );
+                    }
+                } else {
+                    big_int_paths.push(cli.static_dir.join("big_int_non_preserve.rs"));
+                }
+                if cli.json_serde_derives {
+                    big_int_paths.push(cli.static_dir.join("big_int_json.rs"));
+                }
+                if cli.json_schema_export {
+                    big_int_paths.push(cli.static_dir.join("big_int_schemars.rs"));
+                }
+                std::fs::write(
+                    rust_dir.join("rust/src/big_int.rs"),
+                    rustfmt_generated_string(&concat_files(&big_int_paths)?)?.as_ref(),
+                )?;
+            }
+
+            // date_time.rs
+            if date_time_used(types) {
+                let mut date_time_paths = vec![cli.static_dir.join("date_time.rs")];
+                if cli.preserve_encodings {
+                    date_time_paths.push(cli.static_dir.join("date_time_preserve.rs"));
+                    if cli.canonical_form {
+                        date_time_paths
+                            .push(cli.static_dir.join("date_time_preserve_force_canonical.rs"));
+                    } else {
+                        date_time_paths.push(
+                            cli.static_dir
+                                .join("date_time_preserve_non_force_canonical.rs"),
+                        );
+                    }
+                } else {
+                    date_time_paths.push(cli.static_dir.join("date_time_non_preserve.rs"));
+                }
+                if cli.json_serde_derives {
+                    date_time_paths.push(cli.static_dir.join("date_time_json.rs"));
+                }
+                if cli.json_schema_export {
+                    date_time_paths.push(cli.static_dir.join("date_time_schemars.rs"));
+                }
+                std::fs::write(
+                    rust_dir.join("rust/src/date_time.rs"),
+                    rustfmt_generated_string(&concat_files(&date_time_paths)?)?.as_ref(),
+                )?;
+            }
         }
 
         // wasm crate
@@ -1272,6 +1492,10 @@ impl GenerationScope {
                 wasm_toml.push_str("serde_json = \"1.0.57\"\n");
                 wasm_toml.push_str("serde-wasm-bindgen = \"0.4.5\"\n");
             }
+            if date_time_used(types) {
+                // JS Date conversions
+                wasm_toml.push_str("js-sys = \"0.3\"\n");
+            }
             std::fs::write(
                 rust_dir.join("wasm/Cargo.toml"),
                 wasm_toml.replace("cddl-lib", &cli.lib_name),
@@ -1392,6 +1616,234 @@ impl GenerationScope {
             .or_insert(codegen::Scope::new())
     }
 
+    /// Serializes all elements of an array (excluding the array header/break) e.g. for `[* T]`
+    /// or for `bytes .cborseq [* T]` where there is no header at all.
+    fn generate_serialize_array_elems(
+        &mut self,
+        types: &IntermediateTypes,
+        ty: &RustType,
+        body: &mut dyn CodeBlock,
+        config: SerializeConfig,
+        cli: &Cli,
+    ) {
+        let elem_var_name = format!("{}_elem", config.var_name);
+        let elem_encs = if cli.preserve_encodings {
+            encoding_fields(
+                types,
+                &elem_var_name,
+                &ty.clone().resolve_aliases(),
+                false,
+                cli,
+            )
+        } else {
+            vec![]
+        };
+        let mut loop_block = if !elem_encs.is_empty() {
+            let mut block = Block::new(format!(
+                "for (i, element) in {}.iter().enumerate()",
+                config.expr
+            ));
+            block.line(config.container_encoding_lookup("elem", &elem_encs, "i"));
+            block
+        } else {
+            Block::new(format!("for element in {}.iter()", config.expr))
+        };
+        let elem_config = config
+            .clone()
+            .expr("element")
+            .expr_is_ref(true)
+            .var_name(elem_var_name)
+            .is_end(false)
+            .encoding_var_no_option_struct()
+            .encoding_var_is_ref(false);
+        self.generate_serialize(types, ty.into(), &mut loop_block, elem_config, cli);
+        body.push_block(loop_block);
+    }
+
+    /// Deserializes a single map entry (key then value) and inserts it into the local {var_name}_table
+    /// as well as {var_name}_key_encodings / {var_name}_value_encodings if preserve-encodings=true
+    #[allow(clippy::too_many_arguments)]
+    fn generate_deserialize_map_entry<T: CodeBlock>(
+        &mut self,
+        types: &IntermediateTypes,
+        key_type: &RustType,
+        value_type: &RustType,
+        var_name: &str,
+        deserializer_name_overload: Option<&str>,
+        body: &mut T,
+        cli: &Cli,
+    ) {
+        let table_var = format!("{var_name}_table");
+        let key_var_name = format!("{var_name}_key");
+        let value_var_name = format!("{var_name}_value");
+        let key_encs = if cli.preserve_encodings {
+            encoding_fields(
+                types,
+                &key_var_name,
+                &key_type.clone().resolve_aliases(),
+                false,
+                cli,
+            )
+        } else {
+            vec![]
+        };
+        let value_encs = if cli.preserve_encodings {
+            encoding_fields(
+                types,
+                &value_var_name,
+                &value_type.clone().resolve_aliases(),
+                false,
+                cli,
+            )
+        } else {
+            vec![]
+        };
+        let mut key_config = DeserializeConfig::new(&key_var_name);
+        key_config.deserializer_name_overload = deserializer_name_overload;
+        let mut value_config = DeserializeConfig::new(&value_var_name);
+        value_config.deserializer_name_overload = deserializer_name_overload;
+        let (key_var_names_str, value_var_names_str) = if cli.preserve_encodings {
+            (
+                encoding_var_names_str(types, &key_var_name, key_type, cli),
+                encoding_var_names_str(types, &value_var_name, value_type, cli),
+            )
+        } else {
+            (key_var_name.clone(), value_var_name.clone())
+        };
+        self.generate_deserialize(
+            types,
+            key_type.into(),
+            DeserializeBeforeAfter::new(&format!("let {key_var_names_str} = "), ";", false),
+            key_config,
+            cli,
+        )
+        .add_to(body);
+        self.generate_deserialize(
+            types,
+            value_type.into(),
+            DeserializeBeforeAfter::new(&format!("let {value_var_names_str} = "), ";", false),
+            value_config,
+            cli,
+        )
+        .add_to(body);
+        let mut dup_check = Block::new(format!(
+            "if {}.insert({}{}, {}).is_some()",
+            table_var,
+            key_var_name,
+            if key_type.is_copy(types) {
+                ""
+            } else {
+                ".clone()"
+            },
+            value_var_name
+        ));
+        let dup_key_error_key = match &key_type.conceptual_type {
+            ConceptualRustType::Primitive(Primitive::U8)
+            | ConceptualRustType::Primitive(Primitive::U16)
+            | ConceptualRustType::Primitive(Primitive::U32)
+            | ConceptualRustType::Primitive(Primitive::U64) => {
+                format!("Key::Uint({key_var_name}.into())")
+            }
+            ConceptualRustType::Primitive(Primitive::Str) => {
+                format!("Key::Str({key_var_name})")
+            }
+            // TODO: make a generic one then store serialized CBOR?
+            _ => "Key::Str(String::from(\"some complicated/unsupported type\"))".to_owned(),
+        };
+        dup_check.line(format!(
+            "return Err(DeserializeFailure::DuplicateKey({dup_key_error_key}).into());"
+        ));
+        body.push_block(dup_check);
+        if cli.preserve_encodings {
+            if !key_encs.is_empty() {
+                body.line(&format!(
+                    "{}_key_encodings.insert({}{}, {});",
+                    var_name,
+                    key_var_name,
+                    if key_type.encoding_var_is_copy(types) {
+                        ""
+                    } else {
+                        ".clone()"
+                    },
+                    tuple_str(key_encs.iter().map(|enc| enc.field_name.clone()).collect())
+                ));
+            }
+            if !value_encs.is_empty() {
+                body.line(&format!(
+                    "{}_value_encodings.insert({}{}, {});",
+                    var_name,
+                    key_var_name,
+                    if key_type.encoding_var_is_copy(types) {
+                        ""
+                    } else {
+                        ".clone()"
+                    },
+                    tuple_str(
+                        value_encs
+                            .iter()
+                            .map(|enc| enc.field_name.clone())
+                            .collect()
+                    )
+                ));
+            }
+        }
+    }
+
+    /// Serializes a single map entry (key then value) from the locals `key` and `value`
+    /// e.g. within a loop over a map's entries. {config} refers to the map itself.
+    fn generate_serialize_map_entry(
+        &mut self,
+        types: &IntermediateTypes,
+        key_type: &RustType,
+        value_type: &RustType,
+        body: &mut dyn CodeBlock,
+        config: &SerializeConfig,
+        cli: &Cli,
+    ) {
+        if cli.preserve_encodings {
+            let key_enc_fields = encoding_fields(
+                types,
+                &format!("{}_key", config.var_name),
+                &key_type.clone().resolve_aliases(),
+                false,
+                cli,
+            );
+            if !key_enc_fields.is_empty() {
+                body.line(&config.container_encoding_lookup("key", &key_enc_fields, "key"));
+            }
+        }
+        let key_config = config
+            .clone()
+            .expr("key")
+            .expr_is_ref(true)
+            .var_name(format!("{}_key", config.var_name))
+            .is_end(false)
+            .encoding_var_no_option_struct()
+            .encoding_var_is_ref(false);
+        self.generate_serialize(types, key_type.into(), body, key_config, cli);
+        if cli.preserve_encodings {
+            let value_enc_fields = encoding_fields(
+                types,
+                &format!("{}_value", config.var_name),
+                &value_type.clone().resolve_aliases(),
+                false,
+                cli,
+            );
+            if !value_enc_fields.is_empty() {
+                body.line(&config.container_encoding_lookup("value", &value_enc_fields, "key"));
+            }
+        }
+        let value_config = config
+            .clone()
+            .expr("value")
+            .expr_is_ref(true)
+            .var_name(format!("{}_value", config.var_name))
+            .is_end(false)
+            .encoding_var_no_option_struct()
+            .encoding_var_is_ref(false);
+        self.generate_serialize(types, value_type.into(), body, value_config, cli);
+    }
+
     /// Write code for serializing {serializing_rust_type} directly into {body}
     #[allow(clippy::only_used_in_recursion)]
     fn generate_serialize(
@@ -1510,15 +1962,46 @@ impl GenerationScope {
                         cli,
                     );
                 }
+                SerializingRustType::EncodingOperation(
+                    CBOREncodingOperation::CBORSeqBytes,
+                    child,
+                ) => {
+                    let elem_ty = match *child {
+                        SerializingRustType::Root(ConceptualRustType::Array(elem_ty), _cfg) => {
+                            elem_ty
+                        }
+                        _ => panic!(".cborseq must be applied to an array type"),
+                    };
+                    let inner_se = format!("{}_inner_se", config.var_name);
+                    body.line(&format!("let mut {inner_se} = Serializer::new_vec();"));
+                    let inner_config = config
+                        .clone()
+                        .is_end(false)
+                        .serializer_name_overload((&inner_se, true));
+                    self.generate_serialize_array_elems(types, elem_ty, body, inner_config, cli);
+                    body.line(&format!(
+                        "let {}_bytes = {}.finalize();",
+                        config.var_name, inner_se
+                    ));
+                    write_string_sz(
+                        body,
+                        "write_bytes",
+                        serializer_use,
+                        &format!("{}_bytes", config.var_name),
+                        true,
+                        line_ender,
+                        &config.encoding_var(Some("bytes"), encoding_var_is_copy),
+                        cli,
+                    );
+                }
                 SerializingRustType::Root(ConceptualRustType::Fixed(value), _cfg) => match value {
-                    FixedValue::Null => {
-                        body.line(&format!(
-                            "{serializer_use}.write_special(cbor_event::Special::Null){line_ender}"
-                        ));
-                    }
-                    FixedValue::Bool(b) => {
+                    FixedValue::Null
+                    | FixedValue::Undefined
+                    | FixedValue::Bool(_)
+                    | FixedValue::Simple(_) => {
                         body.line(&format!(
-                            "{serializer_use}.write_special(cbor_event::Special::Bool({b})){line_ender}"
+                            "{serializer_use}.write_special({}){line_ender}",
+                            fixed_value_special(value)
                         ));
                     }
                     FixedValue::Uint(u) => {
@@ -1572,9 +2055,15 @@ impl GenerationScope {
                         }
                     }
                     FixedValue::Float(f) => {
-                        body.line(&format!(
-                            "{serializer_use}.write_special(cbor_event::Special::Float({f})){line_ender}"
-                        ));
+                        write_float(
+                            body,
+                            serializer_use,
+                            &format!("{f:?}f64"),
+                            None,
+                            line_ender,
+                            &encoding_var_deref,
+                            cli,
+                        );
                     }
                     FixedValue::Text(s) => {
                         write_string_sz(
@@ -1588,6 +2077,18 @@ impl GenerationScope {
                             cli,
                         );
                     }
+                    FixedValue::Bytes(bytes) => {
+                        write_string_sz(
+                            body,
+                            "write_bytes",
+                            serializer_use,
+                            &bytes_literal(bytes),
+                            false,
+                            line_ender,
+                            &encoding_var,
+                            cli,
+                        );
+                    }
                 },
                 SerializingRustType::Root(ConceptualRustType::Primitive(primitive), _cfg) => {
                     match primitive {
@@ -1596,15 +2097,22 @@ impl GenerationScope {
                                 "{serializer_use}.write_special(cbor_event::Special::Bool({expr_deref})){line_ender}"
                             ));
                         }
-                        Primitive::F32 => {
-                            body.line(&format!(
-                                "{serializer_use}.write_special(cbor_event::Special::Float({expr_deref} as f64)){line_ender}"
-                            ));
-                        }
-                        Primitive::F64 => {
-                            body.line(&format!(
-                                "{serializer_use}.write_special(cbor_event::Special::Float({expr_deref})){line_ender}"
-                            ));
+                        Primitive::Float | Primitive::F16 | Primitive::F32 | Primitive::F64 => {
+                            let (expr, strict_width) = match primitive {
+                                Primitive::F16 => (format!("{expr_deref} as f64"), Some("Half")),
+                                Primitive::F32 => (format!("{expr_deref} as f64"), Some("Single")),
+                                Primitive::F64 => (expr_deref.clone(), Some("Double")),
+                                _ => (expr_deref.clone(), None),
+                            };
+                            write_float(
+                                body,
+                                serializer_use,
+                                &expr,
+                                strict_width,
+                                line_ender,
+                                &encoding_var_deref,
+                                cli,
+                            );
                         }
                         Primitive::Bytes => {
                             write_string_sz(
@@ -1770,6 +2278,12 @@ impl GenerationScope {
                         }
                     }
                 }
+                SerializingRustType::Root(ConceptualRustType::Array(ty), cfg)
+                    if cfg.embedded_seq =>
+                {
+                    // no header - the elements are directly in the parent array
+                    self.generate_serialize_array_elems(types, ty, body, config.clone(), cli);
+                }
                 SerializingRustType::Root(ConceptualRustType::Array(ty), _cfg) => {
                     let len_expr = match &ty.conceptual_type {
                         ConceptualRustType::Rust(elem_ident)
@@ -1798,44 +2312,7 @@ impl GenerationScope {
                         &len_expr,
                         cli,
                     );
-                    let elem_var_name = format!("{}_elem", config.var_name);
-                    let elem_encs = if cli.preserve_encodings {
-                        encoding_fields(
-                            types,
-                            &elem_var_name,
-                            &ty.clone().resolve_aliases(),
-                            false,
-                            cli,
-                        )
-                    } else {
-                        vec![]
-                    };
-                    let mut loop_block = if !elem_encs.is_empty() {
-                        let mut block = Block::new(format!(
-                            "for (i, element) in {}.iter().enumerate()",
-                            config.expr
-                        ));
-                        block.line(config.container_encoding_lookup("elem", &elem_encs, "i"));
-                        block
-                    } else {
-                        Block::new(format!("for element in {}.iter()", config.expr))
-                    };
-                    let elem_config = config
-                        .clone()
-                        .expr("element")
-                        .expr_is_ref(true)
-                        .var_name(elem_var_name)
-                        .is_end(false)
-                        .encoding_var_no_option_struct()
-                        .encoding_var_is_ref(false);
-                    self.generate_serialize(
-                        types,
-                        (&**ty).into(),
-                        &mut loop_block,
-                        elem_config,
-                        cli,
-                    );
-                    body.push_block(loop_block);
+                    self.generate_serialize_array_elems(types, ty, body, config.clone(), cli);
                     end_len(body, serializer_use, &encoding_var, config.is_end, cli);
                 }
                 SerializingRustType::Root(ConceptualRustType::Map(key, value), _cfg) => {
@@ -1847,7 +2324,7 @@ impl GenerationScope {
                         &format!("{}.len() as u64", config.expr),
                         cli,
                     );
-                    let ser_loop = if cli.preserve_encodings {
+                    let ser_loop = if cli.preserve_encodings && cli.canonical_form {
                         let key_enc_fields = encoding_fields(
                             types,
                             &format!("{}_key", config.var_name),
@@ -1862,86 +2339,55 @@ impl GenerationScope {
                             false,
                             cli,
                         );
-                        let mut ser_loop = if cli.canonical_form {
-                            let mut key_order = Block::new(format!(
-                                "let mut key_order = {}.iter().map(|(k, v)|",
-                                config.expr
+                        let mut key_order = Block::new(format!(
+                            "let mut key_order = {}.iter().map(|(k, v)|",
+                            config.expr
+                        ));
+                        key_order.line("let mut buf = cbor_event::se::Serializer::new_vec();");
+                        if !key_enc_fields.is_empty() {
+                            key_order.line(config.container_encoding_lookup(
+                                "key",
+                                &key_enc_fields,
+                                "k",
                             ));
-                            key_order.line("let mut buf = cbor_event::se::Serializer::new_vec();");
-                            if !key_enc_fields.is_empty() {
-                                key_order.line(config.container_encoding_lookup(
-                                    "key",
-                                    &key_enc_fields,
-                                    "k",
-                                ));
-                            }
-                            let key_config =
-                                SerializeConfig::new("k", format!("{}_key", config.var_name))
-                                    .expr_is_ref(true)
-                                    .is_end(false)
-                                    .serializer_name_overload(("buf", true))
-                                    .encoding_var_is_ref(false);
-                            self.generate_serialize(
-                                types,
-                                (&**key).into(),
-                                &mut key_order,
-                                key_config,
-                                cli,
-                            );
-                            key_order.line("Ok((buf.finalize(), k, v))").after(
-                                ").collect::<Result<Vec<(Vec<u8>, &_, &_)>, cbor_event::Error>>()?;",
-                            );
-                            body.push_block(key_order);
-                            let mut key_order_if = Block::new("if force_canonical");
-                            let mut key_order_sort = Block::new(
-                                "key_order.sort_by(|(lhs_bytes, _, _), (rhs_bytes, _, _)|",
-                            );
-                            let mut key_order_sort_match =
-                                Block::new("match lhs_bytes.len().cmp(&rhs_bytes.len())");
-                            key_order_sort_match
-                                .line("std::cmp::Ordering::Equal => lhs_bytes.cmp(rhs_bytes),")
-                                .line("diff_ord => diff_ord,");
-                            key_order_sort.push_block(key_order_sort_match).after(");");
-                            key_order_if.push_block(key_order_sort);
-                            body.push_block(key_order_if);
-                            let key_loop_var = if value_enc_fields.is_empty() {
-                                "_key"
-                            } else {
-                                "key"
-                            };
-                            let mut ser_loop = Block::new(format!(
-                                "for (key_bytes, {key_loop_var}, value) in key_order"
-                            ));
-                            ser_loop
-                                .line(format!("{serializer_use}.write_raw_bytes(&key_bytes)?;"));
-                            ser_loop
-                        } else {
-                            let mut ser_loop =
-                                Block::new(format!("for (key, value) in {}.iter()", config.expr));
-                            if !key_enc_fields.is_empty() {
-                                ser_loop.line(config.container_encoding_lookup(
-                                    "key",
-                                    &key_enc_fields,
-                                    "key",
-                                ));
-                            }
-                            let key_config = config
-                                .clone()
-                                .expr("key")
+                        }
+                        let key_config =
+                            SerializeConfig::new("k", format!("{}_key", config.var_name))
                                 .expr_is_ref(true)
-                                .var_name(format!("{}_key", config.var_name))
                                 .is_end(false)
-                                .encoding_var_no_option_struct()
+                                .serializer_name_overload(("buf", true))
                                 .encoding_var_is_ref(false);
-                            self.generate_serialize(
-                                types,
-                                (&**key).into(),
-                                &mut ser_loop,
-                                key_config,
-                                cli,
-                            );
-                            ser_loop
+                        self.generate_serialize(
+                            types,
+                            (&**key).into(),
+                            &mut key_order,
+                            key_config,
+                            cli,
+                        );
+                        key_order.line("Ok((buf.finalize(), k, v))").after(
+                            ").collect::<Result<Vec<(Vec<u8>, &_, &_)>, cbor_event::Error>>()?;",
+                        );
+                        body.push_block(key_order);
+                        let mut key_order_if = Block::new("if force_canonical");
+                        let mut key_order_sort =
+                            Block::new("key_order.sort_by(|(lhs_bytes, _, _), (rhs_bytes, _, _)|");
+                        let mut key_order_sort_match =
+                            Block::new("match lhs_bytes.len().cmp(&rhs_bytes.len())");
+                        key_order_sort_match
+                            .line("std::cmp::Ordering::Equal => lhs_bytes.cmp(rhs_bytes),")
+                            .line("diff_ord => diff_ord,");
+                        key_order_sort.push_block(key_order_sort_match).after(");");
+                        key_order_if.push_block(key_order_sort);
+                        body.push_block(key_order_if);
+                        let key_loop_var = if value_enc_fields.is_empty() {
+                            "_key"
+                        } else {
+                            "key"
                         };
+                        let mut ser_loop = Block::new(format!(
+                            "for (key_bytes, {key_loop_var}, value) in key_order"
+                        ));
+                        ser_loop.line(format!("{serializer_use}.write_raw_bytes(&key_bytes)?;"));
                         if !value_enc_fields.is_empty() {
                             ser_loop.line(config.container_encoding_lookup(
                                 "value",
@@ -1968,30 +2414,12 @@ impl GenerationScope {
                     } else {
                         let mut ser_loop =
                             Block::new(format!("for (key, value) in {}.iter()", config.expr));
-                        let key_config = config
-                            .clone()
-                            .expr("key")
-                            .expr_is_ref(true)
-                            .var_name(format!("{}_key", config.var_name))
-                            .is_end(false)
-                            .encoding_var_no_option_struct()
-                            .encoding_var_is_ref(false);
-                        let value_config = key_config
-                            .clone()
-                            .expr("value")
-                            .var_name(format!("{}_value", config.var_name));
-                        self.generate_serialize(
-                            types,
-                            (&**key).into(),
-                            &mut ser_loop,
-                            key_config,
-                            cli,
-                        );
-                        self.generate_serialize(
+                        self.generate_serialize_map_entry(
                             types,
-                            (&**value).into(),
+                            key,
+                            value,
                             &mut ser_loop,
-                            value_config,
+                            &config,
                             cli,
                         );
                         ser_loop
@@ -2165,6 +2593,26 @@ impl GenerationScope {
                                 ));
                             }
                         }
+                        // none of these have any encoding details to preserve
+                        FixedValue::Undefined | FixedValue::Bool(_) | FixedValue::Simple(_) => {
+                            deser_code.content.line(&format!(
+                                "let {}_value = {}.special()?;",
+                                config.var_name, deserializer_name
+                            ));
+                            let special = fixed_value_special(f);
+                            let mut compare_block =
+                                Block::new(format!("if {}_value != {special}", config.var_name));
+                            compare_block.line(format!("return Err(DeserializeFailure::FixedValueMismatch{{ found: Key::Special({}_value), expected: Key::Special({special}) }}.into());", config.var_name));
+                            deser_code.content.push_block(compare_block);
+                            if cli.preserve_encodings {
+                                deser_code.content.line(&format!(
+                                    "{}{}{}",
+                                    before_after.before_str(false),
+                                    final_expr(config.final_exprs, None),
+                                    before_after.after_str(false)
+                                ));
+                            }
+                        }
                         FixedValue::Uint(x) => {
                             if cli.preserve_encodings {
                                 deser_code.content.line(&format!(
@@ -2207,10 +2655,9 @@ impl GenerationScope {
                                     config.var_name, deserializer_name
                                 ));
                             }
-                            let x_abs = (x + 1).abs();
                             let mut compare_block =
                                 Block::new(format!("if {}_value != {}", config.var_name, x));
-                            compare_block.line(format!("return Err(DeserializeFailure::FixedValueMismatch{{ found: Key::Uint(({}_value + 1).abs() as u64), expected: Key::Uint({}) }}.into());", config.var_name, x_abs));
+                            compare_block.line(format!("return Err(DeserializeFailure::FixedValueMismatch{{ found: Key::Nint({}_value), expected: Key::Nint({}) }}.into());", config.var_name, x));
                             deser_code.content.push_block(compare_block);
                             if cli.preserve_encodings {
                                 config
@@ -2255,16 +2702,31 @@ impl GenerationScope {
                             }
                         }
                         FixedValue::Float(x) => {
-                            deser_code.content.line(&format!(
-                                "let {}_value = {}.float()?;",
-                                config.var_name, deserializer_name
-                            ));
+                            if cli.preserve_encodings {
+                                deser_code.content.line(&format!(
+                                    "let ({}_value, {}_encoding) = {}.float_encoded()?;",
+                                    config.var_name, config.var_name, deserializer_name
+                                ));
+                            } else {
+                                deser_code.content.line(&format!(
+                                    "let ({}_value, _) = {}.float_encoded()?;",
+                                    config.var_name, deserializer_name
+                                ));
+                            }
                             let mut compare_block =
-                                Block::new(format!("if {}_value != {}", config.var_name, x));
-                            compare_block.line(format!("return Err(DeserializeFailure::FixedValueMismatch{{ found: Key::Float({}_value), expected: Key::Float({}) }}.into());", config.var_name, x));
+                                Block::new(format!("if {}_value != {:?}", config.var_name, x));
+                            compare_block.line(format!("return Err(DeserializeFailure::FixedValueMismatch{{ found: Key::Float({}_value), expected: Key::Float({:?}) }}.into());", config.var_name, x));
                             deser_code.content.push_block(compare_block);
                             if cli.preserve_encodings {
-                                unimplemented!("preserve_encodings is not implemented for float")
+                                config
+                                    .final_exprs
+                                    .push(format!("Some({}_encoding)", config.var_name));
+                                deser_code.content.line(&format!(
+                                    "{}{}{}",
+                                    before_after.before_str(false),
+                                    final_expr(config.final_exprs, None),
+                                    before_after.after_str(false)
+                                ));
                             }
                         }
                         _ => unimplemented!(),
@@ -2487,31 +2949,58 @@ impl GenerationScope {
                                 "raw.bool().map_err(Into::into)",
                             ));
                         }
-                        Primitive::F32 => {
-                            deser_code.content.line(&final_result_expr_complete(
-                                &mut deser_code.throws,
-                                config.final_exprs,
-                                "f32::deserialize(raw)",
+                        Primitive::Float => {
+                            // any width is accepted, preserve-encodings remembers which one it was
+                            let (enc, value_expr) = if cli.preserve_encodings {
+                                let mut final_exprs = config.final_exprs;
+                                final_exprs.push("Some(enc)".to_owned());
+                                ("enc", final_expr(final_exprs, Some("x".to_owned())))
+                            } else {
+                                ("_enc", "x".to_owned())
+                            };
+                            let enc_map_fn = match &type_cfg.float_bounds {
+                                Some(float_bounds) => format!(
+                                    ".and_then(|(x, {})| {} else {{ Ok({}) }})",
+                                    enc,
+                                    float_bounds_check_if_block(float_bounds, "x", false),
+                                    value_expr
+                                ),
+                                None => format!(".map(|(x, {enc})| {value_expr})"),
+                            };
+                            deser_code.content.line(&format!(
+                                "{}{}.float_encoded(){}{}",
+                                before_after.before_str(true),
+                                deserializer_name,
+                                enc_map_fn,
+                                before_after.after_str(true)
                             ));
-                            if cli.preserve_encodings {
-                                unimplemented!("preserve_encodings is not implemented for float")
-                            }
-                            if type_cfg.bounds.is_some() {
-                                unimplemented!("bounds not supported for floats")
-                            }
                         }
-                        Primitive::F64 => {
-                            deser_code.content.line(&final_result_expr_complete(
-                                &mut deser_code.throws,
-                                config.final_exprs,
-                                "f64::deserialize(raw)",
+                        Primitive::F16 | Primitive::F32 | Primitive::F64 => {
+                            // float16/32/64 are exactly #7.25/26/27 so there is no encoding to remember
+                            let (width, x_expr) = match p {
+                                Primitive::F16 => ("Half", "x as f32"),
+                                Primitive::F32 => ("Single", "x as f32"),
+                                _ => ("Double", "x"),
+                            };
+                            let value_expr =
+                                final_expr(config.final_exprs, Some(x_expr.to_owned()));
+                            let map_fn = match &type_cfg.float_bounds {
+                                Some(float_bounds) => format!(
+                                    ".and_then(|x| {} else {{ Ok({}) }})",
+                                    float_bounds_check_if_block(float_bounds, "x", false),
+                                    value_expr
+                                ),
+                                None if value_expr == "x" => String::new(),
+                                None => format!(".map(|x| {value_expr})"),
+                            };
+                            deser_code.content.line(&format!(
+                                "{}{}.float_with_width(FloatEncoding::{}){}{}",
+                                before_after.before_str(true),
+                                deserializer_name,
+                                width,
+                                map_fn,
+                                before_after.after_str(true)
                             ));
-                            if cli.preserve_encodings {
-                                unimplemented!("preserve_encodings is not implemented for float")
-                            }
-                            if type_cfg.bounds.is_some() {
-                                unimplemented!("bounds not supported for floats")
-                            }
                         }
                     };
                 }
@@ -2810,23 +3299,41 @@ impl GenerationScope {
                         vec![]
                     };
                     if cli.preserve_encodings {
-                        deser_code
-                            .content
-                            .line(&format!("let len = {deserializer_name}.array_sz()?;"))
-                            .line(&format!("let {}_encoding = len.into();", config.var_name));
+                        if !type_cfg.embedded_seq {
+                            deser_code
+                                .content
+                                .line(&format!("let len = {deserializer_name}.array_sz()?;"))
+                                .line(&format!("let {}_encoding = len.into();", config.var_name));
+                        }
                         if !elem_encs.is_empty() {
                             deser_code.content.line(&format!(
                                 "let mut {}_elem_encodings = Vec::new();",
                                 config.var_name
                             ));
                         }
-                    } else {
+                    } else if !type_cfg.embedded_seq {
                         deser_code
                             .content
                             .line(&format!("let len = {deserializer_name}.array()?;"));
                     }
                     let mut elem_config = DeserializeConfig::new(&elem_var_name);
                     let (mut deser_loop, plain_len_check) = match &ty.conceptual_type {
+                        _ if type_cfg.embedded_seq => {
+                            // the elements are directly in the parent array so we keep reading them
+                            // as long as the parent has elements left and the next one is of the right type
+                            let mut deser_loop = Block::new(format!(
+                                "while match len {{ {} => read_len.read() < n, {} => true, }} && {}",
+                                cbor_event_len_n("n", cli),
+                                cbor_event_len_indef(cli),
+                                cbor_type_lookahead_cond(
+                                    deserializer_name,
+                                    &ty.cbor_types(types)
+                                ),
+                            ));
+                            deser_loop.line("read_len.read_elems(1)?;");
+                            deser_code.read_len_used = true;
+                            (deser_loop, None)
+                        }
                         ConceptualRustType::Rust(ty_ident) if types.is_plain_group(ty_ident) => {
                             // two things that must be done differently for embedded plain groups:
                             // 1) We can't directly read the CBOR len's number of items since it could be >1
@@ -2856,7 +3363,11 @@ impl GenerationScope {
                             None,
                         ),
                     };
-                    deser_loop.push_block(make_deser_loop_break_check());
+                    if !type_cfg.embedded_seq {
+                        deser_loop.push_block(make_deser_loop_break_check(
+                            ty.cbor_types(types).contains(&cbor_event::Type::Special),
+                        ));
+                    }
                     if let Some(plain_len_check) = plain_len_check {
                         deser_loop.line(plain_len_check);
                     }
@@ -2902,16 +3413,18 @@ impl GenerationScope {
                     deser_code.content.push_block(deser_loop);
                     if let Some(bounds) = &type_cfg.bounds {
                         // we use cargo fmt after so it's okay if we just use .line() here
-                        deser_code.content.line(&bounds_check_if_block(
+                        deser_code.content.line(&occurrence_check_if_block(
                             bounds,
                             &format!("{arr_var_name}.len()"),
                             true,
                         ));
                     }
                     if cli.preserve_encodings {
-                        config
-                            .final_exprs
-                            .push(format!("{}_encoding", config.var_name));
+                        if !type_cfg.embedded_seq {
+                            config
+                                .final_exprs
+                                .push(format!("{}_encoding", config.var_name));
+                        }
                         if !elem_encs.is_empty() {
                             config
                                 .final_exprs
@@ -3009,111 +3522,24 @@ impl GenerationScope {
                         }
                         let mut deser_loop =
                             make_deser_loop(&len_var, &format!("({table_var}.len() as u64)"), cli);
-                        deser_loop.push_block(make_deser_loop_break_check());
-                        let mut key_config = DeserializeConfig::new(&key_var_name);
-                        key_config.deserializer_name_overload = config.deserializer_name_overload;
-                        let mut value_config = DeserializeConfig::new(&value_var_name);
-                        value_config.deserializer_name_overload = config.deserializer_name_overload;
-                        let (key_var_names_str, value_var_names_str) = if cli.preserve_encodings {
-                            (
-                                encoding_var_names_str(types, &key_var_name, key_type, cli),
-                                encoding_var_names_str(types, &value_var_name, value_type, cli),
-                            )
-                        } else {
-                            (key_var_name.clone(), value_var_name.clone())
-                        };
-                        self.generate_deserialize(
-                            types,
-                            (&**key_type).into(),
-                            DeserializeBeforeAfter::new(
-                                &format!("let {key_var_names_str} = "),
-                                ";",
-                                false,
-                            ),
-                            key_config,
-                            cli,
-                        )
-                        .add_to(&mut deser_loop);
-                        self.generate_deserialize(
+                        deser_loop.push_block(make_deser_loop_break_check(
+                            key_type
+                                .cbor_types(types)
+                                .contains(&cbor_event::Type::Special),
+                        ));
+                        self.generate_deserialize_map_entry(
                             types,
-                            (&**value_type).into(),
-                            DeserializeBeforeAfter::new(
-                                &format!("let {value_var_names_str} = "),
-                                ";",
-                                false,
-                            ),
-                            value_config,
+                            key_type,
+                            value_type,
+                            config.var_name,
+                            config.deserializer_name_overload,
+                            &mut deser_loop,
                             cli,
-                        )
-                        .add_to(&mut deser_loop);
-                        let mut dup_check = Block::new(format!(
-                            "if {}.insert({}{}, {}).is_some()",
-                            table_var,
-                            key_var_name,
-                            if key_type.is_copy(types) {
-                                ""
-                            } else {
-                                ".clone()"
-                            },
-                            value_var_name
-                        ));
-                        let dup_key_error_key = match &key_type.conceptual_type {
-                            ConceptualRustType::Primitive(Primitive::U8)
-                            | ConceptualRustType::Primitive(Primitive::U16)
-                            | ConceptualRustType::Primitive(Primitive::U32)
-                            | ConceptualRustType::Primitive(Primitive::U64) => {
-                                format!("Key::Uint({key_var_name}.into())")
-                            }
-                            ConceptualRustType::Primitive(Primitive::Str) => {
-                                format!("Key::Str({key_var_name})")
-                            }
-                            // TODO: make a generic one then store serialized CBOR?
-                            _ => "Key::Str(String::from(\"some complicated/unsupported type\"))"
-                                .to_owned(),
-                        };
-                        dup_check.line(format!(
-                        "return Err(DeserializeFailure::DuplicateKey({dup_key_error_key}).into());"
-                    ));
-                        deser_loop.push_block(dup_check);
-                        if cli.preserve_encodings {
-                            if !key_encs.is_empty() {
-                                deser_loop.line(format!(
-                                    "{}_key_encodings.insert({}{}, {});",
-                                    config.var_name,
-                                    key_var_name,
-                                    if key_type.encoding_var_is_copy(types) {
-                                        ""
-                                    } else {
-                                        ".clone()"
-                                    },
-                                    tuple_str(
-                                        key_encs.iter().map(|enc| enc.field_name.clone()).collect()
-                                    )
-                                ));
-                            }
-                            if !value_encs.is_empty() {
-                                deser_loop.line(format!(
-                                    "{}_value_encodings.insert({}{}, {});",
-                                    config.var_name,
-                                    key_var_name,
-                                    if key_type.encoding_var_is_copy(types) {
-                                        ""
-                                    } else {
-                                        ".clone()"
-                                    },
-                                    tuple_str(
-                                        value_encs
-                                            .iter()
-                                            .map(|enc| enc.field_name.clone())
-                                            .collect()
-                                    )
-                                ));
-                            }
-                        }
+                        );
                         deser_code.content.push_block(deser_loop);
                         if let Some(bounds) = &type_cfg.bounds {
                             // we use cargo fmt after so it's okay if we just use .line() here
-                            deser_code.content.line(&bounds_check_if_block(
+                            deser_code.content.line(&occurrence_check_if_block(
                                 bounds,
                                 &format!("{table_var}.len()"),
                                 true,
@@ -3203,101 +3629,216 @@ impl GenerationScope {
                     deser_code.throws = true;
                 }
                 SerializingRustType::EncodingOperation(
-                    CBOREncodingOperation::Tagged(tag),
+                    CBOREncodingOperation::CBORSeqBytes,
                     child,
                 ) => {
+                    let ty = match *child {
+                        SerializingRustType::Root(ConceptualRustType::Array(ty), _cfg) => ty,
+                        _ => panic!(".cborseq must be applied to an array type"),
+                    };
+                    if let ConceptualRustType::Rust(ty_ident) = &ty.conceptual_type {
+                        if types.is_plain_group(ty_ident) {
+                            unreachable!(".cborseq of plain groups is rejected during parsing");
+                        }
+                    }
                     if config.optional_field {
                         deser_code.content.line("read_len.read_elems(1)?;");
                         deser_code.read_len_used = true;
                     }
-                    let mut tag_check = if cli.preserve_encodings {
-                        let mut tag_check = Block::new(format!(
-                            "{}match {}.tag_sz()?",
-                            before_after.before, deserializer_name
+                    if cli.preserve_encodings {
+                        config.final_exprs.push(format!(
+                            "StringEncoding::from({}_bytes_encoding)",
+                            config.var_name
+                        ));
+                        deser_code.content.line(&format!(
+                            "let ({}_bytes, {}_bytes_encoding) = {}.bytes_sz()?;",
+                            config.var_name, config.var_name, deserializer_name
                         ));
-                        config.final_exprs.push("Some(tag_enc)".to_owned());
-                        let some_deser_code = self
-                            .generate_deserialize(
-                                types,
-                                *child,
-                                DeserializeBeforeAfter::new("", "", before_after.expects_result),
-                                config.optional_field(false),
-                                cli,
-                            )
-                            .mark_and_extract_content(&mut deser_code);
-                        if let Some(single_line) = some_deser_code.as_single_line() {
-                            tag_check.line(format!("({tag}, tag_enc) => {single_line},"));
-                        } else {
-                            let mut deser_block = Block::new(format!("({tag}, tag_enc) =>"));
-                            deser_block.push_all(some_deser_code);
-                            deser_block.after(",");
-                            tag_check.push_block(deser_block);
-                        }
-                        tag_check
                     } else {
-                        let mut tag_check = Block::new(format!(
-                            "{}match {}.tag()?",
-                            before_after.before, deserializer_name
+                        deser_code.content.line(&format!(
+                            "let {}_bytes = {}.bytes()?;",
+                            config.var_name, deserializer_name
                         ));
-
-                        let some_deser_code = self
-                            .generate_deserialize(
-                                types,
-                                *child,
-                                DeserializeBeforeAfter::new("", "", before_after.expects_result),
-                                config.optional_field(false),
-                                cli,
-                            )
-                            .mark_and_extract_content(&mut deser_code);
-                        if let Some(single_line) = some_deser_code.as_single_line() {
-                            tag_check.line(format!("{tag} => {single_line},"));
-                        } else {
-                            let mut deser_block = Block::new(format!("{tag} =>"));
-                            deser_block.push_all(some_deser_code);
-                            deser_block.after(",");
-                            tag_check.push_block(deser_block);
-                        }
-                        tag_check
                     };
-                    tag_check.line(&format!(
-                    "{} => {}Err(DeserializeFailure::TagMismatch{{ found: tag, expected: {} }}.into()),",
-                    if cli.preserve_encodings { "(tag, _enc)" } else { "tag" },
-                    if before_after.expects_result { "" } else { "return " },
-                    tag));
-                    tag_check.after(before_after.after);
-                    deser_code.content.push_block(tag_check);
-                    deser_code.throws = true;
-                }
-            }
-        }
-        deser_code
-    }
-
-    fn deserialize_generated(&self, name: &RustIdent) -> bool {
-        !self.no_deser_reasons.contains_key(name)
-    }
-
-    fn deserialize_generated_for_type(
-        &self,
-        types: &IntermediateTypes,
-        field_type: &ConceptualRustType,
-    ) -> bool {
-        match field_type {
-            ConceptualRustType::Fixed(_) => true,
-            ConceptualRustType::Primitive(_) => true,
-            ConceptualRustType::Rust(ident) => {
-                types.is_enum(ident) || self.deserialize_generated(ident)
-            }
-            ConceptualRustType::Array(ty) => {
-                self.deserialize_generated_for_type(types, &ty.conceptual_type)
-            }
-            ConceptualRustType::Map(k, v) => {
-                self.deserialize_generated_for_type(types, &k.conceptual_type)
-                    && self.deserialize_generated_for_type(types, &v.conceptual_type)
-            }
-            ConceptualRustType::Optional(ty) => {
-                self.deserialize_generated_for_type(types, &ty.conceptual_type)
-            }
+                    let name_overload = "inner_de";
+                    deser_code.content.line(&format!(
+                        "let {} = &mut Deserializer::from(std::io::Cursor::new({}_bytes));",
+                        name_overload, config.var_name
+                    ));
+                    let arr_var_name = format!("{}_arr", config.var_name);
+                    deser_code
+                        .content
+                        .line(&format!("let mut {arr_var_name} = Vec::new();"));
+                    let elem_var_name = format!("{}_elem", config.var_name);
+                    let elem_encs = if cli.preserve_encodings {
+                        encoding_fields(
+                            types,
+                            &elem_var_name,
+                            &ty.clone().resolve_aliases(),
+                            false,
+                            cli,
+                        )
+                    } else {
+                        vec![]
+                    };
+                    if !elem_encs.is_empty() {
+                        deser_code.content.line(&format!(
+                            "let mut {}_elem_encodings = Vec::new();",
+                            config.var_name
+                        ));
+                    }
+                    // a sequence has no length or break so we read items until the bytes run out
+                    let mut deser_loop = Block::new(format!(
+                        "while !{name_overload}.as_mut_ref().fill_buf().map_err(cbor_event::Error::from)?.is_empty()"
+                    ));
+                    let elem_config =
+                        DeserializeConfig::new(&elem_var_name).overload_deserializer(name_overload);
+                    if !elem_encs.is_empty() {
+                        let elem_var_names_str =
+                            encoding_var_names_str(types, &elem_var_name, ty, cli);
+                        self.generate_deserialize(
+                            types,
+                            ty.into(),
+                            DeserializeBeforeAfter::new(
+                                &format!("let {elem_var_names_str} = "),
+                                ";",
+                                false,
+                            ),
+                            elem_config,
+                            cli,
+                        )
+                        .add_to(&mut deser_loop);
+                        deser_loop
+                            .line(format!("{arr_var_name}.push({elem_var_name});"))
+                            .line(format!(
+                                "{}_elem_encodings.push({});",
+                                config.var_name,
+                                tuple_str(
+                                    elem_encs.iter().map(|enc| enc.field_name.clone()).collect()
+                                )
+                            ));
+                        config
+                            .final_exprs
+                            .push(format!("{}_elem_encodings", config.var_name));
+                    } else {
+                        self.generate_deserialize(
+                            types,
+                            ty.into(),
+                            DeserializeBeforeAfter::new(
+                                &format!("{arr_var_name}.push("),
+                                ");",
+                                false,
+                            ),
+                            elem_config,
+                            cli,
+                        )
+                        .add_to(&mut deser_loop);
+                    }
+                    deser_code.content.push_block(deser_loop);
+                    deser_code.content.line(&format!(
+                        "{}{}{}",
+                        before_after.before_str(false),
+                        final_expr(config.final_exprs, Some(arr_var_name)),
+                        before_after.after_str(false)
+                    ));
+                    deser_code.throws = true;
+                }
+                SerializingRustType::EncodingOperation(
+                    CBOREncodingOperation::Tagged(tag),
+                    child,
+                ) => {
+                    if config.optional_field {
+                        deser_code.content.line("read_len.read_elems(1)?;");
+                        deser_code.read_len_used = true;
+                    }
+                    let mut tag_check = if cli.preserve_encodings {
+                        let mut tag_check = Block::new(format!(
+                            "{}match {}.tag_sz()?",
+                            before_after.before, deserializer_name
+                        ));
+                        config.final_exprs.push("Some(tag_enc)".to_owned());
+                        let some_deser_code = self
+                            .generate_deserialize(
+                                types,
+                                *child,
+                                DeserializeBeforeAfter::new("", "", before_after.expects_result),
+                                config.optional_field(false),
+                                cli,
+                            )
+                            .mark_and_extract_content(&mut deser_code);
+                        if let Some(single_line) = some_deser_code.as_single_line() {
+                            tag_check.line(format!("({tag}, tag_enc) => {single_line},"));
+                        } else {
+                            let mut deser_block = Block::new(format!("({tag}, tag_enc) =>"));
+                            deser_block.push_all(some_deser_code);
+                            deser_block.after(",");
+                            tag_check.push_block(deser_block);
+                        }
+                        tag_check
+                    } else {
+                        let mut tag_check = Block::new(format!(
+                            "{}match {}.tag()?",
+                            before_after.before, deserializer_name
+                        ));
+
+                        let some_deser_code = self
+                            .generate_deserialize(
+                                types,
+                                *child,
+                                DeserializeBeforeAfter::new("", "", before_after.expects_result),
+                                config.optional_field(false),
+                                cli,
+                            )
+                            .mark_and_extract_content(&mut deser_code);
+                        if let Some(single_line) = some_deser_code.as_single_line() {
+                            tag_check.line(format!("{tag} => {single_line},"));
+                        } else {
+                            let mut deser_block = Block::new(format!("{tag} =>"));
+                            deser_block.push_all(some_deser_code);
+                            deser_block.after(",");
+                            tag_check.push_block(deser_block);
+                        }
+                        tag_check
+                    };
+                    tag_check.line(&format!(
+                    "{} => {}Err(DeserializeFailure::TagMismatch{{ found: tag, expected: {} }}.into()),",
+                    if cli.preserve_encodings { "(tag, _enc)" } else { "tag" },
+                    if before_after.expects_result { "" } else { "return " },
+                    tag));
+                    tag_check.after(before_after.after);
+                    deser_code.content.push_block(tag_check);
+                    deser_code.throws = true;
+                }
+            }
+        }
+        deser_code
+    }
+
+    fn deserialize_generated(&self, name: &RustIdent) -> bool {
+        !self.no_deser_reasons.contains_key(name)
+    }
+
+    fn deserialize_generated_for_type(
+        &self,
+        types: &IntermediateTypes,
+        field_type: &ConceptualRustType,
+    ) -> bool {
+        match field_type {
+            ConceptualRustType::Fixed(_) => true,
+            ConceptualRustType::Primitive(_) => true,
+            ConceptualRustType::Rust(ident) => {
+                types.is_enum(ident) || self.deserialize_generated(ident)
+            }
+            ConceptualRustType::Array(ty) => {
+                self.deserialize_generated_for_type(types, &ty.conceptual_type)
+            }
+            ConceptualRustType::Map(k, v) => {
+                self.deserialize_generated_for_type(types, &k.conceptual_type)
+                    && self.deserialize_generated_for_type(types, &v.conceptual_type)
+            }
+            ConceptualRustType::Optional(ty) => {
+                self.deserialize_generated_for_type(types, &ty.conceptual_type)
+            }
             ConceptualRustType::Alias(_ident, ty) => self.deserialize_generated_for_type(types, ty),
         }
     }
@@ -3448,6 +3989,24 @@ fn canonical_param(cli: &Cli) -> &'static str {
     }
 }
 
+/// minItems/maxItems in the JSON schema for arrays with a bounded number of elements.
+/// Same newline workaround as encoding_var_macros() for the field macro.
+fn json_schema_length_macro(ty: &RustType, cli: &Cli) -> String {
+    match (ty.resolve_alias_shallow(), &ty.config.bounds) {
+        (ConceptualRustType::Array(_), Some((min, max))) if cli.json_schema_export => {
+            let mut args = Vec::new();
+            if let Some(min) = min {
+                args.push(format!("min = {min}"));
+            }
+            if let Some(max) = max {
+                args.push(format!("max = {max}"));
+            }
+            format!("#[schemars(length({}))]\n", args.join(", "))
+        }
+        _ => String::new(),
+    }
+}
+
 /// the codegen crate doesn't support proc macros for fields so we need to
 /// do this with newlines. codegen takes care of indentation somehow.
 fn encoding_var_macros(used_in_key: bool, custom_json: bool, cli: &Cli) -> String {
@@ -3544,6 +4103,37 @@ fn write_using_sz(
     }
 }
 
+// float16/32/64 are always written in their declared width (strict_width) while float is written
+// in the width it was read as (if it still fits) or canonically/as a double otherwise
+#[allow(clippy::too_many_arguments)]
+fn write_float(
+    body: &mut dyn CodeBlock,
+    serializer_use: &str,
+    expr: &str,
+    strict_width: Option<&str>,
+    line_ender: &str,
+    encoding_var: &str,
+    cli: &Cli,
+) {
+    let encoding = if let Some(width) = strict_width {
+        format!("FloatEncoding::{width}")
+    } else if cli.preserve_encodings {
+        format!(
+            "fit_float({}, {}, FloatEncoding::Double{})",
+            expr,
+            encoding_var,
+            canonical_param(cli)
+        )
+    } else if cli.canonical_form {
+        format!("FloatEncoding::shortest({expr})")
+    } else {
+        "FloatEncoding::Double".to_owned()
+    };
+    body.line(&format!(
+        "{serializer_use}.write_float_encoded({expr}, {encoding}){line_ender}"
+    ));
+}
+
 #[allow(clippy::too_many_arguments)]
 fn write_string_sz(
     body: &mut dyn CodeBlock,
@@ -3579,6 +4169,8 @@ fn write_string_sz(
 fn bounds_check_expr(p: Primitive, e: &str) -> String {
     match p {
         Primitive::Bool
+        | Primitive::Float
+        | Primitive::F16
         | Primitive::F32
         | Primitive::F64
         | Primitive::I8
@@ -3622,10 +4214,66 @@ fn bounds_check_if_block(
     bounds: &(Option<i128>, Option<i128>),
     e: &str,
     return_err: bool,
+) -> String {
+    failure_check_if_block("RangeCheck", " as isize", bounds, e, return_err)
+}
+
+// e must be an f64 expression. NaN is never within bounds.
+fn float_bounds_check_if_block(float_bounds: &FloatBounds, e: &str, return_err: bool) -> String {
+    let possible_return = if return_err { "return " } else { "" };
+    format!(
+        "if {} {{ {}Err(DeserializeFailure::FloatRangeCheck{{ found: {}, range: String::from(\"{}\") }}.into()) }}",
+        float_bounds.out_of_bounds_expr(e),
+        possible_return,
+        e,
+        float_bounds
+    )
+}
+
+// length check for arrays/maps e.g. from occurrence indicators like [1*3 uint]
+fn occurrence_check_if_block(
+    bounds: &(Option<i128>, Option<i128>),
+    e: &str,
+    return_err: bool,
+) -> String {
+    failure_check_if_block("OccurrenceCheck", "", bounds, e, return_err)
+}
+
+/// Bounds check for a (non-optional) value of type ty stored in var, if it has any bounds and one can be done inline.
+/// Only used in contexts that return the error (e.g. constructors)
+fn bounds_check_rust_type_if_block(ty: &RustType, var: &str) -> Option<String> {
+    if let Some(float_bounds) = &ty.config.float_bounds {
+        let check_expr = match ty.resolve_alias_shallow() {
+            ConceptualRustType::Primitive(Primitive::F16 | Primitive::F32) => {
+                format!("({var} as f64)")
+            }
+            _ => var.to_owned(),
+        };
+        return Some(float_bounds_check_if_block(float_bounds, &check_expr, true));
+    }
+    let bounds = ty.config.bounds.as_ref()?;
+    let check_expr = bounds_check_expr_rust_type(ty, var)?;
+    Some(match ty.resolve_alias_shallow() {
+        ConceptualRustType::Primitive(Primitive::N64) => {
+            bounds_check_if_block(&nint_bounds_to_u64(bounds), &check_expr, true)
+        }
+        ConceptualRustType::Array(_) | ConceptualRustType::Map(_, _) => {
+            occurrence_check_if_block(bounds, &check_expr, true)
+        }
+        _ => bounds_check_if_block(bounds, &check_expr, true),
+    })
+}
+
+fn failure_check_if_block(
+    failure: &str,
+    found_cast: &str,
+    bounds: &(Option<i128>, Option<i128>),
+    e: &str,
+    return_err: bool,
 ) -> String {
     let possible_return = if return_err { "return " } else { "" };
     format!(
-        "if {} {{ {}Err(DeserializeFailure::RangeCheck{{ found: {} as isize, min: {}, max: {}}}.into()) }}",
+        "if {} {{ {}Err(DeserializeFailure::{}{{ found: {}{}, min: {}, max: {}}}.into()) }}",
         match bounds {
             (Some(min), Some(max)) => format!("{e} < {min} || {e} > {max}"),
             (None, Some(max)) => format!("{e} > {max}"),
@@ -3633,7 +4281,9 @@ fn bounds_check_if_block(
             (None, None) => unreachable!(),
         },
         possible_return,
+        failure,
         e,
+        found_cast,
         if let Some(b) = bounds.0 {
             format!("Some({b})")
         } else {
@@ -4292,6 +4942,33 @@ fn make_err_annotate_block(annotation: &str, before: &str, after: &str) -> Block
     if_block
 }
 
+/// Checks (without consuming anything) if the next CBOR item is one of cbor_types.
+/// Evaluates to false at the end of the input or on an indefinite break.
+fn cbor_type_lookahead_cond(deserializer_name: &str, cbor_types: &[cbor_event::Type]) -> String {
+    let type_check = match cbor_types {
+        [cbor_type] => format!(
+            "cbor_event::Type::from(*byte) == {}",
+            cbor_type_code_str(*cbor_type)
+        ),
+        _ => format!(
+            "[{}].contains(&cbor_event::Type::from(*byte))",
+            cbor_types
+                .iter()
+                .map(|ty| cbor_type_code_str(*ty))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let break_check = if cbor_types.contains(&cbor_event::Type::Special) {
+        " && *byte != 0xff"
+    } else {
+        ""
+    };
+    format!(
+        "{deserializer_name}.as_mut_ref().fill_buf().ok().and_then(|buf| buf.first()).map(|byte: &u8| {type_check}{break_check}).unwrap_or(false)"
+    )
+}
+
 fn make_deser_loop(len_var: &str, len_expr: &str, cli: &Cli) -> Block {
     Block::new(format!(
         "while match {} {{ {} => {} < n, {} => true, }}",
@@ -4302,12 +4979,21 @@ fn make_deser_loop(len_var: &str, len_expr: &str, cli: &Cli) -> Block {
     ))
 }
 
-fn make_deser_loop_break_check() -> Block {
-    let mut break_check = Block::new("if raw.cbor_type()? == cbor_event::Type::Special");
-    // TODO: read special and go back 1 character
-    break_check.line("assert_eq!(raw.special()?, cbor_event::Special::Break);");
-    break_check.line("break;");
-    break_check
+/// * `elem_can_be_special` - If the element (or key for maps) can itself be a special value
+///   in which case we must peek at the raw byte to tell it apart from a break
+fn make_deser_loop_break_check(elem_can_be_special: bool) -> Block {
+    if elem_can_be_special {
+        let mut break_check = Block::new("if raw.cbor_type()? == cbor_event::Type::Special && raw.as_mut_ref().fill_buf().map_err(cbor_event::Error::from)?.first() == Some(&0xff)");
+        break_check.line("raw.special()?;");
+        break_check.line("break;");
+        break_check
+    } else {
+        let mut break_check = Block::new("if raw.cbor_type()? == cbor_event::Type::Special");
+        // TODO: read special and go back 1 character
+        break_check.line("assert_eq!(raw.special()?, cbor_event::Special::Break);");
+        break_check.line("break;");
+        break_check
+    }
 }
 
 pub fn table_type(cli: &Cli) -> &'static str {
@@ -4473,42 +5159,94 @@ impl EncodingField {
     }
 }
 
-fn key_encoding_field(name: &str, key: &FixedValue) -> EncodingField {
-    match key {
-        FixedValue::Text(_) => EncodingField {
-            field_name: format!("{name}_key_encoding"),
-            type_name: "StringEncoding".to_owned(),
-            default_expr: "StringEncoding::default()",
-            enc_conversion_before: "StringEncoding::from(",
-            enc_conversion_after: ")",
-            is_copy: false,
-            inner: Vec::new(),
-        },
-        FixedValue::Uint(_) => EncodingField {
-            field_name: format!("{name}_key_encoding"),
-            type_name: "Option<cbor_event::Sz>".to_owned(),
-            default_expr: "None",
-            enc_conversion_before: "Some(",
-            enc_conversion_after: ")",
-            is_copy: true,
-            inner: Vec::new(),
-        },
-        _ => unimplemented!(),
-    }
+// e.g. foo_key_encoding for { "foo": uint } and also foo_key_tag_encoding for { #6.42("foo") => uint }
+fn key_encoding_fields(
+    types: &IntermediateTypes,
+    name: &str,
+    key: &MapKey,
+    cli: &Cli,
+) -> Vec<EncodingField> {
+    encoding_fields_impl(
+        types,
+        &format!("{name}_key"),
+        (&key.to_rust_type()).into(),
+        cli,
+    )
 }
 
-fn encoding_fields(
+/// Encoding fields for the entries of the catch-all table of a map struct.
+/// This excludes the table's own length encoding as it doesn't have its own map.
+fn extra_encoding_fields(
     types: &IntermediateTypes,
-    name: &str,
-    ty: &RustType,
-    include_default: bool,
+    extra: &RustType,
     cli: &Cli,
 ) -> Vec<EncodingField> {
-    assert!(cli.preserve_encodings);
-    // TODO: how do we handle defaults for nested things? e.g. inside of a ConceptualRustType::Map
-    let mut encs = encoding_fields_impl(types, name, ty.into(), cli);
-    if include_default && ty.config.default.is_some() {
-        encs.push(EncodingField {
+    encoding_fields(types, "extra", &extra.clone().resolve_aliases(), false, cli)
+        .into_iter()
+        .filter(|enc| enc.field_name != "extra_encoding")
+        .collect()
+}
+
+/// Deserializes an entry of a map struct's catch-all table inside of a match arm with header {arm}.
+/// If {rewind} then the key was already read and we need to go back to key_position first.
+#[allow(clippy::too_many_arguments)]
+fn deserialize_extra_entry(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    key_type: &RustType,
+    value_type: &RustType,
+    extra_index: usize,
+    arm: &str,
+    rewind: bool,
+    cli: &Cli,
+) -> Block {
+    let mut entry_block = Block::new(arm);
+    // extra entries are like optional fields in that we don't know how many there are beforehand
+    entry_block.line("read_len.read_elems(1)?;");
+    if rewind {
+        entry_block.line("raw.as_mut_ref().seek(SeekFrom::Start(key_position)).map_err(cbor_event::Error::from)?;");
+    }
+    push_extra_entry_deserialization(
+        gen_scope,
+        types,
+        key_type,
+        value_type,
+        extra_index,
+        &mut entry_block,
+        cli,
+    );
+    entry_block.after(",");
+    entry_block
+}
+
+/// Reads an entry of a map struct's catch-all table into extra_table
+fn push_extra_entry_deserialization(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    key_type: &RustType,
+    value_type: &RustType,
+    extra_index: usize,
+    body: &mut Block,
+    cli: &Cli,
+) {
+    gen_scope.generate_deserialize_map_entry(types, key_type, value_type, "extra", None, body, cli);
+    if cli.preserve_encodings {
+        body.line(&format!("orig_deser_order.push({extra_index});"));
+    }
+}
+
+fn encoding_fields(
+    types: &IntermediateTypes,
+    name: &str,
+    ty: &RustType,
+    include_default: bool,
+    cli: &Cli,
+) -> Vec<EncodingField> {
+    assert!(cli.preserve_encodings);
+    // TODO: how do we handle defaults for nested things? e.g. inside of a ConceptualRustType::Map
+    let mut encs = encoding_fields_impl(types, name, ty.into(), cli);
+    if include_default && ty.config.default.is_some() {
+        encs.push(EncodingField {
             field_name: format!("{name}_default_present"),
             type_name: "bool".to_owned(),
             default_expr: "false",
@@ -4529,7 +5267,7 @@ fn encoding_fields_impl(
 ) -> Vec<EncodingField> {
     assert!(cli.preserve_encodings);
     match ty {
-        SerializingRustType::Root(ConceptualRustType::Array(elem_ty), _cfg) => {
+        SerializingRustType::Root(ConceptualRustType::Array(elem_ty), cfg) => {
             let base = EncodingField {
                 field_name: format!("{name}_encoding"),
                 type_name: "LenEncoding".to_owned(),
@@ -4541,8 +5279,10 @@ fn encoding_fields_impl(
             };
             let inner_encs =
                 encoding_fields_impl(types, &format!("{name}_elem"), (&**elem_ty).into(), cli);
+            // embedded sequences have no length of their own to encode
+            let base = if cfg.embedded_seq { None } else { Some(base) };
             if inner_encs.is_empty() {
-                vec![base]
+                base.into_iter().collect()
             } else {
                 let type_name_elem = if inner_encs.len() == 1 {
                     inner_encs.first().unwrap().type_name.clone()
@@ -4556,9 +5296,8 @@ fn encoding_fields_impl(
                             .join(", ")
                     )
                 };
-                vec![
-                    base,
-                    EncodingField {
+                base.into_iter()
+                    .chain(std::iter::once(EncodingField {
                         field_name: format!("{name}_elem_encodings"),
                         type_name: format!("Vec<{type_name_elem}>"),
                         default_expr: "Vec::new()",
@@ -4566,8 +5305,8 @@ fn encoding_fields_impl(
                         enc_conversion_after: "",
                         is_copy: false,
                         inner: inner_encs,
-                    },
-                ]
+                    }))
+                    .collect()
             }
         }
         SerializingRustType::Root(ConceptualRustType::Map(k, v), _cfg) => {
@@ -4659,9 +5398,7 @@ fn encoding_fields_impl(
             | Primitive::U8
             | Primitive::U16
             | Primitive::U32
-            | Primitive::U64
-            | Primitive::F32
-            | Primitive::F64 => vec![EncodingField {
+            | Primitive::U64 => vec![EncodingField {
                 field_name: format!("{name}_encoding"),
                 type_name: "Option<cbor_event::Sz>".to_owned(),
                 default_expr: "None",
@@ -4670,14 +5407,28 @@ fn encoding_fields_impl(
                 is_copy: true,
                 inner: Vec::new(),
             }],
+            Primitive::Float => vec![EncodingField {
+                field_name: format!("{name}_encoding"),
+                type_name: "Option<FloatEncoding>".to_owned(),
+                default_expr: "None",
+                enc_conversion_before: "Some(",
+                enc_conversion_after: ")",
+                is_copy: true,
+                inner: Vec::new(),
+            }],
             Primitive::Bool =>
             /* bool only has 1 encoding */
             {
                 vec![]
             }
+            // float16/32/64 only allow their own width
+            Primitive::F16 | Primitive::F32 | Primitive::F64 => vec![],
         },
         SerializingRustType::Root(ConceptualRustType::Fixed(f), _cfg) => match f {
-            FixedValue::Bool(_) | FixedValue::Null => vec![],
+            FixedValue::Bool(_)
+            | FixedValue::Null
+            | FixedValue::Undefined
+            | FixedValue::Simple(_) => vec![],
             FixedValue::Nint(_) => encoding_fields_impl(
                 types,
                 name,
@@ -4693,7 +5444,7 @@ fn encoding_fields_impl(
             FixedValue::Float(_) => encoding_fields_impl(
                 types,
                 name,
-                (&ConceptualRustType::Primitive(Primitive::F64)).into(),
+                (&ConceptualRustType::Primitive(Primitive::Float)).into(),
                 cli,
             ),
             FixedValue::Text(_) => encoding_fields_impl(
@@ -4702,6 +5453,12 @@ fn encoding_fields_impl(
                 (&ConceptualRustType::Primitive(Primitive::Str)).into(),
                 cli,
             ),
+            FixedValue::Bytes(_) => encoding_fields_impl(
+                types,
+                name,
+                (&ConceptualRustType::Primitive(Primitive::Bytes)).into(),
+                cli,
+            ),
         },
         SerializingRustType::Root(ConceptualRustType::Alias(_, ty), _cfg) => {
             encoding_fields_impl(types, name, (&**ty).into(), cli)
@@ -4748,6 +5505,22 @@ fn encoding_fields_impl(
             encs.append(&mut encoding_fields_impl(types, name, *child, cli));
             encs
         }
+        SerializingRustType::EncodingOperation(CBOREncodingOperation::CBORSeqBytes, child) => {
+            let mut encs = encoding_fields_impl(
+                types,
+                &format!("{name}_bytes"),
+                (&ConceptualRustType::Primitive(Primitive::Bytes)).into(),
+                cli,
+            );
+            // there is no array header so we only keep the element encodings
+            let len_encoding = format!("{name}_encoding");
+            encs.extend(
+                encoding_fields_impl(types, name, *child, cli)
+                    .into_iter()
+                    .filter(|enc| enc.field_name != len_encoding),
+            );
+            encs
+        }
     }
 }
 
@@ -4902,6 +5675,38 @@ fn generate_array_struct_deserialization(
     let mut deser_ctor_fields = vec![];
     let mut encoding_struct_ctor_fields = vec![];
     for (field_index, field) in record.fields.iter().enumerate() {
+        if field.rust_type.is_embedded_seq() {
+            // we read elements until we find one of a different type so anything that could
+            // come right after this (skipping anything possibly not present) must be distinguishable
+            let elem_cbor_types = field.rust_type.cbor_types(types);
+            for next_field in record.fields.iter().skip(field_index + 1) {
+                if next_field
+                    .rust_type
+                    .cbor_types(types)
+                    .iter()
+                    .any(|ct| elem_cbor_types.contains(ct))
+                {
+                    panic!(
+                        "{}: ambiguous array layout. {} can take a variable number of elements so its elements must not have the same CBOR type(s) as the field(s) right after it but {} overlaps: {:?}",
+                        name,
+                        field.name,
+                        next_field.name,
+                        elem_cbor_types
+                    );
+                }
+                let possibly_absent = next_field.optional
+                    || (next_field.rust_type.is_embedded_seq()
+                        && next_field
+                            .rust_type
+                            .config
+                            .bounds
+                            .map(|(min, _max)| min.is_none())
+                            .unwrap_or(true));
+                if !possibly_absent {
+                    break;
+                }
+            }
+        }
         let (before, after) = if cli.preserve_encodings {
             let var_names_str = encoding_var_names_str(types, &field.name, &field.rust_type, cli);
             if cli.annotate_fields {
@@ -5032,12 +5837,8 @@ fn generate_array_struct_deserialization(
                 } else {
                     (Cow::from("Some"), Cow::from("None"))
                 };
-                let mut deser_config = DeserializeConfig::new(&field.name)
-                    .in_embedded(in_embedded)
+                let deser_config = DeserializeConfig::for_field(&field.name, field, in_embedded)
                     .optional_field(true);
-                if let Some(custom_deserialize) = &field.rule_metadata.custom_deserialize {
-                    deser_config = deser_config.custom_deserialize(custom_deserialize.clone());
-                }
                 gen_scope
                     .generate_deserialize(
                         types,
@@ -5051,12 +5852,8 @@ fn generate_array_struct_deserialization(
                     .add_to_code(&mut deser_code);
                 type_check_else.line(format!("Ok({defaults})"));
             } else {
-                let mut deser_config = DeserializeConfig::new(&field.name)
-                    .in_embedded(in_embedded)
+                let deser_config = DeserializeConfig::for_field(&field.name, field, in_embedded)
                     .optional_field(true);
-                if let Some(custom_deserialize) = &field.rule_metadata.custom_deserialize {
-                    deser_config = deser_config.custom_deserialize(custom_deserialize.clone());
-                }
                 gen_scope
                     .generate_deserialize(
                         types,
@@ -5074,10 +5871,7 @@ fn generate_array_struct_deserialization(
         } else {
             // mandatory fields
             if cli.annotate_fields {
-                let mut deser_config = DeserializeConfig::new(&field.name).in_embedded(in_embedded);
-                if let Some(custom_deserialize) = &field.rule_metadata.custom_deserialize {
-                    deser_config = deser_config.custom_deserialize(custom_deserialize.clone());
-                }
+                let deser_config = DeserializeConfig::for_field(&field.name, field, in_embedded);
                 gen_scope
                     .generate_deserialize(
                         types,
@@ -5089,10 +5883,7 @@ fn generate_array_struct_deserialization(
                     .annotate(&field.name, before.as_ref(), after.as_ref())
                     .add_to_code(&mut deser_code);
             } else {
-                let mut deser_config = DeserializeConfig::new(&field.name).in_embedded(in_embedded);
-                if let Some(custom_deserialize) = &field.rule_metadata.custom_deserialize {
-                    deser_config = deser_config.custom_deserialize(custom_deserialize.clone());
-                }
+                let deser_config = DeserializeConfig::for_field(&field.name, field, in_embedded);
                 gen_scope
                     .generate_deserialize(
                         types,
@@ -5153,7 +5944,7 @@ fn codegen_struct(
     let new_can_fail = record
         .fields
         .iter()
-        .any(|f| !f.optional && f.rust_type.config.bounds.is_some());
+        .any(|f| !f.optional && f.rust_type.has_bounds());
     // wasm wrapper
     if cli.wasm {
         let mut wrapper = create_base_wasm_wrapper(gen_scope, types, name, true, cli);
@@ -5177,22 +5968,12 @@ fn codegen_struct(
                         .arg(&field.name, field.rust_type.for_wasm_param(types))
                         .vis("pub");
                     // don't call needs_bounds_check_if_inlined() since if it's a RustType it's checked during that ctor
-                    if let Some(bounds) = field.rust_type.config.bounds.as_ref() {
+                    if field.rust_type.has_bounds() {
                         setter.ret("Result<(), JsError>");
-                        if let Some(check_expr) =
-                            bounds_check_expr_rust_type(&field.rust_type, &field.name)
+                        if let Some(check) =
+                            bounds_check_rust_type_if_block(&field.rust_type, &field.name)
                         {
-                            if let ConceptualRustType::Primitive(Primitive::N64) =
-                                field.rust_type.resolve_alias_shallow()
-                            {
-                                setter.line(bounds_check_if_block(
-                                    &nint_bounds_to_u64(bounds),
-                                    &check_expr,
-                                    true,
-                                ));
-                            } else {
-                                setter.line(bounds_check_if_block(bounds, &check_expr, true));
-                            }
+                            setter.line(check);
                         }
                     }
                     if field.rust_type.config.default.is_some() {
@@ -5272,6 +6053,31 @@ fn codegen_struct(
                 }
             }
         }
+        if let Some(extra) = &record.extra {
+            // setter
+            let mut setter = codegen::Function::new("set_extra");
+            setter
+                .arg_mut_self()
+                .arg("extra", extra.for_wasm_param(types))
+                .vis("pub")
+                .line(format!(
+                    "self.0.extra = {}",
+                    ToWasmBoundaryOperations::format(
+                        extra
+                            .from_wasm_boundary_clone(types, "extra", false)
+                            .into_iter()
+                    )
+                ));
+            wrapper.s_impl.push_fn(setter);
+            // getter
+            let mut getter = codegen::Function::new("extra");
+            getter
+                .arg_ref_self()
+                .ret(extra.for_wasm_return(types))
+                .vis("pub")
+                .line(extra.to_wasm_boundary(types, "self.0.extra", false));
+            wrapper.s_impl.push_fn(getter);
+        }
         if new_can_fail {
             wasm_new.line(format!(
                 "{}::new({}).map(Into::into).map_err(Into::into)",
@@ -5330,6 +6136,15 @@ fn codegen_struct(
         }
         // Fixed values only exist in (de)serialization code (outside of preserve-encodings=true)
         if !field.rust_type.is_fixed_value() {
+            let field_decl = if config.custom_json {
+                format!("pub {}", field.name)
+            } else {
+                format!(
+                    "{}pub {}",
+                    json_schema_length_macro(&field.rust_type, cli),
+                    field.name
+                )
+            };
             let mut codegen_field = if let Some(default_value) = &field.rust_type.config.default {
                 // new
                 native_new_block.line(format!(
@@ -5339,7 +6154,7 @@ fn codegen_struct(
                 ));
                 // field
                 codegen::Field::new(
-                    &format!("pub {}", field.name),
+                    &field_decl,
                     field.rust_type.for_rust_member(types, false, cli),
                 )
             } else if field.optional {
@@ -5347,7 +6162,7 @@ fn codegen_struct(
                 native_new_block.line(format!("{}: None,", field.name));
                 // field
                 codegen::Field::new(
-                    &format!("pub {}", field.name),
+                    &field_decl,
                     format!(
                         "Option<{}>",
                         field.rust_type.for_rust_member(types, false, cli)
@@ -5361,26 +6176,16 @@ fn codegen_struct(
                 }
                 new_arg_count += 1;
                 native_new_block.line(format!("{},", field.name));
-                if let Some(bounds) = field.rust_type.config.bounds.as_ref() {
-                    if let Some(check_expr) =
-                        bounds_check_expr_rust_type(&field.rust_type, &field.name)
+                if field.rust_type.has_bounds() {
+                    if let Some(check) =
+                        bounds_check_rust_type_if_block(&field.rust_type, &field.name)
                     {
-                        if let ConceptualRustType::Primitive(Primitive::N64) =
-                            field.rust_type.resolve_alias_shallow()
-                        {
-                            native_new.line(bounds_check_if_block(
-                                &nint_bounds_to_u64(bounds),
-                                &check_expr,
-                                true,
-                            ));
-                        } else {
-                            native_new.line(bounds_check_if_block(bounds, &check_expr, true));
-                        }
+                        native_new.line(check);
                     }
                 }
                 // field
                 codegen::Field::new(
-                    &format!("pub {}", field.name),
+                    &field_decl,
                     field.rust_type.for_rust_member(types, false, cli),
                 )
             };
@@ -5390,6 +6195,22 @@ fn codegen_struct(
             native_struct.push_field(codegen_field);
         }
     }
+    if let Some(extra) = &record.extra {
+        if !gen_scope.deserialize_generated_for_type(types, &extra.conceptual_type) {
+            gen_scope.dont_generate_deserialize(
+                name,
+                format!(
+                    "extra: {} couldn't generate deserialize",
+                    extra.for_rust_member(types, false, cli)
+                ),
+            );
+        }
+        native_new_block.line(format!("extra: {}::new(),", table_type(cli)));
+        let mut extra_field =
+            codegen::Field::new("pub extra", extra.for_rust_member(types, false, cli));
+        extra_field.doc("Entries for any other keys not covered by the fields above");
+        native_struct.push_field(extra_field);
+    }
     if !native_new_comments.is_empty() {
         native_new.doc(native_new_comments.join("\n"));
     }
@@ -5426,9 +6247,20 @@ fn codegen_struct(
                     field_enc.type_name,
                 );
             }
-            if record.rep == Representation::Map {
-                let key_enc = key_encoding_field(&field.name, field.key.as_ref().unwrap());
-                encoding_struct.field(&format!("pub {}", key_enc.field_name), key_enc.type_name);
+            // embedded plain groups in maps have no key of their own
+            if let Some(key) = &field.key {
+                for key_enc in key_encoding_fields(types, &field.name, key, cli) {
+                    encoding_struct
+                        .field(&format!("pub {}", key_enc.field_name), key_enc.type_name);
+                }
+            }
+        }
+        if let Some(extra) = &record.extra {
+            for extra_enc in extra_encoding_fields(types, extra, cli) {
+                encoding_struct.field(
+                    &format!("pub {}", extra_enc.field_name),
+                    extra_enc.type_name,
+                );
             }
         }
 
@@ -5513,8 +6345,7 @@ fn codegen_struct(
                 deser_ctor
             }
             Representation::Map => {
-                let mut uint_field_deserializers = Vec::new();
-                let mut text_field_deserializers = Vec::new();
+                let mut key_deserializers = MapKeyDeserializers::default();
                 // (field_index, field, content) -- this is ordered by canonical order
                 let mut ser_content: Vec<(usize, &RustField, BlocksOrLines)> = Vec::new();
                 if cli.preserve_encodings {
@@ -5522,55 +6353,70 @@ fn codegen_struct(
                         .content
                         .line("let mut orig_deser_order = Vec::new();");
                 }
-                // we default to canonical ordering here as the default ordering as that should be the most useful
-                // keep in mind this is always overwritten if you have cli.preserve_encodings enabled AND there was
-                // a deserialized encoding, otherwise we still use this by default.
-                for (field_index, field) in record.canonical_ordering() {
-                    // to support maps with plain groups inside is very difficult as we cannot guarantee
-                    // the order of fields so foo = {a, b, bar}, bar = (c, d) could have the order be
-                    // {a, d, c, b}, {c, a, b, d}, etc which doesn't fit with the nature of deserialize_as_embedded_group
-                    // A possible solution would be to take all fields into one big map, either in generation to begin with,
-                    // or just for deserialization then constructing at the end with locals like a, b, bar_c, bar_d.
-                    if let ConceptualRustType::Rust(ident) = &field.rust_type.conceptual_type {
-                        if types.is_plain_group(ident) {
-                            gen_scope.dont_generate_deserialize(
-                                name,
-                                format!(
-                                    "Map with plain group field {}: {}",
-                                    field.name,
-                                    field.rust_type.for_rust_member(types, false, cli)
-                                ),
-                            );
+                // catch-all table for any keys not covered by the fields e.g. { 1: uint, * uint => any }
+                let extra_entry = record
+                    .extra
+                    .as_ref()
+                    .map(|extra| match &extra.conceptual_type {
+                        ConceptualRustType::Map(key_type, value_type) => {
+                            (&**key_type, &**value_type)
                         }
-                    }
-                    // declare variables for deser loop
+                        _ => unreachable!("extra table must be a map: {:?}", extra),
+                    });
+                // this is after all the fields in orig_deser_order
+                let extra_index = record.fields.len();
+                let extra_ser_config = SerializeConfig::new("self.extra", "extra")
+                    .encoding_var_in_option_struct("self.encodings");
+                if let Some(extra) = &record.extra {
+                    deser_code.read_len_used = true;
+                    deser_code.content.line(&format!(
+                        "let mut extra_table = {}::new();",
+                        table_type(cli)
+                    ));
                     if cli.preserve_encodings {
-                        for field_enc in encoding_fields(
-                            types,
-                            &field.name,
-                            &field.rust_type.clone().resolve_aliases(),
-                            true,
-                            cli,
-                        ) {
+                        for extra_enc in extra_encoding_fields(types, extra, cli) {
                             deser_code.content.line(&format!(
                                 "let mut {} = {};",
-                                field_enc.field_name, field_enc.default_expr
+                                extra_enc.field_name, extra_enc.default_expr
                             ));
                         }
-                        let key_enc = key_encoding_field(&field.name, field.key.as_ref().unwrap());
-                        deser_code.content.line(&format!(
-                            "let mut {} = {};",
-                            key_enc.field_name, key_enc.default_expr
-                        ));
                     }
-                    if field.rust_type.is_fixed_value() {
-                        deser_code
-                            .content
-                            .line(&format!("let mut {}_present = false;", field.name));
+                }
+                // we default to canonical ordering here as the default ordering as that should be the most useful
+                // keep in mind this is always overwritten if you have cli.preserve_encodings enabled AND there was
+                // a deserialized encoding, otherwise we still use this by default.
+                for (field_index, field) in record.canonical_ordering() {
+                    let orig_deser_orders = [(String::from("orig_deser_order"), field_index)];
+                    if field.key.is_some() {
+                        generate_map_field_deserialization(
+                            gen_scope,
+                            types,
+                            name,
+                            field,
+                            &field.name,
+                            &orig_deser_orders,
+                            in_embedded,
+                            &mut deser_code,
+                            &mut key_deserializers,
+                            extra_entry,
+                            extra_index,
+                            cli,
+                        );
                     } else {
-                        deser_code
-                            .content
-                            .line(&format!("let mut {} = None;", field.name));
+                        generate_embedded_map_group_deserialization(
+                            gen_scope,
+                            types,
+                            name,
+                            field,
+                            &field.name,
+                            &orig_deser_orders,
+                            in_embedded,
+                            &mut deser_code,
+                            &mut key_deserializers,
+                            extra_entry,
+                            extra_index,
+                            cli,
+                        );
                     }
                     let (data_name, expr_is_ref) =
                         if field.optional && field.rust_type.config.default.is_none() {
@@ -5579,278 +6425,25 @@ fn codegen_struct(
                             (format!("self.{}", field.name), false)
                         };
 
-                    let key = field.key.clone().unwrap();
-                    // deserialize key + value
-                    let mut deser_block = match &key {
-                        FixedValue::Uint(x) => {
-                            if cli.preserve_encodings {
-                                Block::new(format!("({x}, key_enc) => "))
-                            } else {
-                                Block::new(format!("{x} => "))
-                            }
-                        }
-                        FixedValue::Text(x) => Block::new(format!("\"{x}\" => ")),
-                        _ => panic!(
-                            "unsupported map key type for {}.{}: {:?}",
-                            name, field.name, key
-                        ),
-                    };
-                    deser_block.after(",");
-                    let mut deser_block_code = DeserializationCode::default();
-                    let key_in_rust = match &key {
-                        FixedValue::Uint(x) => format!("Key::Uint({x})"),
-                        FixedValue::Text(x) => format!("Key::Str(\"{x}\".into())"),
-                        _ => unimplemented!(),
-                    };
-                    if cli.preserve_encodings {
-                        let mut dup_check = if field.rust_type.is_fixed_value() {
-                            Block::new(format!("if {}_present", field.name))
-                        } else {
-                            Block::new(format!("if {}.is_some()", field.name))
-                        };
-                        dup_check.line(&format!(
-                            "return Err(DeserializeFailure::DuplicateKey({key_in_rust}).into());"
-                        ));
-                        deser_block_code.content.push_block(dup_check);
-
-                        let temp_var_prefix = format!("tmp_{}", field.name);
-                        let var_names_str =
-                            encoding_var_names_str(types, &temp_var_prefix, &field.rust_type, cli);
-                        if cli.annotate_fields {
-                            let (before, after) = if var_names_str.is_empty() {
-                                ("".to_owned(), "?")
-                            } else {
-                                (format!("let {var_names_str} = "), "?;")
-                            };
-                            let mut deser_config = DeserializeConfig::new(&field.name)
-                                .in_embedded(in_embedded)
-                                .optional_field(field.optional);
-                            if let Some(custom_deserialize) =
-                                &field.rule_metadata.custom_deserialize
-                            {
-                                deser_config =
-                                    deser_config.custom_deserialize(custom_deserialize.clone());
-                            }
-                            gen_scope
-                                .generate_deserialize(
-                                    types,
-                                    (&field.rust_type).into(),
-                                    DeserializeBeforeAfter::new("", "", true),
-                                    deser_config,
-                                    cli,
-                                )
-                                .annotate(&field.name, &before, after)
-                                .add_to_code(&mut deser_block_code);
-                        } else {
-                            let (before, after) = if var_names_str.is_empty() {
-                                ("".to_owned(), "")
-                            } else {
-                                (format!("let {var_names_str} = "), ";")
-                            };
-                            let mut deser_config = DeserializeConfig::new(&field.name)
-                                .in_embedded(in_embedded)
-                                .optional_field(field.optional);
-                            if let Some(custom_deserialize) =
-                                &field.rule_metadata.custom_deserialize
-                            {
-                                deser_config =
-                                    deser_config.custom_deserialize(custom_deserialize.clone());
-                            }
-                            gen_scope
-                                .generate_deserialize(
-                                    types,
-                                    (&field.rust_type).into(),
-                                    DeserializeBeforeAfter::new(&before, after, false),
-                                    deser_config,
-                                    cli,
-                                )
-                                .add_to_code(&mut deser_block_code);
-                        }
-                        // Due to destructuring assignemnt (RFC 372 / 71156) being unstable we're forced to use temporaries then reassign after
-                        // which is not ideal but doing the assignment inside the lambda or otherwise has issues where it's putting lots of
-                        // context-sensitive logic into generate_deserialize and you would need to declare temporaries in most cases anyway
-                        // as cbor_event encoding-aware functions return tuples which just pushes the problem there instead.
-                        // We might be able to write a nice way around this in the annotate_fields=false, preserve_encodings=true case
-                        // but I don't think anyone (or many) would care about this as it's incredibly niche
-                        // (annotate_fields=false would be for minimizing code size but then preserve_encodings=true generates way more code)
-                        if field.rust_type.is_fixed_value() {
-                            deser_block_code
-                                .content
-                                .line(&format!("{}_present = true;", field.name));
-                        } else {
-                            deser_block_code
-                                .content
-                                .line(&format!("{} = Some(tmp_{});", field.name, field.name));
-                        }
-                        for enc_field in encoding_fields(
-                            types,
-                            &field.name,
-                            &field.rust_type.clone().resolve_aliases(),
-                            false,
-                            cli,
-                        ) {
-                            deser_block_code.content.line(&format!(
-                                "{} = tmp_{};",
-                                enc_field.field_name, enc_field.field_name
-                            ));
-                        }
-                    } else if field.rust_type.is_fixed_value() {
-                        let mut dup_check = Block::new(format!("if {}_present", field.name));
-                        dup_check.line(&format!(
-                            "return Err(DeserializeFailure::DuplicateKey({key_in_rust}).into());"
-                        ));
-                        deser_block_code.content.push_block(dup_check);
-                        // only does verification and sets the field_present bool to do error checking later
-                        if cli.annotate_fields {
-                            let mut deser_config = DeserializeConfig::new(&field.name)
-                                .in_embedded(in_embedded)
-                                .optional_field(field.optional);
-                            if let Some(custom_deserialize) =
-                                &field.rule_metadata.custom_deserialize
-                            {
-                                deser_config =
-                                    deser_config.custom_deserialize(custom_deserialize.clone());
-                            }
-                            let mut err_deser = gen_scope.generate_deserialize(
-                                types,
-                                (&field.rust_type).into(),
-                                DeserializeBeforeAfter::new("", "", false),
-                                deser_config,
-                                cli,
-                            );
-                            err_deser.content.line("Ok(true)");
-                            err_deser
-                                .annotate(&field.name, &format!("{}_present = ", field.name), "?;")
-                                .add_to_code(&mut deser_block_code);
-                        } else {
-                            let mut deser_config = DeserializeConfig::new(&field.name)
-                                .in_embedded(in_embedded)
-                                .optional_field(field.optional);
-                            if let Some(custom_deserialize) =
-                                &field.rule_metadata.custom_deserialize
-                            {
-                                deser_config =
-                                    deser_config.custom_deserialize(custom_deserialize.clone());
-                            }
-                            gen_scope
-                                .generate_deserialize(
-                                    types,
-                                    (&field.rust_type).into(),
-                                    DeserializeBeforeAfter::new("", "", false),
-                                    deser_config,
-                                    cli,
-                                )
-                                .add_to_code(&mut deser_block_code);
-                            deser_block_code
-                                .content
-                                .line(&format!("{}_present = true;", field.name));
-                        }
-                    } else {
-                        let mut dup_check = Block::new(format!("if {}.is_some()", field.name));
-                        dup_check.line(&format!(
-                            "return Err(DeserializeFailure::DuplicateKey({key_in_rust}).into());"
-                        ));
-                        deser_block_code.content.push_block(dup_check);
-                        if cli.annotate_fields {
-                            let mut deser_config = DeserializeConfig::new(&field.name)
-                                .in_embedded(in_embedded)
-                                .optional_field(field.optional);
-                            if let Some(custom_deserialize) =
-                                &field.rule_metadata.custom_deserialize
-                            {
-                                deser_config =
-                                    deser_config.custom_deserialize(custom_deserialize.clone());
-                            }
-                            gen_scope
-                                .generate_deserialize(
-                                    types,
-                                    (&field.rust_type).into(),
-                                    DeserializeBeforeAfter::new("", "", true),
-                                    deser_config,
-                                    cli,
-                                )
-                                .annotate(&field.name, &format!("{} = Some(", field.name), "?);")
-                                .add_to_code(&mut deser_block_code);
-                        } else {
-                            let mut deser_config = DeserializeConfig::new(&field.name)
-                                .in_embedded(in_embedded)
-                                .optional_field(field.optional);
-                            if let Some(custom_deserialize) =
-                                &field.rule_metadata.custom_deserialize
-                            {
-                                deser_config =
-                                    deser_config.custom_deserialize(custom_deserialize.clone());
-                            }
-                            gen_scope
-                                .generate_deserialize(
-                                    types,
-                                    (&field.rust_type).into(),
-                                    DeserializeBeforeAfter::new(
-                                        &format!("{} = Some(", field.name),
-                                        ");",
-                                        false,
-                                    ),
-                                    deser_config,
-                                    cli,
-                                )
-                                .add_to_code(&mut deser_block_code);
-                        }
-                    }
-                    if cli.preserve_encodings {
-                        let key_encoding = key_encoding_field(&field.name, &key);
-                        deser_block_code
-                            .content
-                            .line(&format!(
-                                "{} = {};",
-                                key_encoding.field_name,
-                                key_encoding.enc_conversion("key_enc")
-                            ))
-                            .line(&format!("orig_deser_order.push({field_index});"));
-                    }
-
                     // serialize key
                     let mut map_ser_content = BlocksOrLines::default();
                     let serialize_config = SerializeConfig::new(&data_name, &field.name)
                         .expr_is_ref(expr_is_ref)
                         .encoding_var_in_option_struct("self.encodings");
-                    let key_encoding_var =
-                        serialize_config.encoding_var(Some("key"), key.encoding_var_is_copy(types));
-
-                    deser_block
-                        .push_all(deser_block_code.mark_and_extract_content(&mut deser_code));
-                    match &key {
-                        FixedValue::Uint(x) => {
-                            let expr = format!("{x}u64");
-                            write_using_sz(
-                                &mut map_ser_content,
-                                "write_unsigned_integer",
-                                "serializer",
-                                &expr,
-                                &expr,
-                                "?;",
-                                &key_encoding_var,
-                                cli,
-                            );
-                            uint_field_deserializers.push(deser_block);
-                        }
-                        FixedValue::Text(s) => {
-                            write_string_sz(
-                                &mut map_ser_content,
-                                "write_text",
-                                "serializer",
-                                &format!("\"{s}\""),
-                                false,
-                                "?;",
-                                &key_encoding_var,
-                                cli,
-                            );
-                            text_field_deserializers.push(deser_block);
-                        }
-                        _ => panic!(
-                            "unsupported map key type for {}.{}: {:?}",
-                            name, field.name, key
-                        ),
-                    };
+                    // embedded plain groups (no key) serialize their own keys
+                    if let Some(key) = &field.key {
+                        // keys are serialized as their literal value with encoding vars named as per key_encoding_fields()
+                        let key_serialize_config =
+                            SerializeConfig::new("", format!("{}_key", field.name))
+                                .encoding_var_in_option_struct("self.encodings");
+                        gen_scope.generate_serialize(
+                            types,
+                            (&key.to_rust_type()).into(),
+                            &mut map_ser_content,
+                            key_serialize_config,
+                            cli,
+                        );
+                    }
 
                     // serialize value
                     gen_scope.generate_serialize(
@@ -5862,6 +6455,8 @@ fn codegen_struct(
                     );
                     ser_content.push((field_index, field, map_ser_content));
                 }
+                let mut entries_ser = BlocksOrLines::default();
+                let has_embedded_groups = record.fields.iter().any(|field| field.key.is_none());
                 if cli.preserve_encodings {
                     let (check_canonical, serialization_order) = if cli.canonical_form {
                         let indices_str = record
@@ -5874,13 +6469,26 @@ fn codegen_struct(
                     } else {
                         ("", format!("(0..{}).collect()", ser_content.len()))
                     };
-                    ser_func.line(format!(
-                    "let deser_order = self.encodings.as_ref().filter(|encs| {}encs.orig_deser_order.len() == {}).map(|encs| encs.orig_deser_order.clone()).unwrap_or_else(|| {});",
-                    check_canonical,
-                    record.definite_info("self", false, types, cli),
-                    serialization_order));
-                    let mut ser_loop = Block::new("for field_index in deser_order");
-                    let mut ser_loop_match = Block::new("match field_index");
+                    // embedded plain groups have their index in orig_deser_order once for each of their entries
+                    // as those can be interleaved with the other entries of the map
+                    let orig_deser_order_len = record.definite_info("self", false, types, cli);
+                    let orig_deser_order = format!(
+                        "self.encodings.as_ref().filter(|encs| {check_canonical}encs.orig_deser_order.len() == {orig_deser_order_len}).map(|encs| encs.orig_deser_order.clone())"
+                    );
+                    if has_embedded_groups {
+                        entries_ser.line(&format!("let orig_deser_order = {orig_deser_order};"));
+                        // without the original order there's nothing to interleave them by
+                        entries_ser.line("let embedded_entries_per_index = if orig_deser_order.is_some() { 1 } else { usize::MAX };");
+                        entries_ser.line(&format!(
+                            "let deser_order = orig_deser_order.unwrap_or_else(|| {serialization_order});"
+                        ));
+                    } else {
+                        entries_ser.line(&format!(
+                            "let deser_order = {orig_deser_order}.unwrap_or_else(|| {serialization_order});"
+                        ));
+                    }
+                    let mut ser_loop = Block::new("for field_index in deser_order");
+                    let mut ser_loop_match = Block::new("match field_index");
                     for (field_index, field, content) in ser_content.into_iter() {
                         // TODO: while this would be nice we would need to either:
                         // 1) know this before we call gen_scope.generate_serialize() OR
@@ -5889,6 +6497,33 @@ fn codegen_struct(
                         //    ser_loop_match.line(format!("{} => {},"));
                         //} else {
                         //}
+                        if field.key.is_none() {
+                            // the embedded group is serialized beforehand to be able to write its entries one by one
+                            let mut embedded_ser_block =
+                                Block::new(format!("let {}_bytes =", field.name));
+                            embedded_ser_block
+                                .line(format!(
+                                    "let mut {}_se = Serializer::new_vec();",
+                                    field.name
+                                ))
+                                .line(format!("let serializer = &mut {}_se;", field.name));
+                            embedded_ser_block.push_all(content);
+                            embedded_ser_block
+                                .line(format!("{}_se.finalize()", field.name))
+                                .after(";");
+                            entries_ser.push_block(embedded_ser_block);
+                            entries_ser.line(&format!(
+                                "let mut {}_entries = split_map_entries(&{}_bytes)?.into_iter();",
+                                field.name, field.name
+                            ));
+                            let mut embedded_entries_block = Block::new(format!(
+                                "{field_index} => for entry in {}_entries.by_ref().take(embedded_entries_per_index)",
+                                field.name
+                            ));
+                            embedded_entries_block.line("serializer.write_raw_bytes(entry)?;");
+                            ser_loop_match.push_block(embedded_entries_block);
+                            continue;
+                        }
                         let mut field_ser_block =
                             if field.optional && field.rust_type.config.default.is_none() {
                                 Block::new(format!(
@@ -5901,9 +6536,46 @@ fn codegen_struct(
                         field_ser_block.push_all(content);
                         ser_loop_match.push_block(field_ser_block);
                     }
+                    if let Some((key_type, value_type)) = extra_entry {
+                        let mut extra_ser_block = Block::new(format!(
+                            "{extra_index} => if let Some((key, value)) = extra_iter.next()"
+                        ));
+                        gen_scope.generate_serialize_map_entry(
+                            types,
+                            key_type,
+                            value_type,
+                            &mut extra_ser_block,
+                            &extra_ser_config,
+                            cli,
+                        );
+                        ser_loop_match.push_block(extra_ser_block);
+                    }
                     ser_loop_match.line("_ => unreachable!()").after(";");
                     ser_loop.push_block(ser_loop_match);
-                    ser_func.push_block(ser_loop);
+                    if extra_entry.is_some() {
+                        entries_ser.line("let mut extra_iter = self.extra.iter();");
+                    }
+                    entries_ser.push_block(ser_loop);
+                    // anything not in the original order e.g. modified after deserializing
+                    for field in record.fields.iter().filter(|field| field.key.is_none()) {
+                        let mut embedded_entries_loop =
+                            Block::new(format!("for entry in {}_entries", field.name));
+                        embedded_entries_loop.line("serializer.write_raw_bytes(entry)?;");
+                        entries_ser.push_block(embedded_entries_loop);
+                    }
+                    if let Some((key_type, value_type)) = extra_entry {
+                        // anything not in the original order e.g. newly added or canonical ordering.
+                        let mut extra_ser_loop = Block::new("for (key, value) in extra_iter");
+                        gen_scope.generate_serialize_map_entry(
+                            types,
+                            key_type,
+                            value_type,
+                            &mut extra_ser_loop,
+                            &extra_ser_config,
+                            cli,
+                        );
+                        entries_ser.push_block(extra_ser_loop);
+                    }
                 } else {
                     for (_field_index, field, content) in ser_content.into_iter() {
                         if field.optional {
@@ -5919,64 +6591,103 @@ fn codegen_struct(
                                 };
                             let mut optional_ser_field = Block::new(optional_ser_field_check);
                             optional_ser_field.push_all(content);
-                            ser_func.push_block(optional_ser_field);
+                            entries_ser.push_block(optional_ser_field);
                         } else {
-                            ser_func.push_all(content);
+                            entries_ser.push_all(content);
                         }
                     }
+                    if let Some((key_type, value_type)) = extra_entry {
+                        let mut extra_ser_loop =
+                            Block::new("for (key, value) in self.extra.iter()");
+                        gen_scope.generate_serialize_map_entry(
+                            types,
+                            key_type,
+                            value_type,
+                            &mut extra_ser_loop,
+                            &extra_ser_config,
+                            cli,
+                        );
+                        entries_ser.push_block(extra_ser_loop);
+                    }
+                }
+                if cli.canonical_form && (extra_entry.is_some() || has_embedded_groups) {
+                    // catch-all keys and the keys of embedded plain groups' entries (which can be anywhere
+                    // in the canonical order) are only known at runtime so all entries are sorted after
+                    ser_func.line("let mut entries_se = Serializer::new_vec();");
+                    let mut entries_block = Block::new("");
+                    entries_block.line("let serializer = &mut entries_se;");
+                    entries_block.push_all(entries_ser);
+                    ser_func.push_block(entries_block);
+                    ser_func.line("let entries = entries_se.finalize();");
+                    if cli.preserve_encodings {
+                        let mut canonical_block = Block::new("if force_canonical");
+                        canonical_block.line("write_canonical_map_entries(serializer, &entries)?;");
+                        ser_func.push_block(canonical_block);
+                        let mut orig_order_block = Block::new("else");
+                        orig_order_block.line("serializer.write_raw_bytes(&entries)?;");
+                        ser_func.push_block(orig_order_block);
+                    } else {
+                        ser_func.line("write_canonical_map_entries(serializer, &entries)?;");
+                    }
+                } else {
+                    ser_func.push_all(entries_ser);
                 }
                 // needs to be in one line rather than a block because Block::after() only takes a string
                 deser_code.content.line("let mut read = 0;");
                 let mut deser_loop = make_deser_loop("len", "read", cli);
+                let rewind_extra_key = extra_entry.is_some() && !key_deserializers.is_empty();
+                if rewind_extra_key {
+                    // unknown keys are only known after reading them so we need to go back to read them as extra entries
+                    deser_loop
+                        .line("let key_position = raw.as_mut_ref().stream_position().map_err(cbor_event::Error::from)?;");
+                }
                 let mut type_match = Block::new("match raw.cbor_type()?");
-                if uint_field_deserializers.is_empty() {
-                    type_match.line("cbor_event::Type::UnsignedInteger => return Err(DeserializeFailure::UnknownKey(Key::Uint(raw.unsigned_integer()?)).into()),");
-                } else {
-                    let mut uint_match = if cli.preserve_encodings {
-                        Block::new(
-                            "cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()?",
-                        )
+                push_map_key_arms(
+                    gen_scope,
+                    types,
+                    &mut type_match,
+                    key_deserializers.untagged,
+                    false,
+                    extra_entry,
+                    extra_index,
+                    cli,
+                );
+                if !key_deserializers.tagged.is_empty() {
+                    // tagged keys e.g. #6.42("foo") are matched on (tag, key) after reading the tag
+                    let mut tag_block = Block::new("cbor_event::Type::Tag =>");
+                    if cli.preserve_encodings {
+                        tag_block.line("let (key_tag, key_tag_enc) = raw.tag_sz()?;");
                     } else {
-                        Block::new(
-                            "cbor_event::Type::UnsignedInteger => match raw.unsigned_integer()?",
-                        )
-                    };
-                    for case in uint_field_deserializers {
-                        uint_match.push_block(case);
+                        tag_block.line("let key_tag = raw.tag()?;");
                     }
-                    let unknown_key_decl = if cli.preserve_encodings {
-                        "(unknown_key, _enc)"
+                    let mut tagged_type_match = Block::new("match raw.cbor_type()?");
+                    push_map_key_arms(
+                        gen_scope,
+                        types,
+                        &mut tagged_type_match,
+                        key_deserializers.tagged,
+                        true,
+                        extra_entry,
+                        extra_index,
+                        cli,
+                    );
+                    if let Some((key_type, value_type)) = extra_entry {
+                        tagged_type_match.push_block(deserialize_extra_entry(
+                            gen_scope,
+                            types,
+                            key_type,
+                            value_type,
+                            extra_index,
+                            "_ =>",
+                            true,
+                            cli,
+                        ));
                     } else {
-                        "unknown_key"
-                    };
-                    uint_match.line(format!("{unknown_key_decl} => return Err(DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()),"));
-                    uint_match.after(",");
-                    type_match.push_block(uint_match);
-                }
-                // we can't map text_sz() with String::as_str() to match it since that would return a reference to a temporary
-                // so we need to store it in a local and have an extra block to declare it
-                if text_field_deserializers.is_empty() {
-                    type_match.line("cbor_event::Type::Text => return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into()),");
-                } else if cli.preserve_encodings {
-                    let mut outer_match = Block::new("cbor_event::Type::Text =>");
-                    outer_match.line("let (text_key, key_enc) = raw.text_sz()?;");
-                    let mut text_match = Block::new("match text_key.as_str()");
-                    for case in text_field_deserializers {
-                        text_match.push_block(case);
-                    }
-                    text_match.line("unknown_key => return Err(DeserializeFailure::UnknownKey(Key::Str(unknown_key.to_owned())).into()),");
-                    outer_match.after(",");
-                    outer_match.push_block(text_match);
-                    type_match.push_block(outer_match);
-                } else {
-                    let mut text_match =
-                        Block::new("cbor_event::Type::Text => match raw.text()?.as_str()");
-                    for case in text_field_deserializers {
-                        text_match.push_block(case);
+                        tagged_type_match.line("other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),");
                     }
-                    text_match.line("unknown_key => return Err(DeserializeFailure::UnknownKey(Key::Str(unknown_key.to_owned())).into()),");
-                    text_match.after(",");
-                    type_match.push_block(text_match);
+                    tag_block.push_block(tagged_type_match);
+                    tag_block.after(",");
+                    type_match.push_block(tag_block);
                 }
                 let mut special_match = Block::new("cbor_event::Type::Special => match len");
                 special_match.line(format!(
@@ -5994,75 +6705,49 @@ fn codegen_struct(
                 special_match.push_block(break_check);
                 special_match.after(",");
                 type_match.push_block(special_match);
-                type_match.line("other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),");
+                if let Some((key_type, value_type)) = extra_entry {
+                    type_match.push_block(deserialize_extra_entry(
+                        gen_scope,
+                        types,
+                        key_type,
+                        value_type,
+                        extra_index,
+                        "_ =>",
+                        false,
+                        cli,
+                    ));
+                } else {
+                    type_match.line("other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),");
+                }
                 deser_loop.push_block(type_match);
                 deser_loop.line("read += 1;");
                 deser_code.content.push_block(deser_loop);
                 let mut ctor_block = Block::new("Ok(Self");
                 // make sure the field is present, and unwrap the Option<T>
                 for field in &record.fields {
-                    if !field.optional {
-                        let key = match &field.key {
-                            Some(FixedValue::Uint(x)) => format!("Key::Uint({x})"),
-                            Some(FixedValue::Text(x)) => format!("Key::Str(String::from(\"{x}\"))"),
-                            None => unreachable!(),
-                            _ => unimplemented!(),
-                        };
-                        if field.rust_type.is_fixed_value() {
-                            let mut mandatory_field_check =
-                                Block::new(format!("if !{}_present", field.name));
-                            mandatory_field_check.line(format!(
-                            "return Err(DeserializeFailure::MandatoryFieldMissing({key}).into());"
-                        ));
-                            deser_code.content.push_block(mandatory_field_check);
-                        } else {
-                            let mut mandatory_field_check =
-                                Block::new(format!("let {} = match {}", field.name, field.name));
-                            mandatory_field_check.line("Some(x) => x,");
-
-                            mandatory_field_check.line(format!("None => return Err(DeserializeFailure::MandatoryFieldMissing({key}).into()),"));
-                            mandatory_field_check.after(";");
-                            deser_code.content.push_block(mandatory_field_check);
-                        }
-                    } else if let Some(default_value) = &field.rust_type.config.default {
-                        if cli.preserve_encodings {
-                            let mut default_present_check = Block::new(format!(
-                                "if {} == Some({})",
-                                field.name,
-                                default_value.to_primitive_str_assign()
-                            ));
-                            default_present_check
-                                .line(format!("{}_default_present = true;", field.name));
-                            deser_code.content.push_block(default_present_check);
-                        }
-                        match default_value {
-                            FixedValue::Text(_) => {
-                                // to avoid clippy::or_fun_call
-                                deser_code.content.line(&format!(
-                                    "let {} = {}.unwrap_or_else(|| {});",
-                                    field.name,
-                                    field.name,
-                                    default_value.to_primitive_str_assign()
-                                ));
-                            }
-                            FixedValue::Bool(_)
-                            | FixedValue::Nint(_)
-                            | FixedValue::Null
-                            | FixedValue::Float(_)
-                            | FixedValue::Uint(_) => {
-                                deser_code.content.line(&format!(
-                                    "let {} = {}.unwrap_or({});",
-                                    field.name,
-                                    field.name,
-                                    default_value.to_primitive_str_assign()
-                                ));
-                            }
-                        }
+                    if field.key.is_some() {
+                        generate_map_field_presence_check(
+                            field,
+                            &field.name,
+                            &mut deser_code.content,
+                            cli,
+                        );
+                    } else {
+                        generate_embedded_map_group_construction(
+                            types,
+                            field,
+                            &field.name,
+                            &mut deser_code.content,
+                            cli,
+                        );
                     }
                     if !field.rust_type.is_fixed_value() {
                         ctor_block.line(format!("{},", field.name));
                     }
                 }
+                if record.extra.is_some() {
+                    ctor_block.line("extra: extra_table,");
+                }
                 if cli.preserve_encodings {
                     let mut encoding_ctor = Block::new(format!("encodings: Some({name}Encoding"));
                     if tag.is_some() {
@@ -6072,8 +6757,11 @@ fn codegen_struct(
                         .line("len_encoding,")
                         .line("orig_deser_order,");
                     for field in record.fields.iter() {
-                        let key_enc = key_encoding_field(&field.name, field.key.as_ref().unwrap());
-                        encoding_ctor.line(format!("{},", key_enc.field_name));
+                        if let Some(key) = &field.key {
+                            for key_enc in key_encoding_fields(types, &field.name, key, cli) {
+                                encoding_ctor.line(format!("{},", key_enc.field_name));
+                            }
+                        }
                         for field_enc in encoding_fields(
                             types,
                             &field.name,
@@ -6084,6 +6772,11 @@ fn codegen_struct(
                             encoding_ctor.line(format!("{},", field_enc.field_name));
                         }
                     }
+                    if let Some(extra) = &record.extra {
+                        for extra_enc in extra_encoding_fields(types, extra, cli) {
+                            encoding_ctor.line(format!("{},", extra_enc.field_name));
+                        }
+                    }
                     encoding_ctor.after("),");
                     ctor_block.push_block(encoding_ctor);
                 }
@@ -6257,9 +6950,7 @@ fn codegen_group_choices(
                         .iter()
                         .filter(|f| (!f.optional || inlined) && !f.rust_type.is_fixed_value())
                         .collect();
-                    let can_fail = ctor_fields
-                        .iter()
-                        .any(|f| f.rust_type.config.bounds.is_some());
+                    let can_fail = ctor_fields.iter().any(|f| f.rust_type.has_bounds());
                     match ctor_fields.len() {
                         0 => {
                             new_func
@@ -6335,7 +7026,7 @@ fn codegen_group_choices(
                             )
                         );
                         new_func.arg(&field_name, variant.rust_type().for_wasm_param(types));
-                        if variant.rust_type().config.bounds.is_some() {
+                        if variant.rust_type().has_bounds() {
                             new_func
                                 .ret(format!("Result<{name}, JsError>"))
                                 .line(format!("{ctor}.map(Into::into).map_err(Into::into)"));
@@ -6805,97 +7496,864 @@ fn surround_in_len_checks(
     main_deser_code
 }
 
-fn make_inline_deser_code(
+/// Match arms for the literal keys of a map struct's fields, grouped by the CBOR type the key is read as
+#[derive(Default)]
+struct MapKeyArms {
+    uint: Vec<Block>,
+    nint: Vec<Block>,
+    text: Vec<Block>,
+    bytes: Vec<Block>,
+}
+
+impl MapKeyArms {
+    fn is_empty(&self) -> bool {
+        self.uint.is_empty()
+            && self.nint.is_empty()
+            && self.text.is_empty()
+            && self.bytes.is_empty()
+    }
+}
+
+/// All the key match arms of a map struct. Tagged keys e.g. #6.42("foo") are read after their tag
+/// so they're kept separate and match on (tag, key) instead
+#[derive(Default)]
+struct MapKeyDeserializers {
+    untagged: MapKeyArms,
+    tagged: MapKeyArms,
+}
+
+impl MapKeyDeserializers {
+    fn push(&mut self, key: &MapKey, arm: Block) {
+        let arms = match key.tag {
+            Some(_) => &mut self.tagged,
+            None => &mut self.untagged,
+        };
+        match &key.value {
+            FixedValue::Uint(_) => arms.uint.push(arm),
+            FixedValue::Nint(_) => arms.nint.push(arm),
+            FixedValue::Text(_) => arms.text.push(arm),
+            FixedValue::Bytes(_) => arms.bytes.push(arm),
+            _ => unreachable!(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.untagged.is_empty() && self.tagged.is_empty()
+    }
+}
+
+// e.g. b"\x00\xff" for h'00ff'
+fn bytes_literal(bytes: &[u8]) -> String {
+    format!("b\"{}\"", bytes.escape_ascii())
+}
+
+// The cbor_event::Special that false / true / null / undefined / #7.n are written as
+fn fixed_value_special(value: &FixedValue) -> String {
+    match value {
+        FixedValue::Null => "cbor_event::Special::Null".to_owned(),
+        FixedValue::Undefined => "cbor_event::Special::Undefined".to_owned(),
+        FixedValue::Bool(b) => format!("cbor_event::Special::Bool({b})"),
+        FixedValue::Simple(n) => format!("cbor_event::Special::Unassigned({n})"),
+        _ => unreachable!("not a special value: {:?}", value),
+    }
+}
+
+// The pattern a key's arm uses to match the key read in push_map_key_arms() or None if the key type isn't supported
+fn map_key_pattern(key: &MapKey, cli: &Cli) -> Option<String> {
+    let value = match &key.value {
+        FixedValue::Uint(x) if cli.preserve_encodings => format!("({x}, key_enc)"),
+        FixedValue::Uint(x) => x.to_string(),
+        FixedValue::Nint(x) if cli.preserve_encodings => format!("({x}, key_enc)"),
+        FixedValue::Nint(x) => x.to_string(),
+        FixedValue::Text(x) => format!("\"{x}\""),
+        FixedValue::Bytes(bytes) => bytes_literal(bytes),
+        FixedValue::Null
+        | FixedValue::Undefined
+        | FixedValue::Bool(_)
+        | FixedValue::Simple(_)
+        | FixedValue::Float(_) => return None,
+    };
+    Some(match key.tag {
+        Some(tag) => format!("({tag}, {value})"),
+        None => value,
+    })
+}
+
+// The key as a Key expression for errors e.g. Key::Tagged(42, Box::new(Key::Nint(-1)))
+fn map_key_in_rust(key: &MapKey) -> String {
+    let value = match &key.value {
+        FixedValue::Uint(x) => format!("Key::Uint({x})"),
+        FixedValue::Nint(x) => format!("Key::Nint({x})"),
+        FixedValue::Text(x) => format!("Key::Str(String::from(\"{x}\"))"),
+        FixedValue::Bytes(bytes) => format!("Key::Bytes(vec!{bytes:?})"),
+        FixedValue::Null
+        | FixedValue::Undefined
+        | FixedValue::Bool(_)
+        | FixedValue::Simple(_)
+        | FixedValue::Float(_) => {
+            unreachable!("unsupported map key type: {}", key.to_cddl_str())
+        }
+    };
+    match key.tag {
+        Some(tag) => format!("Key::Tagged({tag}, Box::new({value}))"),
+        None => value,
+    }
+}
+
+// Adds an arm to key_type_match (a match on raw.cbor_type()?) per CBOR type of key in arms which reads
+// the key and matches it against them. Keys not matching any arm go to the extra table if there is one.
+// For tagged keys the tag was already read into key_tag (and key_tag_enc) and are matched as (key_tag, key).
+#[allow(clippy::too_many_arguments)]
+fn push_map_key_arms(
     gen_scope: &mut GenerationScope,
     types: &IntermediateTypes,
-    name: &RustIdent,
-    tag: Option<usize>,
-    record: &RustRecord,
-    enum_gen_info: &EnumVariantInRust,
+    key_type_match: &mut Block,
+    arms: MapKeyArms,
+    tagged: bool,
+    extra_entry: Option<(&RustType, &RustType)>,
+    extra_index: usize,
     cli: &Cli,
-) -> DeserializationCode {
-    let mut variant_deser_code = generate_array_struct_deserialization(
-        gen_scope, types, name, record, tag, false, false, cli,
-    );
-    // generate_constructor zips the expressions with the names in the enum_gen_info
-    // so just make sure we're in the same order as returned above
-    assert_eq!(
-        enum_gen_info.names.len(),
-        variant_deser_code.deser_ctor_fields.len()
-            + variant_deser_code.encoding_struct_ctor_fields.len()
-    );
-    let ctor_exprs = variant_deser_code
-        .deser_ctor_fields
-        .into_iter()
-        .chain(variant_deser_code.encoding_struct_ctor_fields)
-        .zip(enum_gen_info.names.iter())
-        .map(|((var, expr), name)| {
-            assert_eq!(var, *name);
-            expr
-        })
-        .collect();
-    variant_deser_code.deser_code = surround_in_len_checks(
-        variant_deser_code.deser_code,
-        record.cbor_len_info(types),
-        record.rep,
-        cli,
-    );
-    enum_gen_info.generate_constructor(
-        &mut variant_deser_code.deser_code.content,
-        "Ok(",
-        ")",
-        Some(&ctor_exprs),
-    );
-    variant_deser_code.deser_code
+) {
+    let (int_unknown_key, int_sz) = if cli.preserve_encodings {
+        ("(unknown_key, _enc)", "_sz")
+    } else {
+        ("unknown_key", "")
+    };
+    let nint_read = if cli.preserve_encodings {
+        "raw.negative_integer_sz()?"
+    } else {
+        // the _sz variant also gets around the incomplete nint support in the regular negative_integer()
+        "raw.negative_integer_sz()?.0"
+    };
+    // unknown keys fall through to the extra table if there is one. Other key types without any
+    // arms are left to the UnexpectedKeyType arm as before they were supported as keys
+    if !tagged && extra_entry.is_none() {
+        if arms.uint.is_empty() {
+            key_type_match.line("cbor_event::Type::UnsignedInteger => return Err(DeserializeFailure::UnknownKey(Key::Uint(raw.unsigned_integer()?)).into()),");
+        }
+        if arms.text.is_empty() {
+            key_type_match.line("cbor_event::Type::Text => return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into()),");
+        }
+    }
+    // (arms, CBOR type, read before matching, key expr matched against, unknown key pattern, Key for unknown keys)
+    let key_types = [
+        (
+            arms.uint,
+            "UnsignedInteger",
+            None,
+            format!("raw.unsigned_integer{int_sz}()?"),
+            int_unknown_key,
+            "Key::Uint(unknown_key)",
+        ),
+        (
+            arms.nint,
+            "NegativeInteger",
+            None,
+            nint_read.to_owned(),
+            int_unknown_key,
+            "Key::Nint(unknown_key)",
+        ),
+        // we can't map text_sz() with String::as_str() to match it since that would return a reference to a temporary
+        // so we need to store it in a local and have an extra block to declare it
+        if cli.preserve_encodings {
+            (
+                arms.text,
+                "Text",
+                Some("let (text_key, key_enc) = raw.text_sz()?;"),
+                "text_key.as_str()".to_owned(),
+                "unknown_key",
+                "Key::Str(unknown_key.to_owned())",
+            )
+        } else {
+            (
+                arms.text,
+                "Text",
+                None,
+                "raw.text()?.as_str()".to_owned(),
+                "unknown_key",
+                "Key::Str(unknown_key.to_owned())",
+            )
+        },
+        if cli.preserve_encodings {
+            (
+                arms.bytes,
+                "Bytes",
+                Some("let (bytes_key, key_enc) = raw.bytes_sz()?;"),
+                "bytes_key.as_slice()".to_owned(),
+                "unknown_key",
+                "Key::Bytes(unknown_key.to_vec())",
+            )
+        } else {
+            (
+                arms.bytes,
+                "Bytes",
+                None,
+                "raw.bytes()?.as_slice()".to_owned(),
+                "unknown_key",
+                "Key::Bytes(unknown_key.to_vec())",
+            )
+        },
+    ];
+    for (cases, cbor_type, read_before, key_expr, unknown_key_pattern, unknown_key) in key_types {
+        if cases.is_empty() {
+            continue;
+        }
+        let (key_expr, unknown_key_pattern, unknown_key) = if tagged {
+            (
+                format!("(key_tag, {key_expr})"),
+                format!("(unknown_tag, {unknown_key_pattern})"),
+                format!("Key::Tagged(unknown_tag, Box::new({unknown_key}))"),
+            )
+        } else {
+            (
+                key_expr,
+                unknown_key_pattern.to_owned(),
+                unknown_key.to_owned(),
+            )
+        };
+        let (mut key_match, outer_block) = match read_before {
+            Some(read_before) => {
+                let mut outer_block = Block::new(format!("cbor_event::Type::{cbor_type} =>"));
+                outer_block.line(read_before);
+                outer_block.after(",");
+                (Block::new(format!("match {key_expr}")), Some(outer_block))
+            }
+            None => {
+                let mut key_match =
+                    Block::new(format!("cbor_event::Type::{cbor_type} => match {key_expr}"));
+                key_match.after(",");
+                (key_match, None)
+            }
+        };
+        for case in cases {
+            key_match.push_block(case);
+        }
+        if let Some((key_type, value_type)) = extra_entry {
+            key_match.push_block(deserialize_extra_entry(
+                gen_scope,
+                types,
+                key_type,
+                value_type,
+                extra_index,
+                "_ =>",
+                true,
+                cli,
+            ));
+        } else {
+            key_match.line(format!(
+                "{unknown_key_pattern} => return Err(DeserializeFailure::UnknownKey({unknown_key}).into()),"
+            ));
+        }
+        match outer_block {
+            Some(mut outer_block) => {
+                outer_block.push_block(key_match);
+                key_type_match.push_block(outer_block);
+            }
+            None => {
+                key_type_match.push_block(key_match);
+            }
+        }
+    }
 }
 
-// Generates a general enum e.g. Foo { A(A), B(B), C(C) } for types A, B, C
-// if generate_deserialize_directly, don't generate deserialize_as_embedded_group() and just inline it within deserialize()
-// This is useful for type choicecs which don't have any enclosing array/map tags, and thus don't benefit from exposing a
-// deserialize_as_embedded_group as the behavior would be identical.
+// Generates the deserialization of a map field's key/value within a map deserialization loop.
+// var_name is the local it's deserialized into which differs from the field name for fields of
+// plain groups embedded into the map. orig_deser_orders are the (local, index) pairs to record
+// the order in, from the outermost struct to the one the field is directly a member of.
 #[allow(clippy::too_many_arguments)]
-fn generate_enum(
+fn generate_map_field_deserialization(
     gen_scope: &mut GenerationScope,
     types: &IntermediateTypes,
     name: &RustIdent,
-    variants: &[EnumVariant],
-    rep: Option<Representation>,
-    generate_deserialize_directly: bool,
-    tag: Option<usize>,
-    config: &RustStructConfig,
+    field: &RustField,
+    var_name: &str,
+    orig_deser_orders: &[(String, usize)],
+    in_embedded: bool,
+    deser_code: &mut DeserializationCode,
+    key_deserializers: &mut MapKeyDeserializers,
+    extra_entry: Option<(&RustType, &RustType)>,
+    extra_index: usize,
     cli: &Cli,
 ) {
-    if cli.wasm {
-        // also create a wasm-exposed enum just to distinguish the type
-        let mut kind = codegen::Enum::new(format!("{name}Kind"));
-        kind.vis("pub");
-        for variant in variants.iter() {
-            kind.new_variant(variant.name.to_string());
+    let key = field.key.as_ref().unwrap();
+    // Optional entries that aren't cut e.g. ? "foo" => uint instead of ? "foo" ^ => uint or ? foo: uint
+    // fall through to the catch-all table if their value doesn't match and the key also fits there.
+    // Mandatory ones would be missing in that case so those are an error regardless.
+    let fallback_entry = extra_entry.filter(|(extra_key_type, _)| {
+        let extra_key_cbor_types = extra_key_type.cbor_types(types);
+        !key.cut
+            && field.optional
+            && key
+                .to_rust_type()
+                .cbor_types(types)
+                .iter()
+                .all(|cbor_type| extra_key_cbor_types.contains(cbor_type))
+    });
+    // read_len is counted outside of the fallback's value closure so it isn't counted twice
+    let value_optional_field = field.optional && fallback_entry.is_none();
+    // declare variables for deser loop
+    if cli.preserve_encodings {
+        for field_enc in encoding_fields(
+            types,
+            var_name,
+            &field.rust_type.clone().resolve_aliases(),
+            true,
+            cli,
+        ) {
+            deser_code.content.line(&format!(
+                "let mut {} = {};",
+                field_enc.field_name, field_enc.default_expr
+            ));
+        }
+        for key_enc in key_encoding_fields(types, var_name, key, cli) {
+            deser_code.content.line(&format!(
+                "let mut {} = {};",
+                key_enc.field_name, key_enc.default_expr
+            ));
         }
-        kind.attr("wasm_bindgen");
-        gen_scope.wasm(types, name).push_enum(kind);
-    }
-
-    // rust enum containing the data
-    let mut e = codegen::Enum::new(name.to_string());
-    e.vis("pub");
-    if let Some(doc) = config.doc.as_ref() {
-        e.doc(doc);
     }
-    let mut e_impl = codegen::Impl::new(name.to_string());
-    // instead of using create_serialize_impl() and having the length encoded there, we want to make it easier
-    // to offer definite length encoding even if we're mixing plain group members and non-plain group members (or mixed length plain ones)
-    // by potentially wrapping the choices with the array/map tag in the variant branch when applicable
-    add_struct_derives(
-        &mut e,
-        types.used_as_key(name),
-        true,
-        config.custom_json,
-        cli,
-    );
-    let mut ser_impl = make_serialization_impl(name.as_ref(), cli);
-    let mut ser_func = make_serialization_function("serialize", cli);
-    if let Some(tag) = tag {
+    if field.rust_type.is_fixed_value() {
+        deser_code
+            .content
+            .line(&format!("let mut {}_present = false;", var_name));
+    } else {
+        deser_code
+            .content
+            .line(&format!("let mut {} = None;", var_name));
+    }
+    // deserialize key + value
+    let mut deser_block = Block::new(format!(
+        "{} => ",
+        map_key_pattern(key, cli).unwrap_or_else(|| panic!(
+            "unsupported map key type for {}.{}: {}",
+            name,
+            field.name,
+            key.to_cddl_str()
+        ))
+    ));
+    deser_block.after(",");
+    let mut deser_block_code = DeserializationCode::default();
+    let key_in_rust = map_key_in_rust(key);
+    let mut dup_check = if field.rust_type.is_fixed_value() {
+        Block::new(format!("if {}_present", var_name))
+    } else {
+        Block::new(format!("if {}.is_some()", var_name))
+    };
+    dup_check.line(&format!(
+        "return Err(DeserializeFailure::DuplicateKey({key_in_rust}).into());"
+    ));
+    deser_block.push_block(dup_check);
+    if cli.preserve_encodings {
+        let temp_var_prefix = format!("tmp_{}", var_name);
+        let var_names_str = encoding_var_names_str(types, &temp_var_prefix, &field.rust_type, cli);
+        if cli.annotate_fields {
+            let (before, after) = if var_names_str.is_empty() {
+                ("".to_owned(), "?")
+            } else {
+                (format!("let {var_names_str} = "), "?;")
+            };
+            let deser_config = DeserializeConfig::for_field(var_name, field, in_embedded)
+                .optional_field(value_optional_field);
+            gen_scope
+                .generate_deserialize(
+                    types,
+                    (&field.rust_type).into(),
+                    DeserializeBeforeAfter::new("", "", true),
+                    deser_config,
+                    cli,
+                )
+                .annotate(&field.name, &before, after)
+                .add_to_code(&mut deser_block_code);
+        } else {
+            let (before, after) = if var_names_str.is_empty() {
+                ("".to_owned(), "")
+            } else {
+                (format!("let {var_names_str} = "), ";")
+            };
+            let deser_config = DeserializeConfig::for_field(var_name, field, in_embedded)
+                .optional_field(value_optional_field);
+            gen_scope
+                .generate_deserialize(
+                    types,
+                    (&field.rust_type).into(),
+                    DeserializeBeforeAfter::new(&before, after, false),
+                    deser_config,
+                    cli,
+                )
+                .add_to_code(&mut deser_block_code);
+        }
+        // Due to destructuring assignemnt (RFC 372 / 71156) being unstable we're forced to use temporaries then reassign after
+        // which is not ideal but doing the assignment inside the lambda or otherwise has issues where it's putting lots of
+        // context-sensitive logic into generate_deserialize and you would need to declare temporaries in most cases anyway
+        // as cbor_event encoding-aware functions return tuples which just pushes the problem there instead.
+        // We might be able to write a nice way around this in the annotate_fields=false, preserve_encodings=true case
+        // but I don't think anyone (or many) would care about this as it's incredibly niche
+        // (annotate_fields=false would be for minimizing code size but then preserve_encodings=true generates way more code)
+        if field.rust_type.is_fixed_value() {
+            deser_block_code
+                .content
+                .line(&format!("{}_present = true;", var_name));
+        } else {
+            deser_block_code
+                .content
+                .line(&format!("{} = Some(tmp_{});", var_name, var_name));
+        }
+        for enc_field in encoding_fields(
+            types,
+            var_name,
+            &field.rust_type.clone().resolve_aliases(),
+            false,
+            cli,
+        ) {
+            deser_block_code.content.line(&format!(
+                "{} = tmp_{};",
+                enc_field.field_name, enc_field.field_name
+            ));
+        }
+    } else if field.rust_type.is_fixed_value() {
+        // only does verification and sets the field_present bool to do error checking later
+        if cli.annotate_fields {
+            let deser_config = DeserializeConfig::for_field(var_name, field, in_embedded)
+                .optional_field(value_optional_field);
+            let mut err_deser = gen_scope.generate_deserialize(
+                types,
+                (&field.rust_type).into(),
+                DeserializeBeforeAfter::new("", "", false),
+                deser_config,
+                cli,
+            );
+            err_deser.content.line("Ok(true)");
+            err_deser
+                .annotate(&field.name, &format!("{}_present = ", var_name), "?;")
+                .add_to_code(&mut deser_block_code);
+        } else {
+            let deser_config = DeserializeConfig::for_field(var_name, field, in_embedded)
+                .optional_field(value_optional_field);
+            gen_scope
+                .generate_deserialize(
+                    types,
+                    (&field.rust_type).into(),
+                    DeserializeBeforeAfter::new("", "", false),
+                    deser_config,
+                    cli,
+                )
+                .add_to_code(&mut deser_block_code);
+            deser_block_code
+                .content
+                .line(&format!("{}_present = true;", var_name));
+        }
+    } else if cli.annotate_fields {
+        let deser_config = DeserializeConfig::for_field(var_name, field, in_embedded)
+            .optional_field(value_optional_field);
+        gen_scope
+            .generate_deserialize(
+                types,
+                (&field.rust_type).into(),
+                DeserializeBeforeAfter::new("", "", true),
+                deser_config,
+                cli,
+            )
+            .annotate(&field.name, &format!("{} = Some(", var_name), "?);")
+            .add_to_code(&mut deser_block_code);
+    } else {
+        let deser_config = DeserializeConfig::for_field(var_name, field, in_embedded)
+            .optional_field(value_optional_field);
+        gen_scope
+            .generate_deserialize(
+                types,
+                (&field.rust_type).into(),
+                DeserializeBeforeAfter::new(&format!("{} = Some(", var_name), ");", false),
+                deser_config,
+                cli,
+            )
+            .add_to_code(&mut deser_block_code);
+    }
+    if cli.preserve_encodings {
+        // tagged keys have the tag's encoding first as in the arm patterns from map_key_pattern()
+        let key_enc_vars = match key.tag {
+            Some(_) => vec!["key_tag_enc", "key_enc"],
+            None => vec!["key_enc"],
+        };
+        for (key_encoding, key_enc_var) in key_encoding_fields(types, var_name, key, cli)
+            .into_iter()
+            .zip(key_enc_vars)
+        {
+            deser_block_code.content.line(&format!(
+                "{} = {};",
+                key_encoding.field_name,
+                key_encoding.enc_conversion(key_enc_var)
+            ));
+        }
+        // embedded plain groups keep track of their own order but also need to be placed within the
+        // order of the structs they're embedded into (once per entry as they can be interleaved)
+        for (order_var, index) in orig_deser_orders {
+            deser_block_code
+                .content
+                .line(&format!("{order_var}.push({index});"));
+        }
+    }
+
+    match fallback_entry {
+        Some((extra_key_type, extra_value_type)) => {
+            // the value is read in a closure so if it doesn't match we can go back and read the
+            // whole entry into the catch-all table instead
+            deser_block.line("read_len.read_elems(1)?;");
+            let mut value_closure = Block::new(format!(
+                "let {var_name}_result: Result<(), DeserializeError> = (||"
+            ));
+            value_closure.push_all(deser_block_code.mark_and_extract_content(deser_code));
+            value_closure.line("Ok(())");
+            value_closure.after(")();");
+            deser_block.push_block(value_closure);
+            let mut fallback = Block::new(format!("if {var_name}_result.is_err()"));
+            fallback.line(
+                "raw.as_mut_ref().seek(SeekFrom::Start(key_position)).map_err(cbor_event::Error::from)?;",
+            );
+            push_extra_entry_deserialization(
+                gen_scope,
+                types,
+                extra_key_type,
+                extra_value_type,
+                extra_index,
+                &mut fallback,
+                cli,
+            );
+            deser_block.push_block(fallback);
+        }
+        None => {
+            deser_block.push_all(deser_block_code.mark_and_extract_content(deser_code));
+        }
+    }
+    key_deserializers.push(key, deser_block);
+}
+
+// Makes sure a mandatory map field deserialized via generate_map_field_deserialization() was present
+// (unwrapping the local's Option<T>) or applies the default value for optional ones
+fn generate_map_field_presence_check(
+    field: &RustField,
+    var_name: &str,
+    body: &mut BlocksOrLines,
+    cli: &Cli,
+) {
+    if !field.optional {
+        let key = map_key_in_rust(field.key.as_ref().unwrap());
+        if field.rust_type.is_fixed_value() {
+            let mut mandatory_field_check = Block::new(format!("if !{}_present", var_name));
+            mandatory_field_check.line(format!(
+                "return Err(DeserializeFailure::MandatoryFieldMissing({key}).into());"
+            ));
+            body.push_block(mandatory_field_check);
+        } else {
+            let mut mandatory_field_check =
+                Block::new(format!("let {} = match {}", var_name, var_name));
+            mandatory_field_check.line("Some(x) => x,");
+
+            mandatory_field_check.line(format!(
+                "None => return Err(DeserializeFailure::MandatoryFieldMissing({key}).into()),"
+            ));
+            mandatory_field_check.after(";");
+            body.push_block(mandatory_field_check);
+        }
+    } else if let Some(default_value) = &field.rust_type.config.default {
+        if cli.preserve_encodings {
+            let mut default_present_check = Block::new(format!(
+                "if {} == Some({})",
+                var_name,
+                default_value.to_primitive_str_assign()
+            ));
+            default_present_check.line(format!("{}_default_present = true;", var_name));
+            body.push_block(default_present_check);
+        }
+        match default_value {
+            FixedValue::Text(_) | FixedValue::Bytes(_) => {
+                // to avoid clippy::or_fun_call
+                body.line(&format!(
+                    "let {} = {}.unwrap_or_else(|| {});",
+                    var_name,
+                    var_name,
+                    default_value.to_primitive_str_assign()
+                ));
+            }
+            FixedValue::Bool(_)
+            | FixedValue::Nint(_)
+            | FixedValue::Null
+            | FixedValue::Undefined
+            | FixedValue::Simple(_)
+            | FixedValue::Float(_)
+            | FixedValue::Uint(_) => {
+                body.line(&format!(
+                    "let {} = {}.unwrap_or({});",
+                    var_name,
+                    var_name,
+                    default_value.to_primitive_str_assign()
+                ));
+            }
+        }
+    }
+}
+
+fn embedded_map_group_record<'a>(
+    types: &'a IntermediateTypes,
+    field: &RustField,
+) -> (RustIdent, &'a RustRecord) {
+    let ident = match field.rust_type.conceptual_type.resolve_alias_shallow() {
+        ConceptualRustType::Rust(ident) => ident,
+        _ => unreachable!("only plain groups can be embedded into maps without keys"),
+    };
+    match types.rust_struct(ident).unwrap().variant() {
+        RustStructType::Record(record) => {
+            assert!(
+                record.extra.is_none(),
+                "{}: plain groups with catch-all table entries can't be embedded into maps",
+                ident
+            );
+            (ident.clone(), record)
+        }
+        _ => panic!(
+            "{}: only plain groups without choices can be embedded into maps",
+            ident
+        ),
+    }
+}
+
+// To support maps with plain groups inside we can't use deserialize_as_embedded_group() as we cannot
+// guarantee the order of fields so foo = {a, b, bar}, bar = (c, d) could have the order be
+// {a, d, c, b}, {c, a, b, d}, etc. Instead the plain group's fields are read within the outer
+// map's loop into locals like bar_c, bar_d and the plain group is constructed at the end.
+#[allow(clippy::too_many_arguments)]
+fn generate_embedded_map_group_deserialization(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    name: &RustIdent,
+    field: &RustField,
+    var_name: &str,
+    orig_deser_orders: &[(String, usize)],
+    in_embedded: bool,
+    deser_code: &mut DeserializationCode,
+    key_deserializers: &mut MapKeyDeserializers,
+    extra_entry: Option<(&RustType, &RustType)>,
+    extra_index: usize,
+    cli: &Cli,
+) {
+    let (_, record) = embedded_map_group_record(types, field);
+    if cli.preserve_encodings {
+        deser_code.content.line(&format!(
+            "let mut {var_name}_orig_deser_order = Vec::new();"
+        ));
+    }
+    for (inner_index, inner_field) in record.fields.iter().enumerate() {
+        let inner_var_name = format!("{var_name}_{}", inner_field.name);
+        let mut inner_orig_deser_orders = orig_deser_orders.to_vec();
+        inner_orig_deser_orders.push((format!("{var_name}_orig_deser_order"), inner_index));
+        if inner_field.key.is_some() {
+            generate_map_field_deserialization(
+                gen_scope,
+                types,
+                name,
+                inner_field,
+                &inner_var_name,
+                &inner_orig_deser_orders,
+                in_embedded,
+                deser_code,
+                key_deserializers,
+                extra_entry,
+                extra_index,
+                cli,
+            );
+        } else {
+            generate_embedded_map_group_deserialization(
+                gen_scope,
+                types,
+                name,
+                inner_field,
+                &inner_var_name,
+                &inner_orig_deser_orders,
+                in_embedded,
+                deser_code,
+                key_deserializers,
+                extra_entry,
+                extra_index,
+                cli,
+            );
+        }
+    }
+}
+
+// Constructs a plain group embedded into a map from the locals read in
+// generate_embedded_map_group_deserialization()
+fn generate_embedded_map_group_construction(
+    types: &IntermediateTypes,
+    field: &RustField,
+    var_name: &str,
+    body: &mut BlocksOrLines,
+    cli: &Cli,
+) {
+    let (ident, record) = embedded_map_group_record(types, field);
+    for inner_field in record.fields.iter() {
+        let inner_var_name = format!("{var_name}_{}", inner_field.name);
+        if inner_field.key.is_some() {
+            generate_map_field_presence_check(inner_field, &inner_var_name, body, cli);
+        } else {
+            generate_embedded_map_group_construction(
+                types,
+                inner_field,
+                &inner_var_name,
+                body,
+                cli,
+            );
+        }
+    }
+    let mut ctor_block = if cli.preserve_encodings {
+        Block::new(format!("let mut {var_name} = {ident}"))
+    } else {
+        Block::new(format!("let {var_name} = {ident}"))
+    };
+    for inner_field in record.fields.iter() {
+        if !inner_field.rust_type.is_fixed_value() {
+            ctor_block.line(format!(
+                "{}: {var_name}_{},",
+                inner_field.name, inner_field.name
+            ));
+        }
+    }
+    if cli.preserve_encodings {
+        ctor_block.line("encodings: Some(Default::default()),");
+    }
+    ctor_block.after(";");
+    body.push_block(ctor_block);
+    if cli.preserve_encodings {
+        // we can't name the encoding struct directly as it might not be imported here
+        let mut encodings_block =
+            Block::new(format!("if let Some(encs) = {var_name}.encodings.as_mut()"));
+        encodings_block.line(format!(
+            "encs.orig_deser_order = {var_name}_orig_deser_order;"
+        ));
+        for inner_field in record.fields.iter() {
+            let inner_var_name = format!("{var_name}_{}", inner_field.name);
+            if let Some(key) = &inner_field.key {
+                for (field_enc, var_enc) in key_encoding_fields(types, &inner_field.name, key, cli)
+                    .into_iter()
+                    .zip(key_encoding_fields(types, &inner_var_name, key, cli))
+                {
+                    encodings_block.line(format!(
+                        "encs.{} = {};",
+                        field_enc.field_name, var_enc.field_name
+                    ));
+                }
+            }
+            let inner_type = inner_field.rust_type.clone().resolve_aliases();
+            for (field_enc, var_enc) in
+                encoding_fields(types, &inner_field.name, &inner_type, true, cli)
+                    .into_iter()
+                    .zip(encoding_fields(
+                        types,
+                        &inner_var_name,
+                        &inner_type,
+                        true,
+                        cli,
+                    ))
+            {
+                encodings_block.line(format!(
+                    "encs.{} = {};",
+                    field_enc.field_name, var_enc.field_name
+                ));
+            }
+        }
+        body.push_block(encodings_block);
+    }
+}
+
+fn make_inline_deser_code(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    name: &RustIdent,
+    tag: Option<usize>,
+    record: &RustRecord,
+    enum_gen_info: &EnumVariantInRust,
+    cli: &Cli,
+) -> DeserializationCode {
+    let mut variant_deser_code = generate_array_struct_deserialization(
+        gen_scope, types, name, record, tag, false, false, cli,
+    );
+    // generate_constructor zips the expressions with the names in the enum_gen_info
+    // so just make sure we're in the same order as returned above
+    assert_eq!(
+        enum_gen_info.names.len(),
+        variant_deser_code.deser_ctor_fields.len()
+            + variant_deser_code.encoding_struct_ctor_fields.len()
+    );
+    let ctor_exprs = variant_deser_code
+        .deser_ctor_fields
+        .into_iter()
+        .chain(variant_deser_code.encoding_struct_ctor_fields)
+        .zip(enum_gen_info.names.iter())
+        .map(|((var, expr), name)| {
+            assert_eq!(var, *name);
+            expr
+        })
+        .collect();
+    variant_deser_code.deser_code = surround_in_len_checks(
+        variant_deser_code.deser_code,
+        record.cbor_len_info(types),
+        record.rep,
+        cli,
+    );
+    enum_gen_info.generate_constructor(
+        &mut variant_deser_code.deser_code.content,
+        "Ok(",
+        ")",
+        Some(&ctor_exprs),
+    );
+    variant_deser_code.deser_code
+}
+
+// Generates a general enum e.g. Foo { A(A), B(B), C(C) } for types A, B, C
+// if generate_deserialize_directly, don't generate deserialize_as_embedded_group() and just inline it within deserialize()
+// This is useful for type choicecs which don't have any enclosing array/map tags, and thus don't benefit from exposing a
+// deserialize_as_embedded_group as the behavior would be identical.
+#[allow(clippy::too_many_arguments)]
+fn generate_enum(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    name: &RustIdent,
+    variants: &[EnumVariant],
+    rep: Option<Representation>,
+    generate_deserialize_directly: bool,
+    tag: Option<usize>,
+    config: &RustStructConfig,
+    cli: &Cli,
+) {
+    if cli.wasm {
+        // also create a wasm-exposed enum just to distinguish the type
+        let mut kind = codegen::Enum::new(format!("{name}Kind"));
+        kind.vis("pub");
+        for variant in variants.iter() {
+            kind.new_variant(variant.name.to_string());
+        }
+        kind.attr("wasm_bindgen");
+        gen_scope.wasm(types, name).push_enum(kind);
+    }
+
+    // rust enum containing the data
+    let mut e = codegen::Enum::new(name.to_string());
+    e.vis("pub");
+    if let Some(doc) = config.doc.as_ref() {
+        e.doc(doc);
+    }
+    let mut e_impl = codegen::Impl::new(name.to_string());
+    // instead of using create_serialize_impl() and having the length encoded there, we want to make it easier
+    // to offer definite length encoding even if we're mixing plain group members and non-plain group members (or mixed length plain ones)
+    // by potentially wrapping the choices with the array/map tag in the variant branch when applicable
+    add_struct_derives(
+        &mut e,
+        types.used_as_key(name),
+        true,
+        config.custom_json,
+        cli,
+    );
+    let mut ser_impl = make_serialization_impl(name.as_ref(), cli);
+    let mut ser_func = make_serialization_function("serialize", cli);
+    if let Some(tag) = tag {
         // TODO: how to even store these? (maybe it could be a new field in every enum variant)
         assert!(!cli.preserve_encodings);
         ser_func.line(format!("serializer.write_tag({tag}u64)?;"));
@@ -7031,9 +8489,7 @@ fn generate_enum(
                             .iter()
                             .filter(|f| !f.optional && !f.rust_type.is_fixed_value())
                             .collect();
-                        let can_fail = ctor_fields
-                            .iter()
-                            .any(|field| field.rust_type.config.bounds.is_some());
+                        let can_fail = ctor_fields.iter().any(|field| field.rust_type.has_bounds());
                         // bounds checking should be handled by the called constructor here
                         let mut ctor = format!("{}::new(", ty.conceptual_type.for_variant());
                         for field in ctor_fields {
@@ -7059,28 +8515,14 @@ fn generate_enum(
                             let field_name = variant.name_as_var();
                             new_func
                                 .arg(&field_name, variant.rust_type().for_rust_move(types, cli));
-                            if let Some(bounds) = &ty.config.bounds {
-                                if let Some(check_expr) =
-                                    bounds_check_expr_rust_type(ty, &field_name)
+                            if ty.has_bounds() {
+                                if let Some(check) =
+                                    bounds_check_rust_type_if_block(ty, &field_name)
                                 {
-                                    if let ConceptualRustType::Primitive(Primitive::N64) =
-                                        ty.resolve_alias_shallow()
-                                    {
-                                        new_func.line(bounds_check_if_block(
-                                            &nint_bounds_to_u64(bounds),
-                                            &check_expr,
-                                            true,
-                                        ));
-                                    } else {
-                                        new_func.line(bounds_check_if_block(
-                                            bounds,
-                                            &check_expr,
-                                            true,
-                                        ));
-                                    }
+                                    new_func.line(check);
                                 }
                             }
-                            (vec![field_name], ty.config.bounds.is_some())
+                            (vec![field_name], ty.has_bounds())
                         }
                     }
                 }
@@ -7100,25 +8542,11 @@ fn generate_enum(
                     .collect();
                 let can_fail = record.fields.iter().any(|field| {
                     let can_fail = field.rust_type.needs_bounds_check_if_inlined(types);
-                    if can_fail {
-                        if let Some(check_expr) =
-                            bounds_check_expr_rust_type(&field.rust_type, &field.name)
+                    if field.rust_type.has_bounds() {
+                        if let Some(check) =
+                            bounds_check_rust_type_if_block(&field.rust_type, &field.name)
                         {
-                            if let ConceptualRustType::Primitive(Primitive::N64) =
-                                field.rust_type.resolve_alias_shallow()
-                            {
-                                new_func.line(bounds_check_if_block(
-                                    &nint_bounds_to_u64(&field.rust_type.config.bounds.unwrap()),
-                                    &check_expr,
-                                    true,
-                                ));
-                            } else {
-                                new_func.line(bounds_check_if_block(
-                                    &field.rust_type.config.bounds.unwrap(),
-                                    &check_expr,
-                                    true,
-                                ));
-                            }
+                            new_func.line(check);
                         }
                     }
                     can_fail
@@ -7527,18 +8955,42 @@ fn generate_tag_check(deser_func: &mut dyn CodeBlock, ident: &RustIdent, tag: Op
 }
 
 // This is used mostly for when thing are tagged have specific ranges.
+#[allow(clippy::too_many_arguments)]
 fn generate_wrapper_struct(
     gen_scope: &mut GenerationScope,
     types: &IntermediateTypes,
     type_name: &RustIdent,
     field_type: &RustType,
     min_max: Option<(Option<i128>, Option<i128>)>,
+    fixed_len: Option<usize>,
+    regexp: Option<&str>,
+    bits: Option<&[(String, u64)]>,
+    excluded: Option<&[FixedValue]>,
     struct_config: &RustStructConfig,
     cli: &Cli,
 ) {
-    if min_max.is_some() {
+    let checks_at_runtime = min_max.is_some()
+        || field_type.config.float_bounds.is_some()
+        || regexp.is_some()
+        || bits.is_some()
+        || excluded.is_some();
+    if checks_at_runtime {
         assert!(types.can_new_fail(type_name));
     }
+    // bounded arrays e.g. [1*3 uint] can't be appended to past their max
+    let bounded_array_elem = match (&field_type.conceptual_type, min_max) {
+        (ConceptualRustType::Array(elem), Some((_, Some(max)))) => Some((&**elem, max)),
+        _ => None,
+    };
+    // arrays of an exact length are stored as [T; N] with --fixed-size-arrays
+    let fixed_array_elem = match (&field_type.conceptual_type, fixed_len) {
+        (ConceptualRustType::Array(elem), Some(len)) => Some((&**elem, len)),
+        _ => None,
+    };
+    let inner_type = match fixed_array_elem {
+        Some((elem, len)) => format!("[{}; {len}]", elem.for_rust_member(types, false, cli)),
+        None => field_type.for_rust_member(types, false, cli),
+    };
     if cli.wasm {
         let mut wrapper = create_base_wasm_wrapper(gen_scope, types, type_name, true, cli);
         let mut wasm_new = codegen::Function::new("new");
@@ -7546,7 +8998,24 @@ fn generate_wrapper_struct(
             .arg("inner", field_type.for_wasm_param(types))
             .vis("pub");
 
-        if types.can_new_fail(type_name) {
+        if fixed_array_elem.is_some() {
+            // the wasm side only has Vec-like arrays so the length is checked on conversion
+            wasm_new
+                .ret(format!("Result<{type_name}, JsError>"))
+                .line(format!(
+                    "let inner: {} = {};",
+                    field_type.for_rust_member(types, true, cli),
+                    ToWasmBoundaryOperations::format(
+                        field_type
+                            .from_wasm_boundary_clone(types, "inner", false)
+                            .into_iter()
+                    )
+                ))
+                .line(format!(
+                    "{}::try_from(inner).map(Into::into).map_err(Into::into)",
+                    rust_crate_struct_from_wasm(types, type_name, cli)
+                ));
+        } else if types.can_new_fail(type_name) {
             // you can't use Self in a parameter in wasm_bindgen for some reason
             wasm_new
                 .ret("Result<{}, JsError>")
@@ -7561,13 +9030,58 @@ fn generate_wrapper_struct(
             ));
         }
         if let Some(Some(getter)) = struct_config.newtype_getter.as_ref() {
+            let inner_expr = if fixed_array_elem.is_some() {
+                format!("self.0.{getter}().to_vec()")
+            } else {
+                format!("self.0.{getter}()")
+            };
             let mut get = codegen::Function::new(getter);
             get.vis("pub")
                 .arg_ref_self()
                 .ret(field_type.for_wasm_return(types))
-                .line(field_type.to_wasm_boundary(types, &format!("self.0.{getter}()"), false));
+                .line(field_type.to_wasm_boundary(types, &inner_expr, false));
             wrapper.s_impl.push_fn(get);
         }
+        for (name, _bit) in bits.unwrap_or_default() {
+            let name = convert_to_snake_case(name);
+            wrapper
+                .s_impl
+                .new_fn(&name)
+                .vis("pub")
+                .arg_ref_self()
+                .ret("bool")
+                .line(format!("self.0.{name}()"));
+            wrapper
+                .s_impl
+                .new_fn(&format!("set_{name}"))
+                .vis("pub")
+                .arg_mut_self()
+                .arg(&name, "bool")
+                .line(format!("self.0.set_{name}({name})"));
+        }
+        if let Some((elem, _max)) = bounded_array_elem {
+            wrapper
+                .s_impl
+                .new_fn("len")
+                .vis("pub")
+                .arg_ref_self()
+                .ret("usize")
+                .line("self.0.len()");
+            wrapper
+                .s_impl
+                .new_fn("add")
+                .vis("pub")
+                .arg_mut_self()
+                .arg("elem", elem.for_wasm_param(types))
+                .ret("Result<(), JsError>")
+                .line(format!(
+                    "self.0.add({}).map_err(Into::into)",
+                    ToWasmBoundaryOperations::format(
+                        elem.from_wasm_boundary_clone(types, "elem", false)
+                            .into_iter()
+                    )
+                ));
+        }
         wrapper.push(gen_scope, types);
     }
 
@@ -7591,7 +9105,13 @@ fn generate_wrapper_struct(
     let json_schema_type = if json_hex_bytes {
         Cow::Borrowed("String")
     } else {
-        Cow::Owned(field_type.for_rust_member(types, false, cli))
+        Cow::Owned(inner_type.clone())
+    };
+    // [T; N] needs to be wrapped in <> to call associated functions on it
+    let json_schema_path = if fixed_array_elem.is_some() {
+        Cow::Owned(format!("<{json_schema_type}>"))
+    } else {
+        json_schema_type.clone()
     };
 
     if !struct_config.custom_json {
@@ -7642,16 +9162,19 @@ fn generate_wrapper_struct(
                             FixedValue::Float(_) => "Float(inner)",
                             FixedValue::Nint(_) => "Signed(inner as i64)",
                             FixedValue::Null => "Option",
+                            FixedValue::Undefined => "Unit",
+                            FixedValue::Simple(_) => "Unsigned(inner as u64)",
                             FixedValue::Text(_) => "Str(&inner)",
                             FixedValue::Uint(_) => "Unsigned(inner)",
+                            FixedValue::Bytes(_) => "Bytes(&inner)",
                         },
                         ConceptualRustType::Map(_, _) => "Map",
                         ConceptualRustType::Optional(_) => "Option",
                         ConceptualRustType::Primitive(p) => match p {
                             Primitive::Bool => "Bool(inner)",
                             Primitive::Bytes => "Bytes(&inner)",
-                            Primitive::F32 => "Float(inner as f64)",
-                            Primitive::F64 => "Float(inner)",
+                            Primitive::F16 | Primitive::F32 => "Float(inner as f64)",
+                            Primitive::Float | Primitive::F64 => "Float(inner)",
                             Primitive::I8
                             | Primitive::I16
                             | Primitive::I32
@@ -7690,12 +9213,28 @@ fn generate_wrapper_struct(
             let mut json_schema_fn = codegen::Function::new("json_schema");
             json_schema_fn
                 .arg("gen", "&mut schemars::gen::SchemaGenerator")
-                .ret("schemars::schema::Schema")
-                .line(format!("{json_schema_type}::json_schema(gen)"));
+                .ret("schemars::schema::Schema");
+            match (&field_type.conceptual_type, min_max) {
+                (ConceptualRustType::Array(_), Some((min, max))) => {
+                    json_schema_fn.line(format!(
+                        "let mut schema = <{json_schema_type} as schemars::JsonSchema>::json_schema(gen).into_object();"
+                    ));
+                    if let Some(min) = min {
+                        json_schema_fn.line(format!("schema.array().min_items = Some({min});"));
+                    }
+                    if let Some(max) = max {
+                        json_schema_fn.line(format!("schema.array().max_items = Some({max});"));
+                    }
+                    json_schema_fn.line("schemars::schema::Schema::Object(schema)");
+                }
+                _ => {
+                    json_schema_fn.line(format!("{json_schema_path}::json_schema(gen)"));
+                }
+            }
             let mut is_referenceable = codegen::Function::new("is_referenceable");
             is_referenceable
                 .ret("bool")
-                .line(format!("{json_schema_type}::is_referenceable()"));
+                .line(format!("{json_schema_path}::is_referenceable()"));
             json_schema_impl
                 .impl_trait("schemars::JsonSchema")
                 .push_fn(schema_name_fn)
@@ -7706,7 +9245,7 @@ fn generate_wrapper_struct(
     s.vis("pub");
     let encoding_name = RustIdent::new(CDDLIdent::new(format!("{type_name}Encoding")));
     let enc_fields = if cli.preserve_encodings {
-        s.field("pub inner", field_type.for_rust_member(types, false, cli));
+        s.field("pub inner", &inner_type);
         let enc_fields = encoding_fields(
             types,
             "inner",
@@ -7736,7 +9275,7 @@ fn generate_wrapper_struct(
         }
         Some(enc_fields)
     } else {
-        s.tuple_field(None, field_type.for_rust_member(types, false, cli));
+        s.tuple_field(None, &inner_type);
         None
     };
     // TODO: is there a way to know if the encoding object is also copyable?
@@ -7750,14 +9289,74 @@ fn generate_wrapper_struct(
             get.ret(field_type.for_rust_member(types, false, cli))
                 .line(field_type.clone_if_not_copy(types, self_var));
         } else {
-            get.ret(format!(
-                "&{}",
-                field_type.for_rust_member(types, false, cli)
-            ))
-            .line(format!("&{self_var}"));
+            get.ret(format!("&{inner_type}"))
+                .line(format!("&{self_var}"));
         }
         s_impl.push_fn(get);
     }
+    // bits are numbered from the least significant bit for uints and
+    // from the least significant bit of the first byte for bytes
+    let bits_are_bytes = matches!(
+        field_type.resolve_alias_shallow(),
+        ConceptualRustType::Primitive(Primitive::Bytes)
+    );
+    for (name, bit) in bits.unwrap_or_default() {
+        let name = convert_to_snake_case(name);
+        let mut getter = codegen::Function::new(&name);
+        getter.vis("pub").arg_ref_self().ret("bool");
+        let mut setter = codegen::Function::new(&format!("set_{name}"));
+        setter.vis("pub").arg_mut_self().arg(&name, "bool");
+        if bits_are_bytes {
+            let (index, mask) = (bit / 8, 1u8 << (bit % 8));
+            getter.line(format!(
+                "{self_var}.get({index}).map(|byte| byte & {mask:#04x} != 0).unwrap_or(false)"
+            ));
+            let mut set_block = Block::new(format!("if {name}"));
+            set_block
+                .line(format!(
+                    "if {self_var}.len() <= {index} {{ {self_var}.resize({}, 0); }}",
+                    index + 1
+                ))
+                .line(format!("{self_var}[{index}] |= {mask:#04x};"));
+            set_block.after(format!(
+                " else if let Some(byte) = {self_var}.get_mut({index}) {{ *byte &= !{mask:#04x}; }}"
+            ));
+            setter.push_block(set_block);
+        } else {
+            let mask = 1u64 << bit;
+            getter.line(format!("{self_var} & {mask:#x} != 0"));
+            setter.line(format!(
+                "if {name} {{ {self_var} |= {mask:#x}; }} else {{ {self_var} &= !{mask:#x}; }}"
+            ));
+        }
+        s_impl.push_fn(getter).push_fn(setter);
+    }
+    if let Some((elem, max)) = bounded_array_elem {
+        s_impl
+            .new_fn("len")
+            .vis("pub")
+            .arg_ref_self()
+            .ret("usize")
+            .line(format!("{self_var}.len()"));
+        let mut add = codegen::Function::new("add");
+        add.vis("pub")
+            .arg_mut_self()
+            .arg("elem", elem.for_rust_move(types, cli))
+            .ret("Result<(), DeserializeError>");
+        let mut max_check = Block::new(format!("if {self_var}.len() >= {max}"));
+        max_check.line(format!(
+            "return Err(DeserializeError::new(\"{}\", DeserializeFailure::OccurrenceCheck{{ found: {self_var}.len() + 1, min: {}, max: Some({max}) }}));",
+            type_name,
+            match min_max.and_then(|(min, _)| min) {
+                Some(min) => format!("Some({min})"),
+                None => String::from("None"),
+            },
+        ));
+        add.push_block(max_check)
+            .line(format!("{self_var}.push(elem);"))
+            .line("Ok(())");
+        s_impl.push_fn(add);
+    }
     let mut ser_func = make_serialization_function("serialize", cli);
     let mut ser_impl = make_serialization_impl(type_name.as_ref(), cli);
     gen_scope.generate_serialize(
@@ -7779,15 +9378,18 @@ fn generate_wrapper_struct(
         }
     }
     let mut new_func = codegen::Function::new("new");
-    new_func
-        .arg("inner", field_type.for_rust_move(types, cli))
-        .vis("pub");
+    if fixed_array_elem.is_some() {
+        new_func.arg("inner", &inner_type);
+    } else {
+        new_func.arg("inner", field_type.for_rust_move(types, cli));
+    }
+    new_func.vis("pub");
     let var_names_str = if cli.preserve_encodings {
         encoding_var_names_str(types, "inner", field_type, cli)
     } else {
         "inner".to_owned()
     };
-    let from_impl = if let Some((min, max)) = min_max {
+    let from_impl = if checks_at_runtime {
         let (before, after) = if var_names_str.is_empty() {
             ("".to_owned(), "")
         } else {
@@ -7803,80 +9405,164 @@ fn generate_wrapper_struct(
             )
             .add_to(&mut deser_func);
 
-        let against = if field_type
-            .encodings
-            .contains(&CBOREncodingOperation::CBORBytes)
-        {
-            "inner.len()"
-        } else {
-            match &field_type.conceptual_type {
-                ConceptualRustType::Primitive(p) => match p {
-                    Primitive::Bytes | Primitive::Str => "inner.len()",
-                    Primitive::Bool
-                    | Primitive::F32
-                    | Primitive::F64
-                    | Primitive::U8
-                    | Primitive::U16
-                    | Primitive::U32
-                    | Primitive::U64
-                    | Primitive::I8
-                    | Primitive::I16
-                    | Primitive::I32
-                    | Primitive::I64
-                    | Primitive::N64 => "inner",
-                },
-                _ => unimplemented!(),
-            }
-        };
-        let mut check = match (min, max) {
-            (Some(min), Some(max)) => if min == max {
-                Block::new(format!("if {against} != {min}"))
+        new_func.ret("Result<Self, DeserializeError>");
+        if let Some((min, max)) = min_max {
+            let against = if field_type
+                .encodings
+                .contains(&CBOREncodingOperation::CBORBytes)
+            {
+                "inner.len()"
             } else {
-                let non_negative = field_type.encodings.is_empty() && match &field_type.conceptual_type {
+                match &field_type.conceptual_type {
+                    ConceptualRustType::Array(_) | ConceptualRustType::Map(_, _) => "inner.len()",
                     ConceptualRustType::Primitive(p) => match p {
-                        Primitive::Bytes |
-                        Primitive::Str => true,
-                        Primitive::Bool |
-                        Primitive::U8 |
-                        Primitive::U16 |
-                        Primitive::U32 |
-                        Primitive::U64 => true,
-                        Primitive::I8 |
-                        Primitive::I16 |
-                        Primitive::I32 |
-                        Primitive::I64 |
-                        Primitive::N64 |
-                        Primitive::F32 |
-                        Primitive::F64 => false,
+                        Primitive::Bytes | Primitive::Str => "inner.len()",
+                        Primitive::Bool
+                        | Primitive::Float
+                        | Primitive::F16
+                        | Primitive::F32
+                        | Primitive::F64
+                        | Primitive::U8
+                        | Primitive::U16
+                        | Primitive::U32
+                        | Primitive::U64
+                        | Primitive::I8
+                        | Primitive::I16
+                        | Primitive::I32
+                        | Primitive::I64
+                        | Primitive::N64 => "inner",
                     },
                     _ => unimplemented!(),
-                };
-                if min == 0 && non_negative {
-                    Block::new(format!("if {against} > {max}"))
+                }
+            };
+            let mut check = match (min, max) {
+                (Some(min), Some(max)) => if min == max {
+                    Block::new(format!("if {against} != {min}"))
                 } else {
-                    Block::new(format!("if {against} < {min} || {against} > {max}"))
+                    let non_negative = field_type.encodings.is_empty() && match &field_type.conceptual_type {
+                        ConceptualRustType::Array(_) | ConceptualRustType::Map(_, _) => true,
+                        ConceptualRustType::Primitive(p) => match p {
+                            Primitive::Bytes |
+                            Primitive::Str => true,
+                            Primitive::Bool |
+                            Primitive::U8 |
+                            Primitive::U16 |
+                            Primitive::U32 |
+                            Primitive::U64 => true,
+                            Primitive::I8 |
+                            Primitive::I16 |
+                            Primitive::I32 |
+                            Primitive::I64 |
+                            Primitive::N64 |
+                            Primitive::Float |
+                            Primitive::F16 |
+                            Primitive::F32 |
+                            Primitive::F64 => false,
+                        },
+                        _ => unimplemented!(),
+                    };
+                    if min == 0 && non_negative {
+                        Block::new(format!("if {against} > {max}"))
+                    } else {
+                        Block::new(format!("if {against} < {min} || {against} > {max}"))
+                    }
+                },
+                (Some(min), None) => Block::new(format!("if {against} < {min}")),
+                (None, Some(max)) => Block::new(format!("if {against} > {max}")),
+                (None, None) => panic!("How did we end up with a range requirement of (None, None)? Entire thing should've been None then"),
+            };
+            let (failure, found_cast) = match &field_type.conceptual_type {
+                ConceptualRustType::Array(_) | ConceptualRustType::Map(_, _) => {
+                    ("OccurrenceCheck", "")
                 }
-            },
-            (Some(min), None) => Block::new(format!("if {against} < {min}")),
-            (None, Some(max)) => Block::new(format!("if {against} > {max}")),
-            (None, None) => panic!("How did we end up with a range requirement of (None, None)? Entire thing should've been None then"),
-        };
-        check.line(format!(
-            "return Err(DeserializeError::new(\"{}\", DeserializeFailure::RangeCheck{{ found: {} as isize, min: {}, max: {} }}));",
-            type_name,
-            against,
-            match min {
-                Some(min) => format!("Some({min})"),
-                None => String::from("None")
-            },
-            match max {
-                Some(max) => format!("Some({max})"),
-                None => String::from("None")
-            }));
-        deser_func.push_block(check.clone());
-        new_func
-            .ret("Result<Self, DeserializeError>")
-            .push_block(check);
+                _ => ("RangeCheck", " as isize"),
+            };
+            check.line(format!(
+                "return Err(DeserializeError::new(\"{}\", DeserializeFailure::{}{{ found: {}{}, min: {}, max: {} }}));",
+                type_name,
+                failure,
+                against,
+                found_cast,
+                match min {
+                    Some(min) => format!("Some({min})"),
+                    None => String::from("None")
+                },
+                match max {
+                    Some(max) => format!("Some({max})"),
+                    None => String::from("None")
+                }));
+            deser_func.push_block(check.clone());
+            new_func.push_block(check);
+        }
+        if let Some(float_bounds) = &field_type.config.float_bounds {
+            // deserializing field_type already checks these so this is only needed in new()
+            let against = match &field_type.conceptual_type {
+                ConceptualRustType::Primitive(Primitive::F16 | Primitive::F32) => "(inner as f64)",
+                _ => "inner",
+            };
+            let mut check = Block::new(format!("if {}", float_bounds.out_of_bounds_expr(against)));
+            check.line(format!(
+                "return Err(DeserializeError::new(\"{type_name}\", DeserializeFailure::FloatRangeCheck{{ found: {against}, range: String::from(\"{float_bounds}\") }}));"
+            ));
+            new_func.push_block(check);
+        }
+        if let Some(regexp) = regexp {
+            // CDDL (XSD) regexes are implicitly anchored so they must match the entire string
+            let mut regex_fn = codegen::Function::new("regex");
+            regex_fn
+                .ret("&'static regex::Regex")
+                .line(
+                    "static REGEX: once_cell::sync::OnceCell<regex::Regex> = once_cell::sync::OnceCell::new();",
+                )
+                .line(format!(
+                    "REGEX.get_or_init(|| regex::Regex::new({:?}).unwrap())",
+                    format!("^(?:{regexp})$")
+                ));
+            s_impl.push_fn(regex_fn);
+            let mut check = Block::new("if !Self::regex().is_match(&inner)");
+            check.line(format!(
+                "return Err(DeserializeError::new(\"{type_name}\", DeserializeFailure::RegexMismatch{{ found: inner.clone(), regex: String::from({regexp:?}) }}));"
+            ));
+            deser_func.push_block(check.clone());
+            new_func.push_block(check);
+        }
+        if let Some(bits) = bits {
+            let mut check = if bits_are_bytes {
+                let max_index = bits.iter().map(|(_name, bit)| bit / 8).max().unwrap_or(0);
+                let mut byte_masks = vec![0u8; max_index as usize + 1];
+                for (_name, bit) in bits {
+                    byte_masks[(bit / 8) as usize] |= 1u8 << (bit % 8);
+                }
+                let mut check = Block::new(format!(
+                    "if let Some(undeclared_bit) = inner.iter().enumerate().find_map(|(i, byte)| {{ let undeclared = byte & !{:?}.get(i).copied().unwrap_or(0u8); (undeclared != 0).then(|| 8 * i as u64 + undeclared.trailing_zeros() as u64) }})",
+                    byte_masks
+                ));
+                check.line(format!(
+                    "return Err(DeserializeError::new(\"{type_name}\", DeserializeFailure::UndeclaredBitSet(undeclared_bit)));"
+                ));
+                check
+            } else {
+                let mask = bits
+                    .iter()
+                    .fold(0u64, |mask, (_name, bit)| mask | (1u64 << bit));
+                let mut check = Block::new(format!("if inner & !{mask:#x} != 0"));
+                check.line(format!(
+                    "return Err(DeserializeError::new(\"{type_name}\", DeserializeFailure::UndeclaredBitSet((inner & !{mask:#x}).trailing_zeros() as u64)));"
+                ));
+                check
+            };
+            deser_func.push_block(check.clone());
+            new_func.push_block(check);
+        }
+        for value in excluded.unwrap_or_default() {
+            let mut check = Block::new(format!("if inner == {}", value.to_primitive_str_compare()));
+            check.line(format!(
+                "return Err(DeserializeError::new(\"{type_name}\", DeserializeFailure::ExcludedValue(String::from({:?}))));",
+                value.to_cddl_str()
+            ));
+            deser_func.push_block(check.clone());
+            new_func.push_block(check);
+        }
         if let Some(enc_fields) = &enc_fields {
             let mut deser_ctor = Block::new("Ok(Self");
             deser_ctor.line("inner,");
@@ -7930,6 +9616,12 @@ fn generate_wrapper_struct(
         // };
         // gen_scope.generate_deserialize(types, &field_type_tagged, "inner", "Ok(Self(", "))", false, false, true, &mut deser_func);
         new_func.ret("Self");
+        // the array is read into a Vec first so its length is checked before converting to [T; N]
+        let fixed_len_check = fixed_array_elem.map(|(_elem, len)| {
+            format!(
+                "let inner = <{inner_type}>::try_from(inner).map_err(|inner| DeserializeError::new(\"{type_name}\", DeserializeFailure::OccurrenceCheck{{ found: inner.len(), min: Some({len}), max: Some({len}) }}))?;"
+            )
+        });
         if let Some(enc_fields) = &enc_fields {
             let (before, after) = if var_names_str.is_empty() {
                 ("".to_owned(), "")
@@ -7945,6 +9637,9 @@ fn generate_wrapper_struct(
                     cli,
                 )
                 .add_to(&mut deser_func);
+            if let Some(fixed_len_check) = &fixed_len_check {
+                deser_func.line(fixed_len_check);
+            }
 
             let mut deser_ctor = Block::new("Ok(Self");
             deser_ctor.line("inner,");
@@ -7965,6 +9660,18 @@ fn generate_wrapper_struct(
                 ctor_block.line("encodings: None,");
             }
             new_func.push_block(ctor_block);
+        } else if let Some(fixed_len_check) = &fixed_len_check {
+            gen_scope
+                .generate_deserialize(
+                    types,
+                    field_type.into(),
+                    DeserializeBeforeAfter::new("let inner = ", ";", false),
+                    DeserializeConfig::new("inner"),
+                    cli,
+                )
+                .add_to(&mut deser_func);
+            deser_func.line(fixed_len_check).line("Ok(Self(inner))");
+            new_func.line("Self(inner)");
         } else {
             gen_scope
                 .generate_deserialize(
@@ -7979,27 +9686,28 @@ fn generate_wrapper_struct(
         }
 
         let mut from = codegen::Impl::new(type_name.to_string());
-        from.impl_trait(format!(
-            "From<{}>",
-            field_type.for_rust_member(types, false, cli)
-        ))
-        .new_fn("from")
-        .arg("inner", field_type.for_rust_member(types, false, cli))
-        .ret("Self")
-        .line(format!(
-            "{}::new({})",
-            type_name,
-            ToWasmBoundaryOperations::format(
-                field_type
-                    .from_wasm_boundary_clone(types, "inner", false)
-                    .into_iter()
-            )
-        ));
+        from.impl_trait(format!("From<{inner_type}>"))
+            .new_fn("from")
+            .arg("inner", &inner_type)
+            .ret("Self");
+        if fixed_array_elem.is_some() {
+            from.line(format!("{type_name}::new(inner)"));
+        } else {
+            from.line(format!(
+                "{}::new({})",
+                type_name,
+                ToWasmBoundaryOperations::format(
+                    field_type
+                        .from_wasm_boundary_clone(types, "inner", false)
+                        .into_iter()
+                )
+            ));
+        }
         from
     };
     deser_impl.push_fn(deser_func);
     s_impl.push_fn(new_func);
-    let mut from_inner_impl = codegen::Impl::new(field_type.for_rust_member(types, false, cli));
+    let mut from_inner_impl = codegen::Impl::new(&inner_type);
     from_inner_impl
         .impl_trait(format!("From<{type_name}>"))
         .new_fn("from")
@@ -8012,6 +9720,20 @@ fn generate_wrapper_struct(
         .push_impl(s_impl)
         .push_impl(from_impl)
         .push_impl(from_inner_impl);
+    if let Some((_elem, len)) = fixed_array_elem {
+        let vec_type = field_type.for_rust_member(types, false, cli);
+        let mut try_from_vec = codegen::Impl::new(type_name.to_string());
+        try_from_vec
+            .associate_type("Error", "DeserializeError")
+            .impl_trait(format!("TryFrom<{vec_type}>"))
+            .new_fn("try_from")
+            .arg("inner", &vec_type)
+            .ret("Result<Self, Self::Error>")
+            .line(format!(
+                "<{inner_type}>::try_from(inner).map(Self::new).map_err(|inner| DeserializeError::new(\"{type_name}\", DeserializeFailure::OccurrenceCheck{{ found: inner.len(), min: Some({len}), max: Some({len}) }}))"
+            ));
+        gen_scope.rust(types, type_name).push_impl(try_from_vec);
+    }
     if !struct_config.custom_json {
         if cli.json_serde_derives {
             gen_scope
@@ -8298,12 +10020,486 @@ fn generate_int(gen_scope: &mut GenerationScope, types: &IntermediateTypes, cli:
         .push_impl(display)
         .push_impl(from_str)
         .push_impl(try_from_i128);
+
+    // conversions to/from the big integer types (integer = int / bigint)
+    if static_extern_used(types, BIG_INT_IDENT) {
+        let mut from_int = codegen::Impl::new(BIG_INT_IDENT);
+        let mut from_int_match = Block::new("match x");
+        if cli.preserve_encodings {
+            from_int_match
+                .line("Int::Uint { value, .. } => Self::from(value),")
+                .line("Int::Nint { value, .. } => Self::from(-(value as i128) - 1),");
+        } else {
+            from_int_match
+                .line("Int::Uint(x) => Self::from(x),")
+                .line("Int::Nint(x) => Self::from(-(x as i128) - 1),");
+        }
+        from_int
+            .impl_trait("From<Int>")
+            .new_fn("from")
+            .arg("x", "Int")
+            .ret("Self")
+            .push_block(from_int_match);
+
+        let big_int_error = format!("{}::big_int::BigIntError", cli.common_import_rust());
+        let mut try_from_big_int = codegen::Impl::new("Int");
+        try_from_big_int
+            .impl_trait(format!("TryFrom<{BIG_INT_IDENT}>"))
+            .associate_type("Error", &big_int_error)
+            .new_fn("try_from")
+            .arg("x", BIG_INT_IDENT)
+            .ret("Result<Self, Self::Error>")
+            .line(format!(
+                "x.as_i128().and_then(|x| Self::try_from(x).ok()).ok_or({big_int_error}::OutOfBounds)"
+            ));
+        gen_scope
+            .rust_lib()
+            .push_impl(from_int)
+            .push_impl(try_from_big_int);
+    }
     gen_scope
         .rust_serialize_lib()
         .push_impl(ser_impl)
         .push_impl(deser_impl);
 }
 
+// a user type of the same name doesn't count (see IntermediateTypes::register_static_externs())
+fn static_extern_used(types: &IntermediateTypes, ident: &str) -> bool {
+    let ident = RustIdent::new(CDDLIdent::new(ident));
+    types.is_static_extern(&ident) && types.is_referenced(&ident)
+}
+
+fn big_int_used(types: &IntermediateTypes) -> bool {
+    [
+        BIG_UINT_IDENT,
+        BIG_INT_IDENT,
+        DECIMAL_FRACTION_IDENT,
+        BIG_FLOAT_IDENT,
+    ]
+    .iter()
+    .any(|ident| static_extern_used(types, ident))
+}
+
+// The rust-side BigUInt/BigInt/DecimalFraction/BigFloat are in the static files so only the wasm wrappers are generated
+fn generate_big_int(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    ident: &RustIdent,
+    cli: &Cli,
+) {
+    if !cli.wasm {
+        return;
+    }
+    let mut wrapper = create_base_wasm_wrapper(gen_scope, types, ident, true, cli);
+
+    let mut to_str = codegen::Function::new("to_str");
+    to_str
+        .vis("pub")
+        .arg_ref_self()
+        .ret("String")
+        .line("self.0.to_string()");
+
+    let mut from_str = codegen::Function::new("from_str");
+    from_str
+        .attr("allow(clippy::should_implement_trait)")
+        .vis("pub")
+        .arg("string", "&str")
+        .ret(format!("Result<{ident}, JsError>"))
+        .line("// have to redefine so it's visible in WASM")
+        .line(format!("std::str::FromStr::from_str(string).map(Self).map_err(|e| JsError::new(&format!(\"{ident}.from_str({{}}): {{:?}}\", string, e)))"));
+
+    wrapper.s_impl.push_fn(to_str).push_fn(from_str);
+
+    match ident.as_ref() {
+        DECIMAL_FRACTION_IDENT | BIG_FLOAT_IDENT => {
+            let base = if ident.as_ref() == DECIMAL_FRACTION_IDENT {
+                10
+            } else {
+                2
+            };
+            wrapper
+                .s_impl
+                .new_fn("exponent")
+                .doc(format!("Base {base} exponent"))
+                .arg_ref_self()
+                .vis("pub")
+                .ret("i64")
+                .line("self.0.exponent()");
+            // the mantissa is returned as a string since BigInt's wrapper isn't always generated
+            wrapper
+                .s_impl
+                .new_fn("mantissa")
+                .doc("Mantissa as a (base 10) integer string")
+                .arg_ref_self()
+                .vis("pub")
+                .ret("String")
+                .line("self.0.mantissa().to_string()");
+        }
+        _ => {
+            let accessors: &[(&str, &str)] = if ident.as_ref() == BIG_UINT_IDENT {
+                &[("as_u64", "u64")]
+            } else {
+                &[("as_u64", "u64"), ("as_i64", "i64")]
+            };
+            for (getter, ret) in accessors {
+                wrapper
+                    .s_impl
+                    .new_fn(getter)
+                    .doc("Value as a native integer if it fits. None (undefined) otherwise")
+                    .arg_ref_self()
+                    .vis("pub")
+                    .ret(format!("Option<{ret}>"))
+                    .line(format!("self.0.{getter}()"));
+            }
+        }
+    }
+    wrapper.push(gen_scope, types);
+}
+
+fn date_time_used(types: &IntermediateTypes) -> bool {
+    [DATE_TIME_IDENT, EPOCH_TIME_IDENT]
+        .iter()
+        .any(|ident| static_extern_used(types, ident))
+}
+
+// The rust-side DateTime/EpochTime are in the static files so only the wasm wrappers are generated
+fn generate_date_time(
+    gen_scope: &mut GenerationScope,
+    types: &IntermediateTypes,
+    ident: &RustIdent,
+    cli: &Cli,
+) {
+    if !cli.wasm {
+        return;
+    }
+    let native_name = rust_crate_struct_from_wasm(types, ident, cli);
+    let mut wrapper = create_base_wasm_wrapper(gen_scope, types, ident, true, cli);
+
+    if ident.as_ref() == DATE_TIME_IDENT {
+        let mut to_str = codegen::Function::new("to_str");
+        to_str
+            .doc("RFC 3339 text")
+            .vis("pub")
+            .arg_ref_self()
+            .ret("String")
+            .line("self.0.to_text()");
+
+        let mut from_str = codegen::Function::new("from_str");
+        from_str
+            .attr("allow(clippy::should_implement_trait)")
+            .doc("* `string` - RFC 3339 date/time e.g. `2023-01-02T03:04:05Z`")
+            .vis("pub")
+            .arg("string", "&str")
+            .ret(format!("Result<{ident}, JsError>"))
+            .line(format!("{native_name}::new(string).map(Self).map_err(|e| JsError::new(&format!(\"{ident}.from_str({{}}): {{}}\", string, e)))"));
+
+        let mut from_js_date = codegen::Function::new("from_js_date");
+        from_js_date
+            .vis("pub")
+            .arg("date", "&js_sys::Date")
+            .ret(format!("Result<{ident}, JsError>"))
+            .line(format!("{native_name}::new(&String::from(date.to_iso_string())).map(Self).map_err(|e| JsError::new(&format!(\"{ident}.from_js_date(): {{}}\", e)))"));
+
+        wrapper
+            .s_impl
+            .push_fn(to_str)
+            .push_fn(from_str)
+            .push_fn(from_js_date);
+    } else {
+        let mut new_func = codegen::Function::new("new");
+        new_func
+            .doc("* `secs` - Whole seconds since the unix epoch")
+            .vis("pub")
+            .arg("secs", "i64")
+            .ret(format!("Result<{ident}, JsError>"))
+            .line(format!("{native_name}::new(secs).map(Self).map_err(|e| JsError::new(&format!(\"{ident}.new({{}}): {{}}\", secs, e)))"));
+
+        let mut from_f64 = codegen::Function::new("from_f64");
+        from_f64
+            .doc("* `secs` - Seconds since the unix epoch")
+            .vis("pub")
+            .arg("secs", "f64")
+            .ret(format!("Result<{ident}, JsError>"))
+            .line(format!("{native_name}::from_f64(secs).map(Self).map_err(|e| JsError::new(&format!(\"{ident}.from_f64({{}}): {{}}\", secs, e)))"));
+
+        let mut from_js_date = codegen::Function::new("from_js_date");
+        from_js_date
+            .vis("pub")
+            .arg("date", "&js_sys::Date")
+            .ret(format!("Result<{ident}, JsError>"))
+            .line(format!("{native_name}::from_f64(date.get_time() / 1000.0).map(Self).map_err(|e| JsError::new(&format!(\"{ident}.from_js_date(): {{}}\", e)))"));
+
+        let mut as_secs = codegen::Function::new("as_secs");
+        as_secs
+            .doc("Whole seconds since the unix epoch (rounded down)")
+            .vis("pub")
+            .arg_ref_self()
+            .ret("i64")
+            .line("self.0.as_secs()");
+
+        let mut as_f64 = codegen::Function::new("as_f64");
+        as_f64
+            .vis("pub")
+            .arg_ref_self()
+            .ret("f64")
+            .line("self.0.as_f64()");
+
+        wrapper
+            .s_impl
+            .push_fn(new_func)
+            .push_fn(from_f64)
+            .push_fn(from_js_date)
+            .push_fn(as_secs)
+            .push_fn(as_f64);
+    }
+    wrapper
+        .s_impl
+        .new_fn("to_js_date")
+        .vis("pub")
+        .arg_ref_self()
+        .ret("js_sys::Date")
+        .line(
+            "js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(self.0.as_datetime().timestamp_millis() as f64))",
+        );
+    wrapper.push(gen_scope, types);
+}
+
+// The rust-side CBORValue (any) is in the static files so only the wasm wrapper is generated
+fn generate_cbor_value(gen_scope: &mut GenerationScope, types: &IntermediateTypes, cli: &Cli) {
+    if !cli.wasm {
+        return;
+    }
+    let ident = RustIdent::new(CDDLIdent::new(CBOR_VALUE_IDENT));
+    let native_name = rust_crate_struct_from_wasm(types, &ident, cli);
+    let special_name = format!("{}::cbor_value::CBORSpecial", cli.common_import_wasm());
+    let mut wrapper = create_base_wasm_wrapper(gen_scope, types, &ident, true, cli);
+
+    // also create a wasm-exposed enum just to distinguish the type
+    let kind_name = format!("{ident}Kind");
+    let mut kind = codegen::Enum::new(&kind_name);
+    kind.vis("pub");
+    let kinds = [
+        "Uint", "Nint", "Bytes", "Text", "Array", "Map", "Tag", "Special",
+    ];
+    for variant in kinds {
+        kind.new_variant(variant);
+    }
+    kind.attr("wasm_bindgen");
+    gen_scope.wasm(types, &ident).push_enum(kind);
+
+    // new
+    for (variant, arg_type) in [
+        ("uint", "u64"),
+        ("nint", "u64"),
+        ("bytes", "Vec<u8>"),
+        ("text", "String"),
+    ] {
+        let mut new_func = codegen::Function::new(&format!("new_{variant}"));
+        new_func
+            .vis("pub")
+            .arg("value", arg_type)
+            .ret("Self")
+            .line(format!("Self({native_name}::new_{variant}(value))"));
+        if variant == "nint" {
+            new_func.doc("* `value` - Value as encoded in CBOR - note: a negative `x` here would be `|x + 1|` due to CBOR's `nint` encoding e.g. to represent -5, pass in 4.");
+        }
+        wrapper.s_impl.push_fn(new_func);
+    }
+    wrapper
+        .s_impl
+        .new_fn("new_tag")
+        .vis("pub")
+        .arg("tag", "u64")
+        .arg("value", &format!("&{ident}"))
+        .ret("Self")
+        .line(format!(
+            "Self({native_name}::new_tag(tag, value.clone().into()))"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("new_bool")
+        .vis("pub")
+        .arg("value", "bool")
+        .ret("Self")
+        .line(format!(
+            "Self({native_name}::new_special({special_name}::Bool(value)))"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("new_float")
+        .vis("pub")
+        .arg("value", "f64")
+        .ret("Self")
+        .line(format!(
+            "Self({native_name}::new_special({special_name}::Float(value)))"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("new_null")
+        .vis("pub")
+        .ret("Self")
+        .line(format!(
+            "Self({native_name}::new_special({special_name}::Null))"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("new_undefined")
+        .vis("pub")
+        .ret("Self")
+        .line(format!(
+            "Self({native_name}::new_special({special_name}::Undefined))"
+        ));
+
+    // kind() getter
+    let mut get_kind_match = Block::new("match &self.0");
+    for variant in kinds {
+        get_kind_match.line(format!(
+            "{native_name}::{variant} {{ .. }} => {kind_name}::{variant},"
+        ));
+    }
+    wrapper
+        .s_impl
+        .new_fn("kind")
+        .arg_ref_self()
+        .vis("pub")
+        .ret(&kind_name)
+        .push_block(get_kind_match);
+
+    // as_{variant} getters (returns None -> undefined when not the type)
+    for (variant, ret, conversion) in [
+        ("uint", "u64", ""),
+        ("nint", "u64", ""),
+        ("bytes", "Vec<u8>", ".cloned()"),
+        ("text", "String", ".cloned()"),
+    ] {
+        let mut as_variant = codegen::Function::new(&format!("as_{variant}"));
+        as_variant
+            .arg_ref_self()
+            .vis("pub")
+            .ret(format!("Option<{ret}>"))
+            .line(format!("self.0.as_{variant}(){conversion}"));
+        if variant == "nint" {
+            as_variant.doc("Value as encoded in CBOR - note: a negative `x` here would be `|x + 1|` due to CBOR's `nint` encoding e.g. -5 is returned as 4.");
+        }
+        wrapper.s_impl.push_fn(as_variant);
+    }
+    wrapper
+        .s_impl
+        .new_fn("as_bool")
+        .arg_ref_self()
+        .vis("pub")
+        .ret("Option<bool>")
+        .line(format!(
+            "self.0.as_special().and_then(|special| match special {{ {special_name}::Bool(b) => Some(b), _ => None }})"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("as_float")
+        .arg_ref_self()
+        .vis("pub")
+        .ret("Option<f64>")
+        .line(format!(
+            "self.0.as_special().and_then(|special| match special {{ {special_name}::Float(f) => Some(f), _ => None }})"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("tag")
+        .doc("Tag number if this is a tagged value")
+        .arg_ref_self()
+        .vis("pub")
+        .ret("Option<u64>")
+        .line("self.0.as_tagged().map(|(tag, _value)| tag)");
+    wrapper
+        .s_impl
+        .new_fn("tagged_value")
+        .doc("Value wrapped by the tag if this is a tagged value")
+        .arg_ref_self()
+        .vis("pub")
+        .ret(format!("Option<{ident}>"))
+        .line("self.0.as_tagged().map(|(_tag, value)| value.clone().into())");
+
+    // arrays / maps
+    wrapper
+        .s_impl
+        .new_fn("len")
+        .doc("Number of elements (array) or entries (map). None for any other kind")
+        .arg_ref_self()
+        .vis("pub")
+        .ret("Option<usize>")
+        .line("self.0.as_array().map(Vec::len).or_else(|| self.0.as_map().map(|map| map.len()))");
+    wrapper
+        .s_impl
+        .new_fn("get_array_elem")
+        .arg_ref_self()
+        .arg("index", "usize")
+        .vis("pub")
+        .ret(format!("Option<{ident}>"))
+        .line(
+            "self.0.as_array().and_then(|elems| elems.get(index)).map(|elem| elem.clone().into())",
+        );
+    wrapper
+        .s_impl
+        .new_fn("get_map_key")
+        .arg_ref_self()
+        .arg("index", "usize")
+        .vis("pub")
+        .ret(format!("Option<{ident}>"))
+        .line(
+            "self.0.as_map().and_then(|map| map.keys().nth(index)).map(|key| key.clone().into())",
+        );
+    wrapper
+        .s_impl
+        .new_fn("get_map_value")
+        .arg_ref_self()
+        .arg("key", &format!("&{ident}"))
+        .vis("pub")
+        .ret(format!("Option<{ident}>"))
+        .line("self.0.as_map().and_then(|map| map.get(key.as_ref())).map(|value| value.clone().into())");
+    wrapper.push(gen_scope, types);
+}
+
+fn cbor_value_used(types: &IntermediateTypes) -> bool {
+    [CBOR_VALUE_IDENT, TAGGED_VALUE_IDENT]
+        .iter()
+        .any(|ident| static_extern_used(types, ident))
+}
+
+// The rust-side TaggedValue is in the static files (cbor_value.rs) so only the wasm wrapper is generated
+fn generate_tagged_value(gen_scope: &mut GenerationScope, types: &IntermediateTypes, cli: &Cli) {
+    if !cli.wasm {
+        return;
+    }
+    let ident = RustIdent::new(CDDLIdent::new(TAGGED_VALUE_IDENT));
+    let native_name = rust_crate_struct_from_wasm(types, &ident, cli);
+    let mut wrapper = create_base_wasm_wrapper(gen_scope, types, &ident, true, cli);
+    wrapper
+        .s_impl
+        .new_fn("new")
+        .vis("pub")
+        .arg("tag", "u64")
+        .arg("value", &format!("&{CBOR_VALUE_IDENT}"))
+        .ret("Self")
+        .line(format!(
+            "Self({native_name}::new(tag, value.clone().into()))"
+        ));
+    wrapper
+        .s_impl
+        .new_fn("tag")
+        .arg_ref_self()
+        .vis("pub")
+        .ret("u64")
+        .line("self.0.tag()");
+    wrapper
+        .s_impl
+        .new_fn("value")
+        .doc("Value wrapped by the tag")
+        .arg_ref_self()
+        .vis("pub")
+        .ret(CBOR_VALUE_IDENT)
+        .line("self.0.value().clone().into()");
+    wrapper.push(gen_scope, types);
+}
+
 /// Gets the rustfmt path to rustfmt the generated bindings.
 fn rustfmt_path<'a>() -> std::io::Result<Cow<'a, std::path::PathBuf>> {
     if let Ok(rustfmt) = std::env::var("RUSTFMT") {
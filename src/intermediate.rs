@@ -9,12 +9,41 @@ use crate::comment_ast::RuleMetadata;
 use crate::generation::table_type;
 use crate::utils::{
     cddl_prelude, convert_to_camel_case, convert_to_snake_case, is_identifier_reserved,
-    is_identifier_user_defined,
+    is_identifier_user_defined, to_hex,
 };
 
 use once_cell::sync::Lazy;
 pub static ROOT_SCOPE: Lazy<ModuleScope> = Lazy::new(|| vec![String::from("lib")].into());
 
+/// Rust type that the prelude's `any` maps to. Defined in the static files (cbor_value.rs)
+pub const CBOR_VALUE_IDENT: &str = "CBORValue";
+/// Rust type that tag-agnostic tagged values (`#6` / `#6(any)`) map to. Also defined in cbor_value.rs
+pub const TAGGED_VALUE_IDENT: &str = "TaggedValue";
+/// Rust types that the prelude's big integer/decfrac/bigfloat types map to. Defined in the static files (big_int.rs)
+pub const BIG_UINT_IDENT: &str = "BigUInt";
+pub const BIG_INT_IDENT: &str = "BigInt";
+pub const DECIMAL_FRACTION_IDENT: &str = "DecimalFraction";
+pub const BIG_FLOAT_IDENT: &str = "BigFloat";
+/// Rust types that the prelude's tdate/time map to with --typed-date-time. Defined in the static files (date_time.rs)
+pub const DATE_TIME_IDENT: &str = "DateTime";
+pub const EPOCH_TIME_IDENT: &str = "EpochTime";
+
+// Static file modules whose types are only registered once used (see register_static_externs())
+// as (rust types defined there, other names re-exported at the crate root along with them)
+static STATIC_EXTERN_MODULES: [(&[&str], &[&str]); 3] = [
+    (&[CBOR_VALUE_IDENT, TAGGED_VALUE_IDENT], &["CBORSpecial"]),
+    (
+        &[
+            BIG_UINT_IDENT,
+            BIG_INT_IDENT,
+            DECIMAL_FRACTION_IDENT,
+            BIG_FLOAT_IDENT,
+        ],
+        &[],
+    ),
+    (&[DATE_TIME_IDENT, EPOCH_TIME_IDENT], &[]),
+];
+
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct ModuleScope {
     export: bool,
@@ -103,6 +132,10 @@ impl<'a> PlainGroupInfo<'a> {
             rule_metadata,
         }
     }
+
+    pub fn group(&self) -> Option<&cddl::ast::Group<'a>> {
+        self.group.as_ref()
+    }
 }
 
 #[derive(Debug)]
@@ -115,15 +148,29 @@ pub struct IntermediateTypes<'a> {
     // Some(group) = directly defined in .cddl (must call set_plain_group_representatio() later)
     // None = indirectly generated due to a group choice (no reason to call set_rep_if_plain_group() later but it won't crash)
     plain_groups: BTreeMap<RustIdent, PlainGroupInfo<'a>>,
+    // array/map definitions that could be spliced into other groups via the unwrap operator ~
+    // these only become plain groups once they are actually unwrapped somewhere
+    unwrappable_groups: BTreeMap<RustIdent, PlainGroupInfo<'a>>,
     type_aliases: BTreeMap<AliasIdent, AliasInfo>,
     rust_structs: BTreeMap<RustIdent, RustStruct>,
     prelude_to_emit: BTreeSet<String>,
     generic_defs: BTreeMap<RustIdent, GenericDef>,
+    // generic definitions that haven't been parsed yet. Instances need their definition to be resolved
+    // (e.g. aliases like foo<T> = T / null are resolved immediately) so these are parsed on first use
+    // instead of depending on the order the rules are parsed in
+    unparsed_generic_rules: BTreeMap<RustIdent, cddl::ast::Rule<'a>>,
     generic_instances: BTreeMap<RustIdent, GenericInstance>,
+    // params of all generic definitions e.g. T in foo<T> = [T]
+    generic_params: BTreeSet<RustIdent>,
     news_can_fail: BTreeSet<RustIdent>,
     used_as_key: BTreeSet<RustIdent>,
+    // names derived for anonymous types defined inline (e.g. the map in foo = [bar: { baz: uint }])
+    // along with the cddl they were defined by so they can't silently replace other types
+    inline_idents: BTreeMap<RustIdent, String>,
     // which scope an ident is declared in
     scopes: BTreeMap<RustIdent, ModuleScope>,
+    // types from the static files (e.g. BigInt) that are used and thus registered in rust_structs
+    static_externs: BTreeSet<RustIdent>,
     // for scope() to work we keep this here.
     // Returning a reference to the const ROOT_SCOPE complains of returning a temporary
     root_scope: ModuleScope,
@@ -142,20 +189,70 @@ impl<'a> IntermediateTypes<'a> {
             RustIdent::new(CDDLIdent::new("int")),
             RustStruct::new_extern(RustIdent::new(CDDLIdent::new("int"))),
         );
+        // any / biguint / tdate (with --typed-date-time) / etc are only registered once used
+        // as they're defined in the static files (see register_static_externs())
         Self {
             plain_groups: BTreeMap::new(),
+            unwrappable_groups: BTreeMap::new(),
             type_aliases: Self::aliases(),
             rust_structs,
             prelude_to_emit: BTreeSet::new(),
             generic_defs: BTreeMap::new(),
+            unparsed_generic_rules: BTreeMap::new(),
             generic_instances: BTreeMap::new(),
+            generic_params: BTreeSet::new(),
             news_can_fail: BTreeSet::new(),
             used_as_key: BTreeSet::new(),
+            inline_idents: BTreeMap::new(),
             scopes: BTreeMap::new(),
+            static_externs: BTreeSet::new(),
             root_scope: ROOT_SCOPE.clone(),
         }
     }
 
+    /// Rust type for a type defined in the static files e.g. TaggedValue, registering it if needed
+    pub fn static_extern_type(&mut self, ident: &str) -> RustType {
+        let ident = RustIdent::new(CDDLIdent::new(ident));
+        self.register_static_externs(&ident);
+        ConceptualRustType::Rust(ident).into()
+    }
+
+    /// if this is a type defined in the static files (e.g. BigInt) instead of a user-defined one
+    pub fn is_static_extern(&self, ident: &RustIdent) -> bool {
+        self.static_externs.contains(ident)
+    }
+
+    // registers all types of the static module {ident} is defined in (if it is one) as they are
+    // all re-exported together. Done lazily so user rules can use these names when they're not needed.
+    fn register_static_externs(&mut self, ident: &RustIdent) {
+        if self.static_externs.contains(ident) {
+            return;
+        }
+        let (idents, other_exports) = match STATIC_EXTERN_MODULES
+            .iter()
+            .find(|(idents, _)| idents.contains(&ident.as_ref()))
+        {
+            Some(module) => module,
+            None => return,
+        };
+        for name in idents.iter().chain(other_exports.iter()) {
+            if self.has_rule(&RustIdent::new(CDDLIdent::new(*name))) {
+                panic!(
+                    "A rule generating the type {} collides with the type of the same name needed for the prelude's {}. Please rename it",
+                    name, ident
+                );
+            }
+        }
+        for name in idents.iter() {
+            let name_ident = RustIdent::new(CDDLIdent::new(*name));
+            self.rust_structs.insert(
+                name_ident.clone(),
+                RustStruct::new_extern(name_ident.clone()),
+            );
+            self.static_externs.insert(name_ident);
+        }
+    }
+
     #[allow(unused)]
     pub fn has_ident(&self, ident: &RustIdent) -> bool {
         let idents: Vec<RustIdent> = self.type_aliases.keys().fold(vec![], |mut acc, alias| {
@@ -293,9 +390,12 @@ impl<'a> IntermediateTypes<'a> {
                         }
                     })
                 }
-                RustStructType::Record(record) => record.fields.iter().for_each(|field| {
-                    mark_refs(&mut refs, self, wasm, current_scope, &field.rust_type)
-                }),
+                RustStructType::Record(record) => record
+                    .fields
+                    .iter()
+                    .map(|field| &field.rust_type)
+                    .chain(record.extra.iter())
+                    .for_each(|ty| mark_refs(&mut refs, self, wasm, current_scope, ty)),
                 RustStructType::Table { domain, range } => {
                     mark_refs(&mut refs, self, wasm, current_scope, domain);
                     mark_refs(&mut refs, self, wasm, current_scope, range);
@@ -345,6 +445,10 @@ impl<'a> IntermediateTypes<'a> {
         let null_type: RustType = ConceptualRustType::Fixed(FixedValue::Null).into();
         insert_alias("null", null_type.clone());
         insert_alias("nil", null_type);
+        insert_alias(
+            "undefined",
+            ConceptualRustType::Fixed(FixedValue::Undefined).into(),
+        );
         insert_alias(
             "true",
             ConceptualRustType::Fixed(FixedValue::Bool(true)).into(),
@@ -353,10 +457,9 @@ impl<'a> IntermediateTypes<'a> {
             "false",
             ConceptualRustType::Fixed(FixedValue::Bool(false)).into(),
         );
-        // Note: defaulting to float64 for "float" (so without precision).
         insert_alias(
             "float",
-            ConceptualRustType::Primitive(Primitive::F64).into(),
+            ConceptualRustType::Primitive(Primitive::Float).into(),
         );
         insert_alias(
             "float64",
@@ -366,7 +469,50 @@ impl<'a> IntermediateTypes<'a> {
             "float32",
             ConceptualRustType::Primitive(Primitive::F32).into(),
         );
-        // What about bingint/other stuff in the standard prelude?
+        insert_alias(
+            "float16",
+            ConceptualRustType::Primitive(Primitive::F16).into(),
+        );
+        insert_alias(
+            "any",
+            ConceptualRustType::Rust(RustIdent::new(CDDLIdent::new(CBOR_VALUE_IDENT))).into(),
+        );
+        let big_uint_type: RustType =
+            ConceptualRustType::Rust(RustIdent::new(CDDLIdent::new(BIG_UINT_IDENT))).into();
+        insert_alias("unsigned", big_uint_type.clone());
+        let big_int_type: RustType =
+            ConceptualRustType::Rust(RustIdent::new(CDDLIdent::new(BIG_INT_IDENT))).into();
+        insert_alias("integer", big_int_type.clone());
+        insert_alias(
+            "decfrac",
+            ConceptualRustType::Rust(RustIdent::new(CDDLIdent::new(DECIMAL_FRACTION_IDENT))).into(),
+        );
+        insert_alias(
+            "bigfloat",
+            ConceptualRustType::Rust(RustIdent::new(CDDLIdent::new(BIG_FLOAT_IDENT))).into(),
+        );
+        // biguint = #6.2(bstr), bignint = #6.3(bstr) and bigint = biguint / bignint can't be plain
+        // uint/nints unlike unsigned/integer so they are kept as aliases (not substituted) in order
+        // to (de)serialize them using the static files' tagged-only functions for them.
+        for (name, ty, funcs) in [
+            ("biguint", big_uint_type, "BigUInt::{}_biguint"),
+            ("bignint", big_int_type.clone(), "BigInt::{}_bignint"),
+            ("bigint", big_int_type, "BigInt::{}_bigint"),
+        ] {
+            aliases.insert(
+                AliasIdent::new(CDDLIdent::new(name)),
+                AliasInfo {
+                    base_type: ty,
+                    gen_rust_alias: true,
+                    gen_wasm_alias: false,
+                    rule_metadata: Some(RuleMetadata {
+                        custom_serialize: Some(funcs.replace("{}", "serialize")),
+                        custom_deserialize: Some(funcs.replace("{}", "deserialize")),
+                        ..Default::default()
+                    }),
+                },
+            );
+        }
         aliases
     }
 
@@ -374,6 +520,10 @@ impl<'a> IntermediateTypes<'a> {
     // are in the CDDL prelude so we don't generate code for all of them, potentially
     // bloating generated code a bit
     pub fn new_type(&mut self, raw: &CDDLIdent, cli: &Cli) -> RustType {
+        // type sockets that were never plugged are open extension points so anything can go there
+        if raw.to_string().starts_with('$') && !self.has_rule(&RustIdent::new(raw.clone())) {
+            return self.new_type(&CDDLIdent::new("any"), cli);
+        }
         let alias_ident = AliasIdent::new(raw.clone());
         let resolved = match self.apply_type_aliases(&alias_ident, cli) {
             Some((ty, true)) => ty,
@@ -394,16 +544,9 @@ impl<'a> IntermediateTypes<'a> {
                 }
             }
         }
-        // this would interfere with map/array loop code generation unless we
-        // specifically handle this case since you wouldn't know whether you hit a break
-        // or are reading a key here, unless we check, but then you'd need to store the
-        // non-break special value once read
-        if let ConceptualRustType::Array(ty) = resolved_inner {
-            assert!(!ty.cbor_types(self).contains(&CBORType::Special));
-        }
-        if let ConceptualRustType::Map(key_type, _val_type) = resolved_inner {
-            assert!(!key_type.cbor_types(self).contains(&CBORType::Special));
-        }
+        // Note: arrays of (or maps keyed by) types that can be special values (e.g. any)
+        // are fine here as the map/array loop code generation peeks at the raw byte in
+        // that case to tell apart a break from a special value
         resolved
     }
 
@@ -416,13 +559,26 @@ impl<'a> IntermediateTypes<'a> {
     ) -> Option<(RustType, bool)> {
         // Assumes we are not trying to pass in any kind of compound type (arrays, etc)
         match self.type_aliases.get(alias_ident) {
-            Some(alias) => Some((alias.base_type.clone(), !alias.gen_rust_alias)),
+            Some(alias) => {
+                let resolved = (alias.base_type.clone(), !alias.gen_rust_alias);
+                if let ConceptualRustType::Rust(ident) = &resolved.0.conceptual_type {
+                    self.register_static_externs(ident);
+                }
+                Some(resolved)
+            }
             None => match alias_ident {
                 AliasIdent::Rust(_rust_ident) => None,
                 AliasIdent::Reserved(reserved) => {
                     if reserved == "int" {
                         // We define an Int rust struct in prelude.rs
                         None
+                    } else if cli.typed_date_time && (reserved == "tdate" || reserved == "time") {
+                        let ident = if reserved == "tdate" {
+                            DATE_TIME_IDENT
+                        } else {
+                            EPOCH_TIME_IDENT
+                        };
+                        Some((self.static_extern_type(ident), true))
                     } else {
                         // we auto-include only the parts of the cddl prelude necessary (and supported)
                         cddl_prelude(reserved).unwrap_or_else(|| {
@@ -446,7 +602,9 @@ impl<'a> IntermediateTypes<'a> {
     }
 
     pub fn register_type_alias(&mut self, alias: RustIdent, info: AliasInfo) {
-        if let ConceptualRustType::Alias(_ident, _ty) = &info.base_type.conceptual_type {
+        // reserved ones (e.g. biguint) are fine as they aren't generated (see aliases())
+        if let ConceptualRustType::Alias(AliasIdent::Rust(_), _ty) = &info.base_type.conceptual_type
+        {
             panic!("register_type_alias*({}, {:?}) wraps automatically in Alias, no need to provide it.", alias, info.base_type);
         }
         self.type_aliases.insert(alias.into(), info);
@@ -507,9 +665,19 @@ impl<'a> IntermediateTypes<'a> {
             }
             RustStructType::Wrapper {
                 min_max: Some(_), ..
+            }
+            | RustStructType::Wrapper {
+                regexp: Some(_), ..
+            }
+            | RustStructType::Wrapper { bits: Some(_), .. }
+            | RustStructType::Wrapper {
+                excluded: Some(_), ..
             } => {
                 self.mark_new_can_fail(rust_struct.ident.clone());
             }
+            RustStructType::Wrapper { wrapped, .. } if wrapped.config.float_bounds.is_some() => {
+                self.mark_new_can_fail(rust_struct.ident.clone());
+            }
             _ => (),
         }
         self.rust_structs
@@ -555,11 +723,38 @@ impl<'a> IntermediateTypes<'a> {
     }
 
     pub fn register_generic_def(&mut self, def: GenericDef) {
-        let ident = def.orig.ident().clone();
+        let ident = def.ident().clone();
         self.generic_defs.insert(ident, def);
     }
 
+    // must be called before parsing the generic definition
+    pub fn mark_generic_params(&mut self, params: &[RustIdent]) {
+        self.generic_params.extend(params.iter().cloned());
+    }
+
+    pub fn is_generic_param(&self, ident: &RustIdent) -> bool {
+        self.generic_params.contains(ident) && !self.has_rule(ident)
+    }
+
     pub fn register_generic_instance(&mut self, instance: GenericInstance) {
+        if !instance.is_unbound(self) {
+            if let Some((mut base_type, rule_metadata, nested_instances)) =
+                instance.resolve_alias(self)
+            {
+                // e.g. foo<T> = T instanced with an alias itself
+                if let ConceptualRustType::Alias(_ident, ty) = base_type.conceptual_type {
+                    base_type.conceptual_type = *ty;
+                }
+                self.register_type_alias(
+                    instance.instance_ident,
+                    AliasInfo::new_from_metadata(base_type, rule_metadata),
+                );
+                for nested_instance in nested_instances {
+                    self.register_generic_instance(nested_instance);
+                }
+                return;
+            }
+        }
         let ident = instance.instance_ident.clone();
         self.generic_instances.insert(ident, instance);
     }
@@ -567,15 +762,27 @@ impl<'a> IntermediateTypes<'a> {
     // call this after all types have been registered
     pub fn finalize(&mut self, parent_visitor: &ParentVisitor, cli: &Cli) {
         // resolve generics
-        // resolve then register in 2 phases to get around borrow checker
-        let resolved_generics = self
-            .generic_instances
-            .values()
-            .map(|instance| instance.resolve(self, cli))
-            .collect::<Vec<_>>();
-        for resolved_instance in resolved_generics {
-            match resolved_instance {
-                GenericResolved::Resolved(rs) => self.register_rust_struct(parent_visitor, rs, cli),
+        // resolving can require other instances to be resolved too so we use a worklist
+        let mut unresolved_generics = self.generic_instances.values().cloned().collect::<Vec<_>>();
+        while let Some(instance) = unresolved_generics.pop() {
+            // unbound ones are resolved as part of the definitions they're used in and
+            // nested instances can be shared between multiple instances so we only do them once
+            if instance.is_unbound(self) || self.rust_structs.contains_key(&instance.instance_ident)
+            {
+                continue;
+            }
+            match instance.resolve(self, cli) {
+                GenericResolved::Resolved(rs, nested_instances) => {
+                    // instances of plain groups (e.g. group choice variants) must be embedded too
+                    if self.is_plain_group(&instance.generic_ident) {
+                        self.mark_plain_group(
+                            instance.instance_ident.clone(),
+                            PlainGroupInfo::new(None, RuleMetadata::default()),
+                        );
+                    }
+                    self.register_rust_struct(parent_visitor, rs, cli);
+                    unresolved_generics.extend(nested_instances);
+                }
                 GenericResolved::Extern {
                     instance_ident,
                     real_ident,
@@ -646,13 +853,24 @@ impl<'a> IntermediateTypes<'a> {
 
     pub fn is_referenced(&self, ident: &RustIdent) -> bool {
         let mut found = false;
-        self.visit_types(&mut |ty| {
+        let mut check = |ty: &ConceptualRustType| {
             if let ConceptualRustType::Rust(id) = ty {
                 if id == ident {
                     found = true
                 }
             }
-        });
+        };
+        self.visit_types(&mut check);
+        // user-defined aliases e.g. foo = any are not part of any struct
+        // but still need the type they refer to generated
+        for (alias_ident, alias_info) in self.type_aliases.iter() {
+            if let AliasIdent::Rust(_) = alias_ident {
+                alias_info
+                    .base_type
+                    .conceptual_type
+                    .visit_types(self, &mut check);
+            }
+        }
         found
     }
 
@@ -662,13 +880,15 @@ impl<'a> IntermediateTypes<'a> {
     }
 
     // see self.plain_groups comments
+    // Returns the ident of the plain group for this representation as a plain group used in both
+    // arrays and maps is generated once per representation e.g. Foo and FooMap if first used in an array
     pub fn set_rep_if_plain_group(
         &mut self,
         parent_visitor: &ParentVisitor,
         ident: &RustIdent,
         rep: Representation,
         cli: &Cli,
-    ) {
+    ) -> RustIdent {
         if let Some(plain_group) = self.plain_groups.get(ident) {
             // the clone is to get around the borrow checker
             let plain_group = plain_group.clone();
@@ -676,13 +896,40 @@ impl<'a> IntermediateTypes<'a> {
                 // we are defined via .cddl and thus need to register a concrete
                 // representation of the plain group
                 if let Some(rust_struct) = self.rust_structs.get(ident) {
-                    // it's already defined, let's check that we're not giving it multiple representations
+                    // it's already defined, let's check which representation it was given
                     let found_rep = match &rust_struct.variant {
                         RustStructType::Record(record) => Some(record.rep),
                         RustStructType::GroupChoice { rep, .. } => Some(*rep),
                         _ => None,
                     };
-                    assert_eq!(found_rep, Some(rep));
+                    assert!(
+                        found_rep.is_some(),
+                        "{ident}: plain group has no representation"
+                    );
+                    if found_rep != Some(rep) {
+                        let rep_ident = RustIdent::new(CDDLIdent::new(format!(
+                            "{ident}{}",
+                            match rep {
+                                Representation::Array => "Array",
+                                Representation::Map => "Map",
+                            }
+                        )));
+                        if !self.is_plain_group(&rep_ident) {
+                            if self.has_rule(&rep_ident)
+                                || self.rust_structs.contains_key(&rep_ident)
+                                || self.inline_idents.contains_key(&rep_ident)
+                            {
+                                panic!(
+                                    "{}: plain group {} is used in both arrays and maps but the name for its {:?} representation is already taken. Rename either of them",
+                                    rep_ident, ident, rep
+                                );
+                            }
+                            let scope = self.scope(ident).clone();
+                            self.mark_scope(rep_ident.clone(), scope);
+                            self.mark_plain_group(rep_ident.clone(), plain_group.clone());
+                        }
+                        return self.set_rep_if_plain_group(parent_visitor, &rep_ident, rep, cli);
+                    }
                 } else {
                     // you can't tag plain groups hence the None
                     // we also don't support generics in plain groups hence the other None
@@ -706,12 +953,69 @@ impl<'a> IntermediateTypes<'a> {
                 assert!(self.rust_structs.contains_key(ident));
             }
         }
+        ident.clone()
+    }
+
+    pub fn mark_generic_rule(&mut self, ident: RustIdent, rule: cddl::ast::Rule<'a>) {
+        self.unparsed_generic_rules.insert(ident, rule);
+    }
+
+    // None if it was already parsed (or isn't a generic definition)
+    pub fn take_unparsed_generic_rule(&mut self, ident: &RustIdent) -> Option<cddl::ast::Rule<'a>> {
+        self.unparsed_generic_rules.remove(ident)
+    }
+
+    /// Reserves the name of an inline type defined by the cddl in {definition}.
+    /// The same definition can be parsed more than once (e.g. plain groups used in both arrays and maps)
+    /// but a name taken by a rule or by a different inline type is an error as it would replace that type.
+    pub fn register_inline_ident(&mut self, ident: &RustIdent, definition: String) {
+        if self.has_rule(ident) {
+            panic!(
+                "{}: the inline type {} has the same name as a rule. Give it another name using @name",
+                ident, definition
+            );
+        }
+        if let Some(existing) = self.inline_idents.get(ident) {
+            if *existing != definition {
+                panic!(
+                    "{}: the inline types {} and {} have the same name. Give one of them another name using @name",
+                    ident, definition, existing
+                );
+            }
+        }
+        self.inline_idents.insert(ident.clone(), definition);
+    }
+
+    pub fn mark_unwrappable_group(&mut self, ident: RustIdent, group_info: PlainGroupInfo<'a>) {
+        self.unwrappable_groups.insert(ident, group_info);
+    }
+
+    // ~foo embeds foo's fields into the outer group so we create a plain group with the same contents
+    // as foo which will get a representation via set_rep_if_plain_group() at the point it is unwrapped
+    pub fn unwrapped_plain_group(&mut self, ident: &RustIdent) -> RustIdent {
+        let group_info = self.unwrappable_groups.get(ident).unwrap_or_else(|| {
+            panic!(
+                "{ident}: only non-generic untagged array/map definitions can be unwrapped via ~"
+            )
+        });
+        let plain_group_ident = RustIdent::new(CDDLIdent::new(format!("{ident}Fields")));
+        if !self.is_plain_group(&plain_group_ident) {
+            let group_info = group_info.clone();
+            let scope = self.scope(ident).clone();
+            self.mark_scope(plain_group_ident.clone(), scope);
+            self.mark_plain_group(plain_group_ident.clone(), group_info);
+        }
+        plain_group_ident
     }
 
     pub fn is_plain_group(&self, name: &RustIdent) -> bool {
         self.plain_groups.contains_key(name)
     }
 
+    pub fn plain_group(&self, name: &RustIdent) -> Option<&PlainGroupInfo<'a>> {
+        self.plain_groups.get(name)
+    }
+
     fn mark_new_can_fail(&mut self, name: RustIdent) {
         self.news_can_fail.insert(name);
     }
@@ -731,6 +1035,11 @@ impl<'a> IntermediateTypes<'a> {
         }
     }
 
+    /// if there was a rule defined for this in the input .cddl (see mark_scope())
+    pub fn has_rule(&self, ident: &RustIdent) -> bool {
+        self.scopes.contains_key(ident)
+    }
+
     pub fn scope(&self, ident: &RustIdent) -> &ModuleScope {
         self.scopes.get(ident).unwrap_or(&self.root_scope)
     }
@@ -740,7 +1049,12 @@ impl<'a> IntermediateTypes<'a> {
     pub fn remove_rust_struct(&mut self, ident: &RustIdent) -> Option<RustStruct> {
         self.plain_groups.remove(ident);
         self.scopes.remove(ident);
-        self.rust_structs.remove(ident)
+        self.rust_structs
+            .remove(ident)
+            .or_else(|| match self.generic_defs.remove(ident)?.orig {
+                GenericDefType::Struct(orig) => Some(orig),
+                GenericDefType::Alias { .. } | GenericDefType::Instance(_) => None,
+            })
     }
 
     pub fn used_as_key(&self, name: &RustIdent) -> bool {
@@ -817,12 +1131,15 @@ pub enum Representation {
 #[derive(Clone, Debug, PartialEq)]
 pub enum FixedValue {
     Null,
+    Undefined,
     Bool(bool),
+    // simple values (#7.n) other than false / true / null / undefined e.g. #7.16
+    Simple(u8),
     Nint(isize),
     Uint(usize),
     Float(f64),
     Text(String),
-    // UTF byte types not supported
+    Bytes(Vec<u8>),
 }
 
 fn convert_to_alphanumeric(input: &str) -> String {
@@ -836,16 +1153,19 @@ impl FixedValue {
     fn for_variant(&self) -> VariantIdent {
         match self {
             FixedValue::Null => VariantIdent::new_custom("Null"),
+            FixedValue::Undefined => VariantIdent::new_custom("Undefined"),
             FixedValue::Bool(b) => VariantIdent::new_custom(match b {
                 true => "True",
                 false => "False",
             }),
+            FixedValue::Simple(n) => VariantIdent::new_custom(format!("Simple{n}")),
             FixedValue::Nint(i) => VariantIdent::new_custom(format!("U{i}")),
             FixedValue::Uint(u) => VariantIdent::new_custom(format!("I{u}")),
             FixedValue::Float(f) => VariantIdent::new_custom(format!("F{f}")),
             FixedValue::Text(s) => {
                 VariantIdent::new_custom(convert_to_alphanumeric(&convert_to_camel_case(s)))
             }
+            FixedValue::Bytes(bytes) => VariantIdent::new_custom(format!("B{}", to_hex(bytes))),
         }
     }
 
@@ -853,11 +1173,14 @@ impl FixedValue {
         let mut buf = cbor_event::se::Serializer::new_vec();
         match self {
             FixedValue::Null => buf.write_special(cbor_event::Special::Null),
+            FixedValue::Undefined => buf.write_special(cbor_event::Special::Undefined),
             FixedValue::Bool(b) => buf.write_special(cbor_event::Special::Bool(*b)),
+            FixedValue::Simple(n) => buf.write_special(cbor_event::Special::Unassigned(*n)),
             FixedValue::Nint(i) => buf.write_negative_integer(*i as i64),
             FixedValue::Uint(u) => buf.write_unsigned_integer(*u as u64),
             FixedValue::Float(f) => buf.write_special(Special::Float(*f)),
             FixedValue::Text(s) => buf.write_text(s),
+            FixedValue::Bytes(bytes) => buf.write_bytes(bytes),
         }
         .expect("Unable to serialize key for canonical ordering");
         buf.finalize()
@@ -867,12 +1190,14 @@ impl FixedValue {
     /// e.g. Text is an actual String, etc
     pub fn to_primitive_str_assign(&self) -> String {
         match self {
-            FixedValue::Null => "None".to_owned(),
+            FixedValue::Null | FixedValue::Undefined => "None".to_owned(),
             FixedValue::Bool(b) => b.to_string(),
+            FixedValue::Simple(n) => n.to_string(),
             FixedValue::Nint(i) => i.to_string(),
             FixedValue::Uint(u) => u.to_string(),
             FixedValue::Float(f) => f.to_string(),
             FixedValue::Text(s) => format!("\"{s}\".to_owned()"),
+            FixedValue::Bytes(bytes) => format!("vec!{bytes:?}"),
         }
     }
 
@@ -881,16 +1206,95 @@ impl FixedValue {
     pub fn to_primitive_str_compare(&self) -> String {
         match self {
             FixedValue::Text(s) => format!("\"{s}\""),
+            FixedValue::Bytes(bytes) => format!("{bytes:?}"),
+            _ => self.to_primitive_str_assign(),
+        }
+    }
+
+    /// How the value is written in CDDL e.g. h'00ff' for bytes. Used for error messages
+    pub fn to_cddl_str(&self) -> String {
+        match self {
+            FixedValue::Text(s) => format!("\"{s}\""),
+            FixedValue::Bytes(bytes) => format!("h'{}'", to_hex(bytes)),
+            FixedValue::Null => "null".to_owned(),
+            FixedValue::Undefined => "undefined".to_owned(),
+            FixedValue::Simple(n) => format!("#7.{n}"),
             _ => self.to_primitive_str_assign(),
         }
     }
 }
 
+/// Literal key of a map field e.g. the 1 in { 1: uint } or the #6.42(h'00') in { #6.42(h'00') => uint }
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapKey {
+    /// Tag the key literal is wrapped in, if any
+    pub tag: Option<usize>,
+    pub value: FixedValue,
+    /// Cut entries (written with : or ^ =>) don't let this key match a catch-all entry
+    /// e.g. in { ? "foo" => uint, * tstr => any } a "foo" with a text value goes in the
+    /// catch-all table but with { ? "foo" ^ => uint, * tstr => any } it's an error
+    pub cut: bool,
+}
+
+impl MapKey {
+    pub fn new(value: FixedValue) -> Self {
+        Self {
+            tag: None,
+            value,
+            cut: false,
+        }
+    }
+
+    pub fn cut(mut self, is_cut: bool) -> Self {
+        self.cut = is_cut;
+        self
+    }
+
+    pub fn tag(mut self, tag: usize) -> Self {
+        assert!(self.tag.is_none(), "doubly nested tags are not supported");
+        self.tag = Some(tag);
+        self
+    }
+
+    /// The type the key is (de)serialized as e.g. for encoding fields
+    pub fn to_rust_type(&self) -> RustType {
+        RustType::new(ConceptualRustType::Fixed(self.value.clone())).tag_if(self.tag)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = match self.tag {
+            Some(tag) => {
+                let mut buf = cbor_event::se::Serializer::new_vec();
+                buf.write_tag(tag as u64)
+                    .expect("Unable to serialize key for canonical ordering");
+                buf.finalize()
+            }
+            None => Vec::new(),
+        };
+        bytes.extend(self.value.to_bytes());
+        bytes
+    }
+
+    /// How the key is written in CDDL e.g. #6.42(h'00ff'). Used for error messages
+    pub fn to_cddl_str(&self) -> String {
+        match self.tag {
+            Some(tag) => format!("#6.{tag}({})", self.value.to_cddl_str()),
+            None => self.value.to_cddl_str(),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Primitive {
     Bool,
+    // float - any width, stored as f64
+    Float,
+    // float64 - always encoded as a double-precision float
     F64,
+    // float32 - always encoded as a single-precision float
     F32,
+    // float16 - stored as f32 but always encoded as a half-precision float
+    F16,
     // u8 in our cddl
     U8,
     // i8 in our cddl
@@ -921,7 +1325,8 @@ impl std::fmt::Display for Primitive {
             match self {
                 Primitive::Bool => "bool",
                 Primitive::F32 => "f32",
-                Primitive::F64 => "f64",
+                Primitive::F16 => "f32",
+                Primitive::Float | Primitive::F64 => "f64",
                 Primitive::U8 => "u8",
                 Primitive::I8 => "i8",
                 Primitive::U16 => "u16",
@@ -939,11 +1344,19 @@ impl std::fmt::Display for Primitive {
 }
 // TODO: impl display or fmt or whatever rust uses
 impl Primitive {
+    pub fn is_float(self) -> bool {
+        matches!(
+            self,
+            Primitive::Float | Primitive::F64 | Primitive::F32 | Primitive::F16
+        )
+    }
+
     pub fn to_variant(self) -> VariantIdent {
         VariantIdent::new_custom(match self {
             Primitive::Bool => "Bool",
             Primitive::F32 => "F32",
-            Primitive::F64 => "F64",
+            Primitive::F16 => "F16",
+            Primitive::Float | Primitive::F64 => "F64",
             Primitive::U8 => "U8",
             Primitive::I8 => "I8",
             Primitive::U16 => "U16",
@@ -963,7 +1376,8 @@ impl Primitive {
         match self {
             Primitive::Bool => vec![CBORType::Special],
             Primitive::F32 => vec![CBORType::Special],
-            Primitive::F64 => vec![CBORType::Special],
+            Primitive::F16 => vec![CBORType::Special],
+            Primitive::Float | Primitive::F64 => vec![CBORType::Special],
             Primitive::U8 => vec![CBORType::UnsignedInteger],
             Primitive::I8 => vec![CBORType::UnsignedInteger, CBORType::NegativeInteger],
             Primitive::U16 => vec![CBORType::UnsignedInteger],
@@ -1142,6 +1556,102 @@ pub enum CBOREncodingOperation {
     Tagged(usize),
     /// bytes .cbor T in cddl, outside of serialization is semantically like T
     CBORBytes,
+    /// bytes .cborseq [* T] in cddl, outside of serialization is semantically like [* T]
+    /// but the elements are encoded one after another without an array header
+    CBORSeqBytes,
+}
+
+/// Bounds of a float e.g. from `float64 .lt 1.5` or `0.5..2.0` as (value, inclusive).
+/// NaN is never within any bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatBounds {
+    pub min: Option<(f64, bool)>,
+    pub max: Option<(f64, bool)>,
+}
+
+impl FloatBounds {
+    pub fn new(min: Option<(f64, bool)>, max: Option<(f64, bool)>) -> Self {
+        for (bound, _inclusive) in min.iter().chain(max.iter()) {
+            assert!(!bound.is_nan(), "NaN can't be used as a float bound");
+        }
+        Self { min, max }
+    }
+
+    /// The tighter of both bounds on each side
+    pub fn intersect(self, other: Self) -> Self {
+        let tighter =
+            |lhs: Option<(f64, bool)>, rhs: Option<(f64, bool)>, is_min: bool| match (lhs, rhs) {
+                (Some(l), Some(r)) => Some(if l.0 == r.0 {
+                    (l.0, l.1 && r.1)
+                } else if (l.0 > r.0) == is_min {
+                    l
+                } else {
+                    r
+                }),
+                (l, r) => l.or(r),
+            };
+        Self {
+            min: tighter(self.min, other.min, true),
+            max: tighter(self.max, other.max, false),
+        }
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        !x.is_nan()
+            && self
+                .min
+                .map(|(min, inclusive)| if inclusive { x >= min } else { x > min })
+                .unwrap_or(true)
+            && self
+                .max
+                .map(|(max, inclusive)| if inclusive { x <= max } else { x < max })
+                .unwrap_or(true)
+    }
+
+    /// Rust expression that is true when the (f64) expression e is NOT within these bounds
+    pub fn out_of_bounds_expr(&self, e: &str) -> String {
+        let mut conditions = vec![format!("{e}.is_nan()")];
+        if let Some((min, inclusive)) = self.min {
+            conditions.push(format!(
+                "{e} {} {}",
+                if inclusive { "<" } else { "<=" },
+                float_literal(min)
+            ));
+        }
+        if let Some((max, inclusive)) = self.max {
+            conditions.push(format!(
+                "{e} {} {}",
+                if inclusive { ">" } else { ">=" },
+                float_literal(max)
+            ));
+        }
+        conditions.join(" || ")
+    }
+}
+
+/// Interval notation e.g. [0.5, 1.5) for error messages
+impl std::fmt::Display for FloatBounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.min {
+            Some((min, inclusive)) => write!(f, "{}{min}", if inclusive { '[' } else { '(' }),
+            None => write!(f, "(-inf"),
+        }?;
+        match self.max {
+            Some((max, inclusive)) => write!(f, ", {max}{}", if inclusive { ']' } else { ')' }),
+            None => write!(f, ", inf)"),
+        }
+    }
+}
+
+// f64 literal that is valid rust code even for infinities
+fn float_literal(x: f64) -> String {
+    if x == f64::INFINITY {
+        "f64::INFINITY".to_owned()
+    } else if x == f64::NEG_INFINITY {
+        "f64::NEG_INFINITY".to_owned()
+    } else {
+        format!("{x:?}f64")
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -1150,8 +1660,13 @@ pub struct RustTypeSerializeConfig {
     pub default: Option<FixedValue>,
     /// Bounds to check. Relevant to primitives + arrays + maps
     pub bounds: Option<(Option<i128>, Option<i128>)>,
+    /// Bounds to check for floats, which unlike the above can be fractional or exclusive
+    pub float_bounds: Option<FloatBounds>,
     /// Basic group encoding override. If true basic encoding will not be used in (de)serialization
     pub basic_override: bool,
+    /// Array whose elements are embedded directly into the parent array instead of being their own
+    /// nested array e.g. the * text in [uint, * text, bytes]
+    pub embedded_seq: bool,
 }
 
 /// A complete rust type, including serialization options that don't impact other areas
@@ -1211,9 +1726,10 @@ impl RustType {
                 FixedValue::Bool(_) => *p == Primitive::Bool,
                 FixedValue::Nint(_) => p.cbor_types().contains(&CBORType::NegativeInteger),
                 FixedValue::Uint(_) => p.cbor_types().contains(&CBORType::UnsignedInteger),
-                FixedValue::Float(_) => *p == Primitive::F64 || *p == Primitive::F32,
-                FixedValue::Null => false,
+                FixedValue::Float(_) => p.is_float(),
+                FixedValue::Null | FixedValue::Undefined | FixedValue::Simple(_) => false,
                 FixedValue::Text(_) => *p == Primitive::Str,
+                FixedValue::Bytes(_) => *p == Primitive::Bytes,
             }
         } else {
             false
@@ -1234,6 +1750,40 @@ impl RustType {
         self
     }
 
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_cbor_seq(mut self) -> Self {
+        // serialization needs to work directly on the elements as there is no array header
+        let resolved = self.conceptual_type.resolve_alias_shallow().clone();
+        if !self.encodings.is_empty() || !matches!(resolved, ConceptualRustType::Array(_)) {
+            panic!(
+                ".cborseq must be on an untagged array type e.g. bytes .cborseq [* foo]. Found: {:?}",
+                self
+            );
+        }
+        self.conceptual_type = resolved;
+        self.encodings.push(CBOREncodingOperation::CBORSeqBytes);
+        self
+    }
+
+    /// For variable-length entries in the middle/end of array groups e.g. [uint, * text, bytes]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_embedded_seq(mut self) -> Self {
+        if !self.encodings.is_empty()
+            || !matches!(self.conceptual_type, ConceptualRustType::Array(_))
+        {
+            panic!(
+                "embedded sequences must be an untagged array type. Found: {:?}",
+                self
+            );
+        }
+        self.config.embedded_seq = true;
+        self
+    }
+
+    pub fn is_embedded_seq(&self) -> bool {
+        self.config.embedded_seq
+    }
+
     // deep resolve aliases
     pub fn resolve_aliases(self) -> Self {
         Self {
@@ -1269,11 +1819,24 @@ impl RustType {
                 } else {
                     None
                 },
+                float_bounds: self.config.float_bounds,
                 basic_override: self.config.basic_override,
+                embedded_seq: self.config.embedded_seq,
             },
         }
     }
 
+    pub fn with_float_bounds(mut self, float_bounds: FloatBounds) -> Self {
+        assert!(self.config.float_bounds.is_none());
+        self.config.float_bounds = Some(float_bounds);
+        self
+    }
+
+    /// If there are any bounds (integer/length or float) to check
+    pub fn has_bounds(&self) -> bool {
+        self.config.bounds.is_some() || self.config.float_bounds.is_some()
+    }
+
     pub fn not_basic(self) -> Self {
         Self {
             conceptual_type: self.conceptual_type,
@@ -1281,7 +1844,9 @@ impl RustType {
             config: RustTypeSerializeConfig {
                 default: self.config.default,
                 bounds: self.config.bounds,
+                float_bounds: self.config.float_bounds,
                 basic_override: true,
+                embedded_seq: self.config.embedded_seq,
             },
         }
     }
@@ -1298,6 +1863,9 @@ impl RustType {
 
     // CBOR len count for the entire type if it were embedded as a member in a cbor collection (array/map)
     pub fn expanded_field_count(&self, types: &IntermediateTypes) -> Option<usize> {
+        if self.is_embedded_seq() {
+            return None;
+        }
         match self.conceptual_type.resolve_alias_shallow() {
             ConceptualRustType::Optional(ty) => match ty.expanded_field_count(types) {
                 Some(1) => Some(1),
@@ -1357,6 +1925,9 @@ impl RustType {
                         String::from("1")
                     }
                 }
+                ConceptualRustType::Array(_) if self.is_embedded_seq() => {
+                    format!("{self_expr}.len() as u64")
+                }
                 _ => String::from("1"),
             },
         }
@@ -1366,6 +1937,10 @@ impl RustType {
     // does not count ANY type choice like types including Optional UNLESS the option Some type
     // has cbor len 1 too - to be consistent with expanded_field_count
     pub fn expanded_mandatory_field_count(&self, types: &IntermediateTypes) -> usize {
+        if self.is_embedded_seq() {
+            // each element is accounted for as it is read
+            return 0;
+        }
         match self.conceptual_type.resolve_alias_shallow() {
             ConceptualRustType::Optional(ty) => match ty.expanded_field_count(types) {
                 Some(1) => 1,
@@ -1391,17 +1966,27 @@ impl RustType {
 
     /// All POSSIBLE outermost CBOR types this can encode to
     pub fn cbor_types(&self, types: &IntermediateTypes) -> Vec<CBORType> {
+        if let ConceptualRustType::Array(elem) = &self.conceptual_type {
+            if self.is_embedded_seq() {
+                return elem.cbor_types(types);
+            }
+        }
         match self.encodings.last() {
             Some(CBOREncodingOperation::Tagged(_)) => vec![CBORType::Tag],
-            Some(CBOREncodingOperation::CBORBytes) => vec![CBORType::Bytes],
+            Some(CBOREncodingOperation::CBORBytes | CBOREncodingOperation::CBORSeqBytes) => {
+                vec![CBORType::Bytes]
+            }
             None => match &self.conceptual_type {
                 ConceptualRustType::Fixed(f) => vec![match f {
                     FixedValue::Uint(_) => CBORType::UnsignedInteger,
                     FixedValue::Nint(_) => CBORType::NegativeInteger,
                     FixedValue::Float(_) => CBORType::Special,
                     FixedValue::Text(_) => CBORType::Text,
-                    FixedValue::Null => CBORType::Special,
-                    FixedValue::Bool(_) => CBORType::Special,
+                    FixedValue::Bytes(_) => CBORType::Bytes,
+                    FixedValue::Null
+                    | FixedValue::Undefined
+                    | FixedValue::Bool(_)
+                    | FixedValue::Simple(_) => CBORType::Special,
                 }],
                 ConceptualRustType::Primitive(p) => p.cbor_types(),
                 ConceptualRustType::Rust(ident) => {
@@ -1411,6 +1996,33 @@ impl RustType {
                     } else {
                         match rust_struct.variant() {
                             RustStructType::Wrapper { wrapped, .. } => wrapped.cbor_types(types),
+                            RustStructType::Extern if ident.as_ref() == CBOR_VALUE_IDENT => vec![
+                                CBORType::UnsignedInteger,
+                                CBORType::NegativeInteger,
+                                CBORType::Bytes,
+                                CBORType::Text,
+                                CBORType::Array,
+                                CBORType::Map,
+                                CBORType::Tag,
+                                CBORType::Special,
+                            ],
+                            RustStructType::Extern if ident.as_ref() == BIG_UINT_IDENT => {
+                                vec![CBORType::UnsignedInteger, CBORType::Tag]
+                            }
+                            RustStructType::Extern
+                                if ident.as_ref() == TAGGED_VALUE_IDENT
+                                    || ident.as_ref() == DATE_TIME_IDENT
+                                    || ident.as_ref() == EPOCH_TIME_IDENT
+                                    || ident.as_ref() == DECIMAL_FRACTION_IDENT
+                                    || ident.as_ref() == BIG_FLOAT_IDENT =>
+                            {
+                                vec![CBORType::Tag]
+                            }
+                            RustStructType::Extern if ident.as_ref() == BIG_INT_IDENT => vec![
+                                CBORType::UnsignedInteger,
+                                CBORType::NegativeInteger,
+                                CBORType::Tag,
+                            ],
                             // we can't know this unless there's a way to provide this info
                             RustStructType::Extern => vec![CBORType::Array, CBORType::Map],
                             RustStructType::Record(record) => match record.rep {
@@ -1444,6 +2056,12 @@ impl RustType {
                     }
                     inner_types
                 }
+                // biguint / bignint / bigint are always bignums (see IntermediateTypes::aliases())
+                ConceptualRustType::Alias(AliasIdent::Reserved(reserved), _ty)
+                    if matches!(reserved.as_str(), "biguint" | "bignint" | "bigint") =>
+                {
+                    vec![CBORType::Tag]
+                }
                 ConceptualRustType::Alias(_ident, ty) => {
                     Self::new((**ty).clone()).cbor_types(types)
                 }
@@ -1452,7 +2070,7 @@ impl RustType {
     }
 
     pub fn needs_bounds_check_if_inlined(&self, types: &IntermediateTypes) -> bool {
-        self.config.bounds.is_some()
+        self.has_bounds()
             || match self.resolve_alias_shallow() {
                 ConceptualRustType::Rust(ident) => types.can_new_fail(ident),
                 _ => false,
@@ -1553,8 +2171,10 @@ impl ConceptualRustType {
                     Self::Primitive(p) => match p {
                         // converts to js number which is supported as Vec<T>
                         Primitive::Bool
+                        | Primitive::F16
                         | Primitive::F32
                         | Primitive::F64
+                        | Primitive::Float
                         | Primitive::I8
                         | Primitive::U8
                         | Primitive::I16
@@ -1632,9 +2252,11 @@ impl ConceptualRustType {
                 format!("Option<{}>", ty.conceptual_type._for_rust_read(types, cli))
             }
             Self::Map(_k, _v) => format!("&{}", self.for_rust_member(types, false, cli)),
-            Self::Alias(ident, ty) => match &**ty {
+            Self::Alias(ident, ty) => match (ident, &**ty) {
+                // reserved aliases aren't generated (see for_rust_member())
+                (AliasIdent::Reserved(_), _) => ty._for_rust_read(types, cli),
                 // TODO: ???
-                Self::Rust(_) => format!("&{ident}"),
+                (_, Self::Rust(_)) => format!("&{ident}"),
                 _ => ident.to_string(),
             },
         }
@@ -1678,6 +2300,10 @@ impl ConceptualRustType {
                 )
             }
             Self::Map(_k, _v) => format!("{}{}", opt_ref, self.for_wasm_member(types)),
+            // reserved aliases aren't generated (see for_rust_member())
+            Self::Alias(AliasIdent::Reserved(_), ty) => {
+                ty.for_wasm_param_impl(types, force_not_ref)
+            }
             // it might not be worth generating this as aliases are ignored by wasm-pack build, but
             // that could change in the future so as long as it doens't cause issues we'll leave it
             Self::Alias(ident, ty) => match &**ty {
@@ -1795,9 +2421,9 @@ impl ConceptualRustType {
                 VariantIdent::new_custom(format!("Opt{}", ty.conceptual_type.for_variant()))
             }
             Self::Map(k, v) => VariantIdent::new_custom(Self::name_for_wasm_map(k, v).to_string()),
-            Self::Alias(ident, _ty) => match ident {
+            Self::Alias(ident, ty) => match ident {
                 AliasIdent::Rust(rust_ident) => VariantIdent::new_rust(rust_ident.clone()),
-                AliasIdent::Reserved(reserved) => VariantIdent::new_custom(reserved),
+                AliasIdent::Reserved(_) => ty.for_variant(),
             },
         }
     }
@@ -1958,8 +2584,10 @@ impl ConceptualRustType {
             Self::Fixed(_f) => unreachable!(),
             Self::Primitive(p) => match p {
                 Primitive::Bool
+                | Primitive::F16
                 | Primitive::F32
                 | Primitive::F64
+                | Primitive::Float
                 | Primitive::I8
                 | Primitive::I16
                 | Primitive::I32
@@ -2236,14 +2864,23 @@ impl EnumVariant {
     pub fn can_embed_fields(types: &IntermediateTypes, ty: &ConceptualRustType) -> bool {
         match ty {
             ConceptualRustType::Rust(ident) => {
-                if let RustStructType::Record(record) = types.rust_struct(ident).unwrap().variant()
-                {
-                    return record
-                        .fields
-                        .iter()
-                        .filter(|field| !field.rust_type.is_fixed_value())
-                        .count()
-                        <= 1;
+                // variants of generic group choices are generic definitions themselves
+                let rust_struct = types.rust_struct(ident).unwrap_or_else(|| {
+                    match &types.generic_defs.get(ident).unwrap().orig {
+                        GenericDefType::Struct(orig) => orig,
+                        GenericDefType::Alias { .. } | GenericDefType::Instance(_) => {
+                            unreachable!()
+                        }
+                    }
+                });
+                if let RustStructType::Record(record) = rust_struct.variant() {
+                    return record.extra.is_none()
+                        && record
+                            .fields
+                            .iter()
+                            .filter(|field| !field.rust_type.is_fixed_value())
+                            .count()
+                            <= 1;
                 }
                 false
             }
@@ -2258,8 +2895,8 @@ pub struct RustField {
     pub name: String,
     pub rust_type: RustType,
     pub optional: bool,
-    // None for array fields, Some for map fields. FixedValue for (de)serialization for map keys
-    pub key: Option<FixedValue>,
+    // None for array fields, Some for map fields. MapKey for (de)serialization for map keys
+    pub key: Option<MapKey>,
     // comment DSL metadata applied to this field
     pub rule_metadata: RuleMetadata,
 }
@@ -2269,7 +2906,7 @@ impl RustField {
         name: String,
         rust_type: RustType,
         optional: bool,
-        key: Option<FixedValue>,
+        key: Option<MapKey>,
         rule_metadata: RuleMetadata,
     ) -> Self {
         Self {
@@ -2359,6 +2996,14 @@ pub enum RustStructType {
     Wrapper {
         wrapped: RustType,
         min_max: Option<(Option<i128>, Option<i128>)>,
+        /// regular expression the (text) wrapped value must fully match e.g. from `.regexp`
+        regexp: Option<String>,
+        /// (name, bit position) of all declared bits e.g. from `.bits`
+        bits: Option<Vec<(String, u64)>>,
+        /// values the wrapped value can't be e.g. from `.ne`
+        excluded: Option<Vec<FixedValue>>,
+        /// length of a wrapped array stored as a fixed-size `[T; N]` e.g. `[3*3 uint]` with --fixed-size-arrays
+        fixed_len: Option<usize>,
     },
     /// This is a no-op in generation but to prevent lookups of things in the prelude
     /// e.g. `int` from not being resolved while still being able to detect it when
@@ -2483,6 +3128,105 @@ impl RustStruct {
             variant: RustStructType::Wrapper {
                 wrapped: wrapped_type,
                 min_max,
+                regexp: None,
+                bits: None,
+                excluded: None,
+                fixed_len: None,
+            },
+        }
+    }
+
+    /// array wrapper over a fixed-size `[T; N]` for arrays with exactly `len` elements
+    pub fn new_fixed_size_array_wrapper(
+        ident: RustIdent,
+        tag: Option<usize>,
+        rule_metadata: Option<&RuleMetadata>,
+        element_type: RustType,
+        len: usize,
+    ) -> Self {
+        Self {
+            ident,
+            tag,
+            config: RustStructConfig::from(rule_metadata),
+            variant: RustStructType::Wrapper {
+                wrapped: ConceptualRustType::Array(Box::new(element_type)).into(),
+                min_max: None,
+                regexp: None,
+                bits: None,
+                excluded: None,
+                fixed_len: Some(len),
+            },
+        }
+    }
+
+    /// text wrapper that only accepts strings that fully match `regexp`
+    pub fn new_regexp_wrapper(
+        ident: RustIdent,
+        tag: Option<usize>,
+        rule_metadata: Option<&RuleMetadata>,
+        regexp: String,
+    ) -> Self {
+        Self {
+            ident,
+            tag,
+            config: RustStructConfig::from(rule_metadata),
+            variant: RustStructType::Wrapper {
+                wrapped: ConceptualRustType::Primitive(Primitive::Str).into(),
+                min_max: None,
+                regexp: Some(regexp),
+                bits: None,
+                excluded: None,
+                fixed_len: None,
+            },
+        }
+    }
+
+    /// wrapper checking any combination of constraints at runtime e.g. from `.and` / `.within`
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_constrained_wrapper(
+        ident: RustIdent,
+        tag: Option<usize>,
+        rule_metadata: Option<&RuleMetadata>,
+        wrapped_type: RustType,
+        min_max: Option<(Option<i128>, Option<i128>)>,
+        regexp: Option<String>,
+        bits: Option<Vec<(String, u64)>>,
+        excluded: Option<Vec<FixedValue>>,
+    ) -> Self {
+        Self {
+            ident,
+            tag,
+            config: RustStructConfig::from(rule_metadata),
+            variant: RustStructType::Wrapper {
+                wrapped: wrapped_type,
+                min_max,
+                regexp,
+                bits,
+                excluded,
+                fixed_len: None,
+            },
+        }
+    }
+
+    /// uint or bytes wrapper where only the declared bits (name, bit position) can be set
+    pub fn new_bits_wrapper(
+        ident: RustIdent,
+        tag: Option<usize>,
+        rule_metadata: Option<&RuleMetadata>,
+        wrapped_type: RustType,
+        bits: Vec<(String, u64)>,
+    ) -> Self {
+        Self {
+            ident,
+            tag,
+            config: RustStructConfig::from(rule_metadata),
+            variant: RustStructType::Wrapper {
+                wrapped: wrapped_type,
+                min_max: None,
+                regexp: None,
+                bits: Some(bits),
+                excluded: None,
+                fixed_len: None,
             },
         }
     }
@@ -2655,12 +3399,15 @@ impl RustStruct {
                     }),
                 })
             }
-            RustStructType::Record(record) => record.fields.iter().for_each(|field| {
-                field
-                    .rust_type
-                    .conceptual_type
-                    .visit_types_excluding(types, f, already_visited)
-            }),
+            RustStructType::Record(record) => record
+                .fields
+                .iter()
+                .map(|field| &field.rust_type)
+                .chain(record.extra.iter())
+                .for_each(|ty| {
+                    ty.conceptual_type
+                        .visit_types_excluding(types, f, already_visited)
+                }),
             RustStructType::Table { domain, range } => {
                 domain
                     .conceptual_type
@@ -2683,18 +3430,25 @@ impl RustStruct {
 pub struct RustRecord {
     pub rep: Representation,
     pub fields: Vec<RustField>,
+    /// Catch-all table for all other keys in maps e.g. the * uint => any in { 1: uint, * uint => any }
+    /// This is always a ConceptualRustType::Map if present
+    pub extra: Option<RustType>,
 }
 
 impl RustRecord {
     pub fn fixed_field_count(&self, types: &IntermediateTypes) -> Option<usize> {
+        if self.extra.is_some() {
+            return None;
+        }
         let mut count = 0;
         for field in &self.fields {
             if field.optional {
                 return None;
             }
             count += match self.rep {
-                Representation::Array => field.rust_type.expanded_field_count(types)?,
-                Representation::Map => 1,
+                // embedded plain groups contribute all of their entries
+                Representation::Map if !field.rust_type.is_basic(types) => 1,
+                _ => field.rust_type.expanded_field_count(types)?,
             };
         }
         Some(count)
@@ -2778,31 +3532,44 @@ impl RustRecord {
                         }
                     } else {
                         match self.rep {
-                            Representation::Array => {
-                                match field.rust_type.expanded_field_count(types) {
-                                    Some(field_expanded_count) => {
-                                        fixed_field_count += field_expanded_count
-                                    }
-                                    None => {
-                                        if !conditional_field_expr.is_empty() {
-                                            conditional_field_expr.push_str(" + ");
-                                        }
-                                        let field_len_expr = field.rust_type.definite_info(
-                                            &format!("self.{}", field.name),
-                                            false,
-                                            types,
-                                            cli,
-                                        );
-                                        conditional_field_expr.push_str(&field_len_expr);
-                                    }
-                                }
-                            }
-                            Representation::Map => {
+                            Representation::Map if !field.rust_type.is_basic(types) => {
                                 fixed_field_count += 1;
                             }
+                            _ => match field.rust_type.expanded_field_count(types) {
+                                Some(field_expanded_count) => {
+                                    fixed_field_count += field_expanded_count
+                                }
+                                None => {
+                                    if !conditional_field_expr.is_empty() {
+                                        conditional_field_expr.push_str(" + ");
+                                    }
+                                    let self_field_expr = if self_expr.is_empty() {
+                                        Cow::Borrowed(&field.name)
+                                    } else {
+                                        Cow::Owned(format!("{}.{}", self_expr, field.name))
+                                    };
+                                    let field_len_expr = field.rust_type.definite_info(
+                                        &self_field_expr,
+                                        false,
+                                        types,
+                                        cli,
+                                    );
+                                    conditional_field_expr.push_str(&field_len_expr);
+                                }
+                            },
                         };
                     }
                 }
+                if self.extra.is_some() {
+                    if !conditional_field_expr.is_empty() {
+                        conditional_field_expr.push_str(" + ");
+                    }
+                    if self_expr.is_empty() {
+                        conditional_field_expr.push_str("extra.len()");
+                    } else {
+                        conditional_field_expr.push_str(&format!("{self_expr}.extra.len()"));
+                    }
+                }
                 if conditional_field_expr.is_empty() || fixed_field_count != 0 {
                     format!("{fixed_field_count} + {conditional_field_expr}")
                 } else {
@@ -2830,13 +3597,18 @@ impl RustRecord {
     pub fn canonical_ordering<'a>(&'a self) -> Vec<(usize, &'a RustField)> {
         let mut fields: Vec<(usize, &'a RustField)> = self.fields.iter().enumerate().collect();
         if self.rep == Representation::Map {
-            fields.sort_by(|lhs, rhs| {
-                let lhs_bytes = lhs.1.key.as_ref().unwrap().to_bytes();
-                let rhs_bytes = rhs.1.key.as_ref().unwrap().to_bytes();
-                match lhs_bytes.len().cmp(&rhs_bytes.len()) {
-                    std::cmp::Ordering::Equal => lhs_bytes.cmp(&rhs_bytes),
-                    diff_ord => diff_ord,
+            // embedded plain groups (no key) can have entries anywhere in the canonical order so they're put
+            // at the end here and the serialized entries are instead sorted at runtime (see write_canonical_map_entries())
+            fields.sort_by(|lhs, rhs| match (lhs.1.key.as_ref(), rhs.1.key.as_ref()) {
+                (Some(lhs_key), Some(rhs_key)) => {
+                    let lhs_bytes = lhs_key.to_bytes();
+                    let rhs_bytes = rhs_key.to_bytes();
+                    match lhs_bytes.len().cmp(&rhs_bytes.len()) {
+                        std::cmp::Ordering::Equal => lhs_bytes.cmp(&rhs_bytes),
+                        diff_ord => diff_ord,
+                    }
                 }
+                (lhs_key, rhs_key) => rhs_key.is_some().cmp(&lhs_key.is_some()),
             });
         }
         fields
@@ -2847,20 +3619,65 @@ impl RustRecord {
 #[derive(Debug)]
 pub struct GenericDef {
     generic_params: Vec<RustIdent>,
-    orig: RustStruct,
+    orig: GenericDefType,
+}
+
+#[derive(Debug)]
+enum GenericDefType {
+    // generates a struct/enum per instance e.g. foo<T> = [x: T] or foo<T> = T / text
+    Struct(RustStruct),
+    // has no struct to swap the types out in so each instance is an alias e.g. foo<T> = T / null
+    Alias {
+        ident: RustIdent,
+        base_type: RustType,
+        rule_metadata: RuleMetadata,
+    },
+    // (partially) applies another generic e.g. bar<T> = foo<T, uint>
+    Instance(GenericInstance),
 }
 
 impl GenericDef {
     pub fn new(generic_params: Vec<RustIdent>, orig: RustStruct) -> Self {
         Self {
             generic_params,
-            orig,
+            orig: GenericDefType::Struct(orig),
+        }
+    }
+
+    pub fn new_alias(
+        ident: RustIdent,
+        generic_params: Vec<RustIdent>,
+        base_type: RustType,
+        rule_metadata: RuleMetadata,
+    ) -> Self {
+        Self {
+            generic_params,
+            orig: GenericDefType::Alias {
+                ident,
+                base_type,
+                rule_metadata,
+            },
+        }
+    }
+
+    pub fn new_instance(generic_params: Vec<RustIdent>, instance: GenericInstance) -> Self {
+        Self {
+            generic_params,
+            orig: GenericDefType::Instance(instance),
+        }
+    }
+
+    pub fn ident(&self) -> &RustIdent {
+        match &self.orig {
+            GenericDefType::Struct(orig) => orig.ident(),
+            GenericDefType::Alias { ident, .. } => ident,
+            GenericDefType::Instance(instance) => &instance.instance_ident,
         }
     }
 }
 
 // invocation of a generic definition e.g. foo = bar<text>
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GenericInstance {
     instance_ident: RustIdent,
     generic_ident: RustIdent,
@@ -2870,7 +3687,8 @@ pub struct GenericInstance {
 #[derive(Debug, Clone)]
 pub enum GenericResolved {
     // resolved with types swapped to concrete instance
-    Resolved(RustStruct),
+    // along with any other instances it needs e.g. structs for multi-field group choice variants
+    Resolved(RustStruct, Vec<GenericInstance>),
     // could not resolve (def is extern)
     Extern {
         // internal generic ident e.g. FooBar for Foo<Bar>
@@ -2922,46 +3740,112 @@ impl GenericInstance {
                 );
             }
         };
-        assert_eq!(def.generic_params.len(), self.generic_args.len());
-        let resolved_args = def
-            .generic_params
-            .iter()
-            .zip(self.generic_args.iter())
-            .collect::<BTreeMap<&RustIdent, &RustType>>();
-        let mut instance = def.orig.clone();
+        let resolved_args = self.resolved_args(def);
+        let mut nested_instances = Vec::new();
+        let mut instance = match &def.orig {
+            GenericDefType::Struct(orig) => orig.clone(),
+            // definitions are parsed before their first use so this can only be from recursion
+            GenericDefType::Alias { .. } => panic!(
+                "{}: recursive generic aliases are not supported",
+                self.generic_ident
+            ),
+            GenericDefType::Instance(applied) => {
+                let applied = self.apply_to(applied, types, &resolved_args, &mut nested_instances);
+                return match applied.resolve(types, cli) {
+                    GenericResolved::Resolved(rs, applied_nested_instances) => {
+                        nested_instances.extend(applied_nested_instances);
+                        GenericResolved::Resolved(rs, nested_instances)
+                    }
+                    extern_resolved => extern_resolved,
+                };
+            }
+        };
         instance.ident = self.instance_ident.clone();
 
         match &mut instance.variant {
             RustStructType::Record(record) => {
                 for field in record.fields.iter_mut() {
-                    field.rust_type = Self::resolve_type(&resolved_args, &field.rust_type);
+                    field.rust_type = Self::resolve_type(
+                        types,
+                        &resolved_args,
+                        &mut nested_instances,
+                        &field.rust_type,
+                    );
+                }
+                if let Some(extra) = record.extra.as_mut() {
+                    *extra =
+                        Self::resolve_type(types, &resolved_args, &mut nested_instances, extra);
                 }
             }
             RustStructType::Table { domain, range } => {
-                *domain = Self::resolve_type(&resolved_args, domain);
-                *range = Self::resolve_type(&resolved_args, range);
+                *domain = Self::resolve_type(types, &resolved_args, &mut nested_instances, domain);
+                *range = Self::resolve_type(types, &resolved_args, &mut nested_instances, range);
             }
             RustStructType::Array { element_type } => {
-                *element_type = Self::resolve_type(&resolved_args, element_type);
+                *element_type =
+                    Self::resolve_type(types, &resolved_args, &mut nested_instances, element_type);
             }
-            RustStructType::TypeChoice { variants } | RustStructType::CStyleEnum { variants } => {
+            RustStructType::TypeChoice { variants }
+            | RustStructType::CStyleEnum { variants }
+            | RustStructType::GroupChoice { variants, .. } => {
                 for variant in variants.iter_mut() {
                     match &mut variant.data {
                         EnumVariantData::RustType(ty) => {
-                            *ty = Self::resolve_type(&resolved_args, ty);
+                            if let ConceptualRustType::Rust(variant_ident) = &ty.conceptual_type {
+                                // multi-field group choice variants are generic definitions themselves
+                                // so every instance needs its own copy of them too
+                                if types.is_plain_group(variant_ident)
+                                    && types.generic_defs.contains_key(variant_ident)
+                                {
+                                    let variant_instance_ident =
+                                        self.nested_instance_ident(def, variant_ident);
+                                    nested_instances.push(GenericInstance::new(
+                                        variant_instance_ident.clone(),
+                                        variant_ident.clone(),
+                                        self.generic_args.clone(),
+                                    ));
+                                    variant.name =
+                                        VariantIdent::new_rust(variant_instance_ident.clone());
+                                    ty.conceptual_type =
+                                        ConceptualRustType::Rust(variant_instance_ident);
+                                    continue;
+                                }
+                            }
+                            let resolved_type = Self::resolve_type(
+                                types,
+                                &resolved_args,
+                                &mut nested_instances,
+                                ty,
+                            );
+                            // variants named after the generic param e.g. T should be named after the type
+                            if variant.name.to_string() == ty.for_variant().to_string() {
+                                variant.name = VariantIdent::new_custom(
+                                    resolved_type.for_variant().to_string(),
+                                );
+                            }
+                            *ty = resolved_type;
+                        }
+                        EnumVariantData::Inlined(record) => {
+                            if let VariantIdent::RustStruct(variant_ident) = &variant.name {
+                                variant.name = VariantIdent::new_rust(
+                                    self.nested_instance_ident(def, variant_ident),
+                                );
+                            }
+                            for field in record.fields.iter_mut() {
+                                field.rust_type = Self::resolve_type(
+                                    types,
+                                    &resolved_args,
+                                    &mut nested_instances,
+                                    &field.rust_type,
+                                );
+                            }
                         }
-                        EnumVariantData::Inlined(_) => unreachable!(),
                     }
                 }
             }
-            RustStructType::GroupChoice { .. } => {
-                // for variant in variants.mut_iter() {
-                //     variant.rust_type = Self::resolve_type(&resolved_args, &variant.rust_type);
-                // }
-                todo!("we might need to recursively resolve on these");
-            }
-            RustStructType::Wrapper { .. } => {
-                todo!("should we look this up in types to resolve?");
+            RustStructType::Wrapper { wrapped, .. } => {
+                *wrapped =
+                    Self::resolve_type(types, &resolved_args, &mut nested_instances, wrapped);
             }
             RustStructType::Extern => {
                 panic!("generics should not be used on types in the prelude (e.g. int)")
@@ -2970,16 +3854,189 @@ impl GenericInstance {
                 panic!("generics not supported on raw bytes types")
             }
         };
-        GenericResolved::Resolved(instance)
+        GenericResolved::Resolved(instance, nested_instances)
     }
 
-    fn resolve_type(args: &BTreeMap<&RustIdent, &RustType>, orig: &RustType) -> RustType {
-        if let ConceptualRustType::Rust(ident) = &orig.conceptual_type {
-            if let Some(resolved_type) = args.get(ident) {
-                return (*resolved_type).clone();
+    // Some(resolved type) for instances of generic aliases e.g. foo<T> = T / null
+    // These must be resolved as soon as they're used since references to aliases are resolved immediately.
+    // Also returns any other instances needed by the resolved type.
+    pub fn resolve_alias(
+        &self,
+        types: &IntermediateTypes,
+    ) -> Option<(RustType, RuleMetadata, Vec<GenericInstance>)> {
+        let def = types.generic_defs.get(&self.generic_ident)?;
+        let resolved_args = self.resolved_args(def);
+        let mut nested_instances = Vec::new();
+        match &def.orig {
+            GenericDefType::Alias {
+                base_type,
+                rule_metadata,
+                ..
+            } => {
+                let resolved_type =
+                    Self::resolve_type(types, &resolved_args, &mut nested_instances, base_type);
+                Some((resolved_type, rule_metadata.clone(), nested_instances))
+            }
+            GenericDefType::Instance(applied) => {
+                let applied = self.apply_to(applied, types, &resolved_args, &mut nested_instances);
+                let (resolved_type, rule_metadata, applied_nested_instances) =
+                    applied.resolve_alias(types)?;
+                nested_instances.extend(applied_nested_instances);
+                Some((resolved_type, rule_metadata, nested_instances))
             }
+            GenericDefType::Struct(_) => None,
         }
-        orig.clone()
+    }
+
+    // instances used within generic definitions e.g. foo<T> = [bar<T>] can only be resolved
+    // once the outer definition is instanced too so they must not be resolved on their own
+    pub fn is_unbound(&self, types: &IntermediateTypes) -> bool {
+        fn references_generic_param(types: &IntermediateTypes, ty: &ConceptualRustType) -> bool {
+            match ty {
+                ConceptualRustType::Rust(ident) => {
+                    types.is_generic_param(ident)
+                        || types
+                            .generic_instances
+                            .get(ident)
+                            .map(|instance| instance.is_unbound(types))
+                            .unwrap_or(false)
+                }
+                ConceptualRustType::Array(inner) | ConceptualRustType::Optional(inner) => {
+                    references_generic_param(types, &inner.conceptual_type)
+                }
+                ConceptualRustType::Map(key, value) => {
+                    references_generic_param(types, &key.conceptual_type)
+                        || references_generic_param(types, &value.conceptual_type)
+                }
+                ConceptualRustType::Alias(_, inner) => references_generic_param(types, inner),
+                ConceptualRustType::Fixed(_) | ConceptualRustType::Primitive(_) => false,
+            }
+        }
+        self.generic_args
+            .iter()
+            .any(|arg| references_generic_param(types, &arg.conceptual_type))
+    }
+
+    fn resolved_args<'b>(&'b self, def: &'b GenericDef) -> BTreeMap<&'b RustIdent, &'b RustType> {
+        if def.generic_params.len() != self.generic_args.len() {
+            panic!(
+                "{}: generic {} takes {} generic argument(s) ({}) but {} were given",
+                self.instance_ident,
+                self.generic_ident,
+                def.generic_params.len(),
+                def.generic_params
+                    .iter()
+                    .map(|param| param.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.generic_args.len()
+            );
+        }
+        def.generic_params
+            .iter()
+            .zip(self.generic_args.iter())
+            .collect()
+    }
+
+    // resolves the args of the instance this def applies e.g. for bar<T> = foo<T, uint>, baz = bar<text>
+    // this would give foo<text, uint> but named baz
+    fn apply_to(
+        &self,
+        applied: &GenericInstance,
+        types: &IntermediateTypes,
+        args: &BTreeMap<&RustIdent, &RustType>,
+        nested_instances: &mut Vec<GenericInstance>,
+    ) -> GenericInstance {
+        GenericInstance::new(
+            self.instance_ident.clone(),
+            applied.generic_ident.clone(),
+            applied
+                .generic_args
+                .iter()
+                .map(|arg| Self::resolve_type(types, args, nested_instances, arg))
+                .collect(),
+        )
+    }
+
+    // e.g. Foo0 for variant Bar0 of generic def Bar when instanced as Foo
+    fn nested_instance_ident(&self, def: &GenericDef, nested_ident: &RustIdent) -> RustIdent {
+        let nested_name = nested_ident.to_string();
+        let suffix = nested_name
+            .strip_prefix(&def.ident().to_string())
+            .unwrap_or(&nested_name);
+        RustIdent::new(CDDLIdent::new(format!("{}{}", self.instance_ident, suffix)))
+    }
+
+    fn resolve_type(
+        types: &IntermediateTypes,
+        args: &BTreeMap<&RustIdent, &RustType>,
+        nested_instances: &mut Vec<GenericInstance>,
+        orig: &RustType,
+    ) -> RustType {
+        let mut resolved = orig.clone();
+        match &orig.conceptual_type {
+            ConceptualRustType::Rust(ident) => {
+                if let Some(resolved_type) = args.get(ident) {
+                    return (*resolved_type).clone();
+                }
+                // generics used within this definition need their own instance for these args
+                if let Some(unbound) = types
+                    .generic_instances
+                    .get(ident)
+                    .filter(|instance| instance.is_unbound(types))
+                {
+                    let generic_args = unbound
+                        .generic_args
+                        .iter()
+                        .map(|arg| Self::resolve_type(types, args, nested_instances, arg))
+                        .collect::<Vec<_>>();
+                    // same naming as anonymous instances e.g. foo: bar<text> would be BarText
+                    let args_name = generic_args
+                        .iter()
+                        .map(|t| t.for_variant().to_string())
+                        .collect::<Vec<String>>()
+                        .join("_");
+                    let bound = GenericInstance::new(
+                        RustIdent::new(CDDLIdent::new(format!(
+                            "{}_{}",
+                            unbound.generic_ident, args_name
+                        ))),
+                        unbound.generic_ident.clone(),
+                        generic_args,
+                    );
+                    if let Some((alias_type, _, alias_nested_instances)) =
+                        bound.resolve_alias(types)
+                    {
+                        nested_instances.extend(alias_nested_instances);
+                        return alias_type;
+                    }
+                    resolved.conceptual_type =
+                        ConceptualRustType::Rust(bound.instance_ident.clone());
+                    nested_instances.push(bound);
+                }
+            }
+            ConceptualRustType::Array(inner) => {
+                resolved.conceptual_type = ConceptualRustType::Array(Box::new(Self::resolve_type(
+                    types,
+                    args,
+                    nested_instances,
+                    inner,
+                )));
+            }
+            ConceptualRustType::Optional(inner) => {
+                resolved.conceptual_type = ConceptualRustType::Optional(Box::new(
+                    Self::resolve_type(types, args, nested_instances, inner),
+                ));
+            }
+            ConceptualRustType::Map(key, value) => {
+                resolved.conceptual_type = ConceptualRustType::Map(
+                    Box::new(Self::resolve_type(types, args, nested_instances, key)),
+                    Box::new(Self::resolve_type(types, args, nested_instances, value)),
+                );
+            }
+            _ => (),
+        }
+        resolved
     }
 }
 
@@ -3004,12 +4061,22 @@ pub fn enum_variants_have_same_encoding_var(variants: &[EnumVariant]) -> bool {
                         Some(FixedValue::Uint(_) | FixedValue::Nint(_) | FixedValue::Float(_)),
                         Some(FixedValue::Uint(_) | FixedValue::Nint(_) | FixedValue::Float(_)),
                     ) => acc,
-                    // bytes would go here once it's supported
                     (Some(FixedValue::Text(_)), Some(FixedValue::Text(_))) => acc,
+                    (Some(FixedValue::Bytes(_)), Some(FixedValue::Bytes(_))) => acc,
                     // these don't have any encoding vars
                     (
-                        Some(FixedValue::Bool(_) | FixedValue::Null),
-                        Some(FixedValue::Bool(_) | FixedValue::Null),
+                        Some(
+                            FixedValue::Bool(_)
+                            | FixedValue::Null
+                            | FixedValue::Undefined
+                            | FixedValue::Simple(_),
+                        ),
+                        Some(
+                            FixedValue::Bool(_)
+                            | FixedValue::Null
+                            | FixedValue::Undefined
+                            | FixedValue::Simple(_),
+                        ),
                     ) => acc,
                     _ => None,
                 }
@@ -3038,7 +4105,10 @@ pub fn _enum_variants_common_constant_type(variants: &[EnumVariant]) -> Option<F
                 (Some(FixedValue::Bool(_)), Some(FixedValue::Bool(_))) => acc,
                 (Some(FixedValue::Float(_)), Some(FixedValue::Float(_))) => acc,
                 (Some(FixedValue::Null), Some(FixedValue::Null)) => acc,
+                (Some(FixedValue::Undefined), Some(FixedValue::Undefined)) => acc,
+                (Some(FixedValue::Simple(_)), Some(FixedValue::Simple(_))) => acc,
                 (Some(FixedValue::Text(_)), Some(FixedValue::Text(_))) => acc,
+                (Some(FixedValue::Bytes(_)), Some(FixedValue::Bytes(_))) => acc,
                 _ => None,
             }
         },
@@ -9,11 +9,14 @@ pub(crate) mod utils;
 
 use clap::Parser;
 use cli::Cli;
-use comment_ast::RuleMetadata;
+use comment_ast::{merge_metadata, RuleMetadata};
 use generation::GenerationScope;
 use intermediate::{CDDLIdent, IntermediateTypes, PlainGroupInfo, RustIdent};
 use once_cell::sync::Lazy;
-use parsing::{parse_rule, rule_ident, rule_is_scope_marker};
+use parsing::{
+    merge_socket_plugs, parse_generic_rule, parse_rule, rule_ident, rule_is_generic,
+    rule_is_scope_marker, rule_is_socket,
+};
 
 pub static CLI_ARGS: Lazy<Cli> = Lazy::new(Cli::parse);
 
@@ -118,14 +121,60 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 false
             } else {
                 let ident = rule_ident(cddl_rule);
-                types.mark_scope(ident, scope.clone());
+                // sockets can be plugged from multiple files but are generated where they're first defined
+                if !(rule_is_socket(cddl_rule) && types.has_rule(&ident)) {
+                    types.mark_scope(ident, scope.clone());
+                }
                 true
             }
         })
         .collect::<Vec<_>>();
+    // all socket plugs need to be in one place to generate a single choice out of them
+    let merged_sockets = merge_socket_plugs(&cddl_rules);
+    let cddl_rules = cddl_rules
+        .into_iter()
+        .filter(|cddl_rule| !rule_is_socket(cddl_rule))
+        .chain(merged_sockets.iter())
+        .collect::<Vec<_>>();
     // We need to know beforehand which are plain groups so we can serialize them properly
     // e.g. x = (3, 4), y = [1, x, 2] should be [1, 3, 4, 2] instead of [1, [3, 4], 2]
     for cddl_rule in cddl_rules.iter() {
+        // arrays/maps can have their contents embedded into other groups with the unwrap operator ~
+        if let cddl::ast::Rule::Type { rule, .. } = cddl_rule {
+            if let [type_choice @ cddl::ast::TypeChoice {
+                type1:
+                    type1 @ cddl::ast::Type1 {
+                        type2:
+                            cddl::ast::Type2::Array { group, .. } | cddl::ast::Type2::Map { group, .. },
+                        operator: None,
+                        ..
+                    },
+                ..
+            }] = rule.value.type_choices.as_slice()
+            {
+                if rule.generic_params.is_none() {
+                    let rule_metadata = merge_metadata(
+                        &RuleMetadata::from(type1.comments_after_type.as_ref()),
+                        &RuleMetadata::from(type_choice.comments_after_type.as_ref()),
+                    );
+                    // the fields are embedded without the array/map header so anything describing
+                    // how the whole type is encoded doesn't carry over to the plain group
+                    let rule_metadata = RuleMetadata {
+                        newtype: None,
+                        custom_serialize: None,
+                        custom_deserialize: None,
+                        ..rule_metadata
+                    };
+                    types.mark_unwrappable_group(
+                        RustIdent::new(CDDLIdent::new(rule.name.to_string())),
+                        PlainGroupInfo::new(Some(group.clone()), rule_metadata),
+                    );
+                }
+            }
+        }
+        if rule_is_generic(cddl_rule) {
+            types.mark_generic_rule(rule_ident(cddl_rule), (*cddl_rule).clone());
+        }
         if let cddl::ast::Rule::Group { rule, .. } = cddl_rule {
             // Freely defined group - no need to generate anything outside of group module
             match &rule.entry {
@@ -134,7 +183,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     comments_after_group,
                     ..
                 } => {
-                    assert_eq!(group.group_choices.len(), 1);
+                    // sockets can have choices from being plugged multiple times
+                    assert!(group.group_choices.len() == 1 || rule.name.socket.is_some());
                     let rule_metadata = RuleMetadata::from(comments_after_group.as_ref());
                     types.mark_plain_group(
                         RustIdent::new(CDDLIdent::new(rule.name.to_string())),
@@ -149,7 +199,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Creating intermediate form from the CDDL
     for cddl_rule in dep_graph::topological_rule_order(&cddl_rules) {
         println!("\n\n------------------------------------------\n- Handling rule: {}:{}\n------------------------------------", scope, cddl_rule.name());
-        parse_rule(&mut types, &pv, cddl_rule, &CLI_ARGS);
+        if rule_is_generic(cddl_rule) {
+            // these might have already been parsed on demand by an instance using them
+            parse_generic_rule(&mut types, &pv, &rule_ident(cddl_rule), &CLI_ARGS);
+        } else {
+            parse_rule(&mut types, &pv, cddl_rule, &CLI_ARGS);
+        }
     }
     types.finalize(&pv, &CLI_ARGS);
 
@@ -5,22 +5,29 @@ use std::collections::BTreeMap;
 
 use crate::comment_ast::{merge_metadata, metadata_from_comments, RuleMetadata};
 use crate::intermediate::{
-    AliasInfo, CBOREncodingOperation, CDDLIdent, ConceptualRustType, EnumVariant, FixedValue,
-    GenericDef, GenericInstance, IntermediateTypes, ModuleScope, PlainGroupInfo, Primitive,
-    Representation, RustField, RustIdent, RustRecord, RustStruct, RustStructType, RustType,
-    VariantIdent,
+    AliasIdent, AliasInfo, CBOREncodingOperation, CDDLIdent, ConceptualRustType, EnumVariant,
+    FixedValue, FloatBounds, GenericDef, GenericInstance, IntermediateTypes, MapKey, ModuleScope,
+    PlainGroupInfo, Primitive, Representation, RustField, RustIdent, RustRecord, RustStruct,
+    RustStructType, RustType, VariantIdent, CBOR_VALUE_IDENT, TAGGED_VALUE_IDENT,
 };
 use crate::utils::{
     append_number_if_duplicate, convert_to_camel_case, convert_to_snake_case,
-    is_identifier_user_defined,
+    is_identifier_user_defined, to_hex,
 };
 
 #[derive(Clone, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 enum ControlOperator {
     Range((Option<i128>, Option<i128>)),
+    // ranges on floats e.g. float64 .lt 1.5 which can be fractional / exclusive
+    FloatRange(FloatBounds),
     CBOR(RustType),
+    CBORSeq(RustType),
     Default(FixedValue),
+    Regexp(String),
+    NotEqual(FixedValue),
+    // (name, bit position)
+    Bits(Vec<(String, u64)>),
 }
 
 pub const SCOPE_MARKER: &str = "_CDDL_CODEGEN_SCOPE_MARKER_";
@@ -55,6 +62,78 @@ pub fn rule_is_scope_marker(cddl_rule: &cddl::ast::Rule) -> Option<ModuleScope>
     }
 }
 
+pub fn rule_is_socket(cddl_rule: &cddl::ast::Rule) -> bool {
+    match cddl_rule {
+        cddl::ast::Rule::Type { rule, .. } => rule.name.socket.is_some(),
+        cddl::ast::Rule::Group { rule, .. } => rule.name.socket.is_some(),
+    }
+}
+
+pub fn rule_is_generic(cddl_rule: &cddl::ast::Rule) -> bool {
+    match cddl_rule {
+        cddl::ast::Rule::Type { rule, .. } => rule.generic_params.is_some(),
+        cddl::ast::Rule::Group { rule, .. } => rule.generic_params.is_some(),
+    }
+}
+
+/// Parses the generic definition of the given ident if it hasn't been parsed yet.
+/// Generic instances call this before registering themselves so that their definition is always
+/// known regardless of the order the rules are parsed in e.g. from recursive types.
+pub fn parse_generic_rule(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    ident: &RustIdent,
+    cli: &Cli,
+) {
+    if let Some(cddl_rule) = types.take_unparsed_generic_rule(ident) {
+        parse_rule(types, parent_visitor, &cddl_rule, cli);
+    }
+}
+
+/// Sockets ($foo / $$foo) can be plugged any amount of times (including from other files)
+/// using /= and //= so we merge all of them into a single rule per socket before parsing
+/// in order to generate just one type / group choice for each.
+pub fn merge_socket_plugs<'a>(cddl_rules: &[&cddl::ast::Rule<'a>]) -> Vec<cddl::ast::Rule<'a>> {
+    let mut merged: Vec<cddl::ast::Rule<'a>> = Vec::new();
+    for cddl_rule in cddl_rules
+        .iter()
+        .filter(|cddl_rule| rule_is_socket(cddl_rule))
+    {
+        match merged
+            .iter_mut()
+            .find(|socket| socket.name() == cddl_rule.name())
+        {
+            Some(socket) => match (socket, cddl_rule) {
+                (
+                    cddl::ast::Rule::Type { rule: socket, .. },
+                    cddl::ast::Rule::Type { rule, .. },
+                ) => socket
+                    .value
+                    .type_choices
+                    .extend(rule.value.type_choices.iter().cloned()),
+                (
+                    cddl::ast::Rule::Group { rule: socket, .. },
+                    cddl::ast::Rule::Group { rule, .. },
+                ) => match (&mut socket.entry, &rule.entry) {
+                    (
+                        GroupEntry::InlineGroup { group: socket, .. },
+                        GroupEntry::InlineGroup { group, .. },
+                    ) => socket
+                        .group_choices
+                        .extend(group.group_choices.iter().cloned()),
+                    (_, x) => panic!("Group rule with non-inline group? {:?}", x),
+                },
+                _ => panic!(
+                    "{}: type sockets can only be plugged with types and group sockets with groups",
+                    cddl_rule.name()
+                ),
+            },
+            None => merged.push((*cddl_rule).clone()),
+        }
+    }
+    merged
+}
+
 pub fn parse_rule(
     types: &mut IntermediateTypes,
     parent_visitor: &ParentVisitor,
@@ -82,6 +161,9 @@ pub fn parse_rule(
                         .map(|id| RustIdent::new(CDDLIdent::new(id.param.to_string())))
                         .collect::<Vec<_>>()
                 });
+                if let Some(params) = &generic_params {
+                    types.mark_generic_params(params);
+                }
                 if rule.value.type_choices.len() == 1 {
                     let choice = &rule.value.type_choices.first().unwrap();
                     parse_type(
@@ -157,11 +239,6 @@ fn parse_type_choices(
         None
     };
     if let Some(inner_type2) = optional_inner_type {
-        if generic_params.is_some() {
-            // the current generic support relies on having a RustStruct to swap out the types with
-            // but that won't happen with T / null types since we generate an alias instead
-            todo!("support foo<T> = T / null");
-        }
         let inner_rust_type = rust_type_from_type1(types, parent_visitor, inner_type2, cli);
         let final_type = match tag {
             Some(tag) => {
@@ -170,10 +247,19 @@ fn parse_type_choices(
             None => RustType::new(ConceptualRustType::Optional(Box::new(inner_rust_type))),
         };
         let rule_metadata = RuleMetadata::from(inner_type2.comments_after_type.as_ref());
-        types.register_type_alias(
-            name.clone(),
-            AliasInfo::new_from_metadata(final_type, rule_metadata),
-        );
+        match generic_params {
+            // there is no RustStruct to swap the types out in so every instance becomes its own alias
+            Some(params) => types.register_generic_def(GenericDef::new_alias(
+                name.clone(),
+                params,
+                final_type,
+                rule_metadata,
+            )),
+            None => types.register_type_alias(
+                name.clone(),
+                AliasInfo::new_from_metadata(final_type, rule_metadata),
+            ),
+        }
     } else {
         let rule_metadata = merge_metadata(
             &RuleMetadata::from(
@@ -208,8 +294,10 @@ fn ident_to_primitive(ident: &CDDLIdent) -> Option<Primitive> {
         "int" => Some(Primitive::I64),
         "uint" => Some(Primitive::U64),
         "nint" => Some(Primitive::N64),
-        "float16" | "float32" => Some(Primitive::F32),
+        "float16" => Some(Primitive::F16),
+        "float32" => Some(Primitive::F32),
         "float64" => Some(Primitive::F64),
+        "float" => Some(Primitive::Float),
         _other => None,
     }
 }
@@ -223,7 +311,8 @@ fn type2_to_number_literal(type2: &Type2) -> i128 {
             let as_int = *value as i128;
             assert_eq!(
                 as_int as f64, *value,
-                "decimal not supported. Issue: https://github.com/dcSpark/cddl-codegen/issues/178"
+                "{} is not a whole number. Fractional bounds are only supported on floats e.g. float64 .le {}",
+                value, value
             );
             as_int
         }
@@ -234,12 +323,32 @@ fn type2_to_number_literal(type2: &Type2) -> i128 {
     }
 }
 
+fn type2_to_float_literal(type2: &Type2) -> f64 {
+    match type2 {
+        Type2::UintValue { value, .. } => *value as f64,
+        Type2::IntValue { value, .. } => *value as f64,
+        Type2::FloatValue { value, .. } => *value,
+        _ => panic!(
+            "Value specified: {:?} must be a number literal to be used here",
+            type2
+        ),
+    }
+}
+
 fn type2_to_fixed_value(type2: &Type2) -> FixedValue {
     match type2 {
         Type2::UintValue { value, .. } => FixedValue::Uint(*value),
         Type2::IntValue { value, .. } => FixedValue::Nint(*value),
         Type2::FloatValue { value, .. } => FixedValue::Float(*value),
         Type2::TextValue { value, .. } => FixedValue::Text(value.to_string()),
+        Type2::UTF8ByteString { value, .. } => FixedValue::Bytes(value.to_vec()),
+        Type2::B16ByteString { value, .. } => FixedValue::Bytes(decode_b16(value)),
+        Type2::DataMajorType {
+            mt: 7,
+            constraint: Some(simple),
+            ..
+        } => simple_value_to_fixed_value(*simple as u64)
+            .unwrap_or_else(|| panic!("#7.{} is a float type and not a fixed value", simple)),
         _ => panic!(
             "Type2: {:?} does not correspond to a supported FixedValue",
             type2
@@ -247,6 +356,279 @@ fn type2_to_fixed_value(type2: &Type2) -> FixedValue {
     }
 }
 
+// #7.n for the simple values e.g. #7.16 or #7.23 (undefined). None for the floats #7.25 - #7.27
+fn simple_value_to_fixed_value(simple: u64) -> Option<FixedValue> {
+    match simple {
+        20 => Some(FixedValue::Bool(false)),
+        21 => Some(FixedValue::Bool(true)),
+        22 => Some(FixedValue::Null),
+        23 => Some(FixedValue::Undefined),
+        25..=27 => None,
+        0..=19 | 32..=255 => Some(FixedValue::Simple(simple as u8)),
+        _ => panic!("#7.{} is not a valid simple value", simple),
+    }
+}
+
+// #7.n e.g. #7.23 (undefined) or #7.26 (float32)
+fn simple_value_to_rust_type(simple: u64) -> RustType {
+    match simple {
+        25 => ConceptualRustType::Primitive(Primitive::F16).into(),
+        26 => ConceptualRustType::Primitive(Primitive::F32).into(),
+        27 => ConceptualRustType::Primitive(Primitive::F64).into(),
+        _ => ConceptualRustType::Fixed(simple_value_to_fixed_value(simple).unwrap()).into(),
+    }
+}
+
+// #n / #n.m for the major types e.g. #2 (any bstr), #0.5 (the uint 5) or #6.32 (tag 32 of any value)
+fn major_type_to_rust_type(
+    types: &mut IntermediateTypes,
+    mt: u8,
+    constraint: Option<u64>,
+    cli: &Cli,
+) -> RustType {
+    match (mt, constraint) {
+        (0, None) => ConceptualRustType::Primitive(Primitive::U64).into(),
+        (0, Some(value)) => ConceptualRustType::Fixed(FixedValue::Uint(value as usize)).into(),
+        (1, None) => ConceptualRustType::Primitive(Primitive::N64).into(),
+        (1, Some(value)) => {
+            let nint = -1 - value as i128;
+            let nint = isize::try_from(nint).unwrap_or_else(|_| {
+                panic!(
+                    "#1.{}: {} is too small to be used as a fixed value",
+                    value, nint
+                )
+            });
+            ConceptualRustType::Fixed(FixedValue::Nint(nint)).into()
+        }
+        (2, None) => ConceptualRustType::Primitive(Primitive::Bytes).into(),
+        (3, None) => ConceptualRustType::Primitive(Primitive::Str).into(),
+        (4, None) => {
+            ConceptualRustType::Array(Box::new(types.new_type(&CDDLIdent::new("any"), cli))).into()
+        }
+        (5, None) => ConceptualRustType::Map(
+            Box::new(types.new_type(&CDDLIdent::new("any"), cli)),
+            Box::new(types.new_type(&CDDLIdent::new("any"), cli)),
+        )
+        .into(),
+        // for byte/text strings, arrays and maps the argument is the length e.g. #2.4 is bytes .size 4
+        (2..=5, Some(len)) => major_type_to_rust_type(types, mt, None, cli)
+            .with_bounds((Some(len as i128), Some(len as i128))),
+        (6, None) => types.static_extern_type(TAGGED_VALUE_IDENT),
+        (6, Some(tag)) => types
+            .new_type(&CDDLIdent::new("any"), cli)
+            .tag(tag as usize),
+        (7, Some(simple)) => simple_value_to_rust_type(simple),
+        (7, None) => panic!("#7 is not supported. Use the specific simple value / float types"),
+        _ => panic!("#{} is not a valid major type", mt),
+    }
+}
+
+// #6(T) i.e. any tag number. Only #6(any) is supported as the value is kept as a CBORValue
+fn any_tag_rust_type(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    t: &Type,
+    cli: &Cli,
+) -> RustType {
+    let inner_type = rust_type(types, parent_visitor, t, cli);
+    match inner_type.conceptual_type.resolve_alias_shallow() {
+        ConceptualRustType::Rust(ident) if ident.as_ref() == CBOR_VALUE_IDENT => {
+            types.static_extern_type(TAGGED_VALUE_IDENT)
+        }
+        _ => panic!(
+            "#6({}): tags without a tag number are only supported for any values e.g. #6(any)",
+            t
+        ),
+    }
+}
+
+// the hex digits of a h'00ff' literal (cddl keeps them as the raw ascii)
+fn decode_b16(hex: &[u8]) -> Vec<u8> {
+    let hex = hex
+        .iter()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| *c as char)
+        .collect::<Vec<char>>();
+    assert!(
+        hex.len() % 2 == 0,
+        "odd number of hex digits in h'{}'",
+        hex.iter().collect::<String>()
+    );
+    hex.chunks(2)
+        .map(|pair| {
+            let digits = pair.iter().collect::<String>();
+            u8::from_str_radix(&digits, 16)
+                .unwrap_or_else(|_| panic!("invalid hex digits {digits} in byte string literal"))
+        })
+        .collect()
+}
+
+// (name, bit position) for all bits declared for .bits e.g. uint .bits &(read: 0, write: 1)
+fn type2_to_bits(types: &IntermediateTypes, type2: &Type2) -> Vec<(String, u64)> {
+    match type2 {
+        Type2::ChoiceFromInlineGroup { group, .. } => group_to_bits(group),
+        Type2::ChoiceFromGroup { ident, .. } => {
+            match types
+                .plain_group(&RustIdent::new(CDDLIdent::new(ident.to_string())))
+                .and_then(PlainGroupInfo::group)
+            {
+                Some(group) => group_to_bits(group),
+                None => panic!(".bits refers to group {} which was not found", ident),
+            }
+        }
+        Type2::Typename { ident, .. } => {
+            match types
+                .rust_struct(&RustIdent::new(CDDLIdent::new(ident.to_string())))
+                .map(RustStruct::variant)
+            {
+                Some(RustStructType::CStyleEnum { variants }) => variants
+                    .iter()
+                    .map(|variant| match &variant.rust_type().conceptual_type {
+                        ConceptualRustType::Fixed(FixedValue::Uint(bit)) => {
+                            (variant.name.to_string(), *bit)
+                        }
+                        _ => panic!(".bits {} contains a non-uint bit position", ident),
+                    })
+                    .collect(),
+                _ => panic!(
+                    ".bits {} must be a choice of uint bit positions e.g. &(read: 0, write: 1)",
+                    ident
+                ),
+            }
+        }
+        _ => panic!(
+            ".bits expects a choice of named uint bit positions e.g. &(read: 0, write: 1), found {:?}",
+            type2
+        ),
+    }
+}
+
+fn group_to_bits(group: &Group) -> Vec<(String, u64)> {
+    assert_eq!(
+        group.group_choices.len(),
+        1,
+        ".bits does not support group choices"
+    );
+    group.group_choices[0]
+        .group_entries
+        .iter()
+        .map(|(group_entry, _)| match group_entry {
+            GroupEntry::ValueMemberKey { ge, .. } => {
+                let name = match &ge.member_key {
+                    Some(MemberKey::Bareword { ident, .. }) => ident.to_string(),
+                    _ => panic!(".bits entries must be named e.g. read: 0"),
+                };
+                let bit = match ge.entry_type.type_choices.as_slice() {
+                    [TypeChoice {
+                        type1:
+                            Type1 {
+                                type2: Type2::UintValue { value, .. },
+                                operator: None,
+                                ..
+                            },
+                        ..
+                    }] => *value as u64,
+                    _ => panic!(".bits entry {} must be a single uint bit position", name),
+                };
+                (name, bit)
+            }
+            _ => panic!(".bits entries must be named bit positions e.g. read: 0"),
+        })
+        .collect()
+}
+
+// (variant name, fixed value type) for a choice from a group e.g. &(red: 0, green: 1) or &colors
+fn choice_from_group_variants(types: &IntermediateTypes, type2: &Type2) -> Vec<(String, RustType)> {
+    match type2 {
+        Type2::ChoiceFromInlineGroup { group, .. } => group_to_choice_variants(types, group),
+        Type2::ChoiceFromGroup {
+            ident,
+            generic_args,
+            ..
+        } => group_to_choice_variants(
+            types,
+            choice_group(types, &ident.to_string(), generic_args.is_some()),
+        ),
+        _ => unreachable!("not a choice from a group: {:?}", type2),
+    }
+}
+
+// The definition of the group {name} that a choice from a group takes its values from
+fn choice_group<'a, 'b>(
+    types: &'b IntermediateTypes<'a>,
+    name: &str,
+    is_generic_instance: bool,
+) -> &'b Group<'a> {
+    let ident = RustIdent::new(CDDLIdent::new(name.to_string()));
+    if is_generic_instance {
+        // generic plain groups can't be defined (see parse_rule) so there's nothing to resolve
+        panic!(
+            "&{}: choices from generic groups are not supported. Take the choices from a non-generic group instead e.g. &(a: 0, b: 1)",
+            name
+        );
+    }
+    match types.plain_group(&ident) {
+        Some(info) => match info.group() {
+            Some(group) => group,
+            None => panic!(
+                "&{}: {} has no group definition in the cddl to take the choices from",
+                name, name
+            ),
+        },
+        None if types.has_rule(&ident) => panic!(
+            "&{}: {} is a type but choices can only be taken from groups e.g. {} = (a: 0, b: 1)",
+            name, name, name
+        ),
+        None => panic!(
+            "&{}: the group {} is not defined. Choices can only be taken from groups defined in the cddl",
+            name, name
+        ),
+    }
+}
+
+fn group_to_choice_variants(types: &IntermediateTypes, group: &Group) -> Vec<(String, RustType)> {
+    group
+        .group_choices
+        .iter()
+        .flat_map(|group_choice| group_choice.group_entries.iter())
+        .flat_map(|(group_entry, _)| match group_entry {
+            GroupEntry::ValueMemberKey { ge, .. } => {
+                let value = match ge.entry_type.type_choices.as_slice() {
+                    [TypeChoice {
+                        type1:
+                            Type1 {
+                                type2,
+                                operator: None,
+                                ..
+                            },
+                        ..
+                    }] => type2_to_fixed_value(type2),
+                    _ => panic!(
+                        "choices from a group must only contain literal values, found {:?}",
+                        ge.entry_type
+                    ),
+                };
+                let rust_type = RustType::new(ConceptualRustType::Fixed(value));
+                let name = match &ge.member_key {
+                    Some(MemberKey::Bareword { ident, .. }) => convert_to_camel_case(ident.ident),
+                    None => rust_type.for_variant().to_string(),
+                    Some(other) => panic!(
+                        "choices from a group must use names as keys e.g. red: 0, found {:?}",
+                        other
+                    ),
+                };
+                vec![(name, rust_type)]
+            }
+            // nested groups contribute all of their values e.g. &(colors, other: 5)
+            GroupEntry::TypeGroupname { ge, .. } => group_to_choice_variants(
+                types,
+                choice_group(types, &ge.name.to_string(), ge.generic_args.is_some()),
+            ),
+            GroupEntry::InlineGroup { group, .. } => group_to_choice_variants(types, group),
+        })
+        .collect()
+}
+
 fn parse_control_operator(
     types: &mut IntermediateTypes,
     parent_visitor: &ParentVisitor,
@@ -258,9 +640,23 @@ fn parse_control_operator(
         Type2::Typename { ident, .. } if ident.to_string() == "uint" => Some(0),
         _ => None,
     };
+    // float bounds can be fractional / exclusive so they're kept separately as floats
+    let is_float = match type2 {
+        Type2::Typename { ident, .. } => ident_to_primitive(&CDDLIdent::new(ident.to_string()))
+            .map(Primitive::is_float)
+            .unwrap_or(false),
+        Type2::FloatValue { .. } => true,
+        _ => matches!(operator.type2, Type2::FloatValue { .. }),
+    };
     //todo: read up on other range control operators in CDDL RFC
     // (rangeop / ctlop) S type2
     match operator.operator {
+        RangeCtlOp::RangeOp { is_inclusive, .. } if is_float => {
+            ControlOperator::FloatRange(FloatBounds::new(
+                Some((type2_to_float_literal(type2), true)),
+                Some((type2_to_float_literal(&operator.type2), is_inclusive)),
+            ))
+        }
         RangeCtlOp::RangeOp { is_inclusive, .. } => {
             let range_start = match type2 {
                 Type2::UintValue { value, .. } => *value as i128,
@@ -284,28 +680,89 @@ fn parse_control_operator(
             ))
         }
         RangeCtlOp::CtlOp { ctrl, .. } => match ctrl {
-            token::ControlOperator::CBORSEQ
-            | token::ControlOperator::WITHIN
-            | token::ControlOperator::AND => todo!("control operator {} not supported", ctrl),
+            token::ControlOperator::WITHIN | token::ControlOperator::AND => {
+                unreachable!(".and / .within are handled by type1_to_intersected()")
+            }
             token::ControlOperator::DEFAULT => {
                 ControlOperator::Default(type2_to_fixed_value(&operator.type2))
             }
+            token::ControlOperator::REGEXP => match &operator.type2 {
+                // CDDL text escapes are not regex escapes e.g. "\\." is the regex \.
+                Type2::TextValue { value, .. } => {
+                    let regexp = value
+                        .to_string()
+                        .replace("\\\"", "\"")
+                        .replace("\\\\", "\\");
+                    // compiled the same way as in the generated code so it can't fail there
+                    if let Err(e) = regex::Regex::new(&format!("^(?:{regexp})$")) {
+                        panic!(
+                            "{}: .regexp \"{}\" is not a valid regex: {}",
+                            get_rule_name(parent_visitor, &CDDLType::from(type2)),
+                            regexp,
+                            e
+                        );
+                    }
+                    ControlOperator::Regexp(regexp)
+                }
+                _ => panic!(
+                    ".regexp requires a text literal, found {:?}",
+                    operator.type2
+                ),
+            },
+            token::ControlOperator::BITS => {
+                ControlOperator::Bits(type2_to_bits(types, &operator.type2))
+            }
             token::ControlOperator::CBOR => ControlOperator::CBOR(rust_type_from_type2(
                 types,
                 parent_visitor,
                 &operator.type2,
                 cli,
             )),
+            token::ControlOperator::CBORSEQ => {
+                let seq_type = rust_type_from_type2(types, parent_visitor, &operator.type2, cli);
+                if let ConceptualRustType::Array(elem_type) =
+                    seq_type.conceptual_type.resolve_alias_shallow()
+                {
+                    if let ConceptualRustType::Rust(elem_ident) = &elem_type.conceptual_type {
+                        if types.is_plain_group(elem_ident) {
+                            panic!(
+                                ".cborseq of plain group {} is not supported. Wrap it in an array or map instead e.g. bytes .cborseq [* [{}]]",
+                                elem_ident, elem_ident
+                            );
+                        }
+                    }
+                }
+                ControlOperator::CBORSeq(seq_type)
+            }
+            token::ControlOperator::EQ
+            | token::ControlOperator::LE
+            | token::ControlOperator::LT
+            | token::ControlOperator::GE
+            | token::ControlOperator::GT
+                if is_float =>
+            {
+                let value = type2_to_float_literal(&operator.type2);
+                ControlOperator::FloatRange(match ctrl {
+                    token::ControlOperator::EQ => {
+                        FloatBounds::new(Some((value, true)), Some((value, true)))
+                    }
+                    token::ControlOperator::LE => FloatBounds::new(None, Some((value, true))),
+                    token::ControlOperator::LT => FloatBounds::new(None, Some((value, false))),
+                    token::ControlOperator::GE => FloatBounds::new(Some((value, true)), None),
+                    token::ControlOperator::GT => FloatBounds::new(Some((value, false)), None),
+                    _ => unreachable!(),
+                })
+            }
+            token::ControlOperator::SIZE if is_float => {
+                panic!(".size is not supported on floats: {:?}", operator)
+            }
             token::ControlOperator::EQ => ControlOperator::Range((
                 Some(type2_to_number_literal(&operator.type2)),
                 Some(type2_to_number_literal(&operator.type2)),
             )),
-            // TODO: this would be MUCH nicer (for error displaying, etc) to handle this in its own dedicated way
-            //       which might be necessary once we support other control operators anyway
-            token::ControlOperator::NE => ControlOperator::Range((
-                Some(type2_to_number_literal(&operator.type2) + 1),
-                Some(type2_to_number_literal(&operator.type2) - 1),
-            )),
+            token::ControlOperator::NE => {
+                ControlOperator::NotEqual(type2_to_fixed_value(&operator.type2))
+            }
             token::ControlOperator::LE => ControlOperator::Range((
                 lower_bound,
                 Some(type2_to_number_literal(&operator.type2)),
@@ -423,6 +880,564 @@ fn parse_control_operator(
     }
 }
 
+fn is_intersection_operator(operator: Option<&Operator>) -> bool {
+    matches!(
+        operator.map(|op| &op.operator),
+        Some(RangeCtlOp::CtlOp {
+            ctrl: token::ControlOperator::AND | token::ControlOperator::WITHIN,
+            ..
+        })
+    )
+}
+
+/// Constraints of a type that is the intersection of both sides of .and / .within
+/// e.g. (uint .ge 5) .and (uint .le 100) is a uint in the range 5..=100
+#[derive(Clone, Debug)]
+struct IntersectedType {
+    // only primitives or fixed values can be intersected
+    base: ConceptualRustType,
+    min_max: Option<(Option<i128>, Option<i128>)>,
+    float_bounds: Option<FloatBounds>,
+    regexp: Option<String>,
+    bits: Option<Vec<(String, u64)>>,
+    excluded: Option<Vec<FixedValue>>,
+}
+
+impl IntersectedType {
+    fn new(base: ConceptualRustType) -> Self {
+        Self {
+            base,
+            min_max: None,
+            float_bounds: None,
+            regexp: None,
+            bits: None,
+            excluded: None,
+        }
+    }
+
+    fn apply_control(mut self, control: ControlOperator) -> Self {
+        match control {
+            ControlOperator::Range(min_max) => {
+                self.min_max = Some(intersect_ranges(self.min_max, Some(min_max)));
+            }
+            ControlOperator::FloatRange(float_bounds) => {
+                self.float_bounds = Some(match self.float_bounds {
+                    Some(existing) => existing.intersect(float_bounds),
+                    None => float_bounds,
+                });
+            }
+            ControlOperator::Regexp(regexp) => {
+                assert!(
+                    self.regexp.is_none(),
+                    "intersecting multiple .regexp not supported"
+                );
+                self.regexp = Some(regexp);
+            }
+            ControlOperator::Bits(bits) => {
+                if !matches!(self.base, ConceptualRustType::Primitive(Primitive::Bytes)) {
+                    assert!(
+                        bits.iter().all(|(_name, bit)| *bit < u64::BITS as u64),
+                        "uint .bits only supports bit positions that fit in a u64"
+                    );
+                }
+                self.bits = Some(intersect_bits(self.bits, bits));
+            }
+            ControlOperator::NotEqual(value) => {
+                check_excluded_value(&self.base, &value);
+                let excluded = self.excluded.get_or_insert_with(Vec::new);
+                if !excluded.contains(&value) {
+                    excluded.push(value);
+                }
+            }
+            ControlOperator::CBOR(_)
+            | ControlOperator::CBORSeq(_)
+            | ControlOperator::Default(_) => {
+                panic!(".cbor / .cborseq / .default can't be combined using .and / .within")
+            }
+        }
+        self
+    }
+
+    fn intersect(self, other: Self) -> Self {
+        match (&self.base, &other.base) {
+            (ConceptualRustType::Fixed(lhs), ConceptualRustType::Fixed(rhs)) => {
+                assert_eq!(lhs, rhs, ".and / .within of two different values is empty");
+                self
+            }
+            (ConceptualRustType::Fixed(_), _) => other.check_fixed(self),
+            (_, ConceptualRustType::Fixed(_)) => self.check_fixed(other),
+            (ConceptualRustType::Primitive(lhs), ConceptualRustType::Primitive(rhs)) => {
+                let (base, min_max) = if lhs == rhs {
+                    let min_max = match (self.min_max, other.min_max) {
+                        (None, None) => None,
+                        (lhs_range, rhs_range) => Some(intersect_ranges(lhs_range, rhs_range)),
+                    };
+                    (*lhs, min_max)
+                } else {
+                    // e.g. uint .size 1 (u8) .and int .le 100 - restrict using the implied ranges of both
+                    let (lhs_range, rhs_range) =
+                        match (primitive_range(*lhs), primitive_range(*rhs)) {
+                            (Some(lhs_range), Some(rhs_range)) => (
+                                intersect_ranges(Some(lhs_range), self.min_max),
+                                intersect_ranges(Some(rhs_range), other.min_max),
+                            ),
+                            _ => panic!(
+                                ".and / .within between incompatible types {:?} and {:?}",
+                                lhs, rhs
+                            ),
+                        };
+                    let min_max = intersect_ranges(Some(lhs_range), Some(rhs_range));
+                    let base = if min_max.0.map(|min| min >= 0).unwrap_or(false) {
+                        Primitive::U64
+                    } else {
+                        Primitive::I64
+                    };
+                    (base, Some(min_max))
+                };
+                let mut intersected = Self::new(ConceptualRustType::Primitive(base));
+                intersected.min_max = min_max;
+                intersected.float_bounds = self.float_bounds;
+                if let Some(float_bounds) = other.float_bounds {
+                    intersected =
+                        intersected.apply_control(ControlOperator::FloatRange(float_bounds));
+                }
+                intersected.regexp = self.regexp;
+                if let Some(regexp) = other.regexp {
+                    intersected = intersected.apply_control(ControlOperator::Regexp(regexp));
+                }
+                intersected.bits = self.bits;
+                if let Some(bits) = other.bits {
+                    intersected = intersected.apply_control(ControlOperator::Bits(bits));
+                }
+                intersected.excluded = self.excluded;
+                for value in other.excluded.unwrap_or_default() {
+                    intersected = intersected.apply_control(ControlOperator::NotEqual(value));
+                }
+                intersected
+            }
+            (lhs, rhs) => panic!(
+                ".and / .within only supported on primitives / values. Found {:?} and {:?}",
+                lhs, rhs
+            ),
+        }
+    }
+
+    // statically checks that the fixed value satisfies our constraints
+    fn check_fixed(self, fixed: Self) -> Self {
+        let value = match &fixed.base {
+            ConceptualRustType::Fixed(value) => value,
+            _ => unreachable!(),
+        };
+        let (compatible, measured) = match (&self.base, value) {
+            (ConceptualRustType::Primitive(Primitive::Str), FixedValue::Text(text)) => {
+                (true, text.len() as i128)
+            }
+            (ConceptualRustType::Primitive(p), FixedValue::Uint(x)) => (
+                matches!(
+                    p,
+                    Primitive::U8
+                        | Primitive::U16
+                        | Primitive::U32
+                        | Primitive::U64
+                        | Primitive::I8
+                        | Primitive::I16
+                        | Primitive::I32
+                        | Primitive::I64
+                ),
+                *x as i128,
+            ),
+            (ConceptualRustType::Primitive(p), FixedValue::Nint(x)) => (
+                matches!(
+                    p,
+                    Primitive::I8
+                        | Primitive::I16
+                        | Primitive::I32
+                        | Primitive::I64
+                        | Primitive::N64
+                ),
+                *x as i128,
+            ),
+            // compared separately below so that e.g. 1.5 isn't truncated to 1
+            (ConceptualRustType::Primitive(p), FixedValue::Float(_)) => (p.is_float(), 0),
+            _ => (false, 0),
+        };
+        let (min, max) = intersect_ranges(primitive_range_or_none(&self.base), self.min_max);
+        let in_range = match value {
+            FixedValue::Float(x) => self
+                .float_bounds
+                .map(|float_bounds| float_bounds.contains(*x))
+                .unwrap_or(true),
+            _ => {
+                min.map(|min| measured >= min).unwrap_or(true)
+                    && max.map(|max| measured <= max).unwrap_or(true)
+            }
+        };
+        assert!(
+            compatible && in_range,
+            ".and / .within: {:?} does not satisfy {:?}",
+            value,
+            self
+        );
+        assert!(
+            self.regexp.is_none(),
+            ".and / .within: can't statically check {:?} against .regexp",
+            value
+        );
+        assert!(
+            !self
+                .excluded
+                .as_ref()
+                .map(|excluded| excluded.contains(value))
+                .unwrap_or(false),
+            ".and / .within: {:?} is excluded by .ne",
+            value
+        );
+        if let (Some(bits), FixedValue::Uint(x)) = (&self.bits, value) {
+            let mask = bits
+                .iter()
+                .fold(0u64, |mask, (_name, bit)| mask | (1u64 << bit));
+            assert_eq!(
+                *x as u64 & !mask,
+                0,
+                ".and / .within: {} sets undeclared bits",
+                x
+            );
+        }
+        fixed
+    }
+}
+
+fn primitive_range_or_none(base: &ConceptualRustType) -> Option<(Option<i128>, Option<i128>)> {
+    match base {
+        ConceptualRustType::Primitive(p) => primitive_range(*p),
+        _ => None,
+    }
+}
+
+// the range of values implied by an integer primitive e.g. U8 is 0..=255
+fn primitive_range(primitive: Primitive) -> Option<(Option<i128>, Option<i128>)> {
+    let (min, max) = match primitive {
+        Primitive::U8 => (u8::MIN as i128, u8::MAX as i128),
+        Primitive::U16 => (u16::MIN as i128, u16::MAX as i128),
+        Primitive::U32 => (u32::MIN as i128, u32::MAX as i128),
+        Primitive::U64 => (u64::MIN as i128, u64::MAX as i128),
+        Primitive::I8 => (i8::MIN as i128, i8::MAX as i128),
+        Primitive::I16 => (i16::MIN as i128, i16::MAX as i128),
+        Primitive::I32 => (i32::MIN as i128, i32::MAX as i128),
+        Primitive::I64 => (i64::MIN as i128, i64::MAX as i128),
+        Primitive::N64 => (-(u64::MAX as i128) - 1, -1),
+        Primitive::Bool
+        | Primitive::F16
+        | Primitive::F32
+        | Primitive::F64
+        | Primitive::Float
+        | Primitive::Bytes
+        | Primitive::Str => return None,
+    };
+    Some((Some(min), Some(max)))
+}
+
+// .ne values are compared directly against the value so they must be of the same type
+fn check_excluded_value(base: &ConceptualRustType, value: &FixedValue) {
+    let compatible = match (base, value) {
+        (ConceptualRustType::Primitive(Primitive::Str), FixedValue::Text(_))
+        | (ConceptualRustType::Primitive(Primitive::Bytes), FixedValue::Bytes(_)) => true,
+        (ConceptualRustType::Primitive(p), FixedValue::Float(_)) => p.is_float(),
+        (ConceptualRustType::Primitive(p), FixedValue::Uint(x)) => {
+            in_primitive_range(*p, *x as i128)
+        }
+        (ConceptualRustType::Primitive(p), FixedValue::Nint(x)) => {
+            in_primitive_range(*p, *x as i128)
+        }
+        _ => false,
+    };
+    assert!(
+        compatible,
+        ".ne {:?} is not a valid value of the type it excludes it from: {:?}",
+        value, base
+    );
+}
+
+fn in_primitive_range(primitive: Primitive, x: i128) -> bool {
+    match primitive_range(primitive) {
+        Some((min, max)) => {
+            min.map(|min| x >= min).unwrap_or(true) && max.map(|max| x <= max).unwrap_or(true)
+        }
+        None => false,
+    }
+}
+
+fn intersect_ranges(
+    lhs: Option<(Option<i128>, Option<i128>)>,
+    rhs: Option<(Option<i128>, Option<i128>)>,
+) -> (Option<i128>, Option<i128>) {
+    let (lhs_min, lhs_max) = lhs.unwrap_or((None, None));
+    let (rhs_min, rhs_max) = rhs.unwrap_or((None, None));
+    let min = match (lhs_min, rhs_min) {
+        (Some(l), Some(r)) => Some(std::cmp::max(l, r)),
+        (l, r) => l.or(r),
+    };
+    let max = match (lhs_max, rhs_max) {
+        (Some(l), Some(r)) => Some(std::cmp::min(l, r)),
+        (l, r) => l.or(r),
+    };
+    if let (Some(min), Some(max)) = (min, max) {
+        assert!(min <= max, ".and / .within resulted in an empty range");
+    }
+    (min, max)
+}
+
+// only bits declared on both sides remain
+fn intersect_bits(lhs: Option<Vec<(String, u64)>>, rhs: Vec<(String, u64)>) -> Vec<(String, u64)> {
+    match lhs {
+        Some(lhs) => lhs.into_iter().filter(|bit| rhs.contains(bit)).collect(),
+        None => rhs,
+    }
+}
+
+fn type1_to_intersected(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type1: &Type1,
+    cli: &Cli,
+) -> IntersectedType {
+    let base = type2_to_intersected(types, parent_visitor, &type1.type2, cli);
+    match &type1.operator {
+        Some(operator) if is_intersection_operator(Some(operator)) => base.intersect(
+            type2_to_intersected(types, parent_visitor, &operator.type2, cli),
+        ),
+        Some(operator) => base.apply_control(parse_control_operator(
+            types,
+            parent_visitor,
+            &type1.type2,
+            operator,
+            cli,
+        )),
+        None => base,
+    }
+}
+
+fn type2_to_intersected(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type2: &Type2,
+    cli: &Cli,
+) -> IntersectedType {
+    match type2 {
+        Type2::ParenthesizedType { pt, .. } => {
+            assert_eq!(
+                pt.type_choices.len(),
+                1,
+                ".and / .within not supported on type choices"
+            );
+            type1_to_intersected(types, parent_visitor, &pt.type_choices[0].type1, cli)
+        }
+        Type2::Typename {
+            ident,
+            generic_args: None,
+            ..
+        } => {
+            let cddl_ident = CDDLIdent::new(ident.to_string());
+            if let Some(p) = ident_to_primitive(&cddl_ident) {
+                return IntersectedType::new(ConceptualRustType::Primitive(p));
+            }
+            // user-defined types that were constrained themselves e.g. foo = uint .le 5
+            match types.rust_struct(&RustIdent::new(cddl_ident.clone())) {
+                Some(rust_struct) => match rust_struct.variant() {
+                    RustStructType::Wrapper {
+                        wrapped,
+                        min_max,
+                        regexp,
+                        bits,
+                        excluded,
+                        fixed_len,
+                    } if rust_struct.tag().is_none() => IntersectedType {
+                        base: wrapped.conceptual_type.clone(),
+                        min_max: fixed_len
+                            .map(|len| (Some(len as i128), Some(len as i128)))
+                            .or(*min_max),
+                        float_bounds: wrapped.config.float_bounds,
+                        regexp: regexp.clone(),
+                        bits: bits.clone(),
+                        excluded: excluded.clone(),
+                    },
+                    _ => panic!(
+                        ".and / .within only supported on primitives / values. Found {}",
+                        ident
+                    ),
+                },
+                None => {
+                    let resolved = types.new_type(&cddl_ident, cli).resolve_aliases();
+                    assert!(
+                        resolved.encodings.is_empty(),
+                        ".and / .within not supported on tagged / encoded types. Found {}",
+                        ident
+                    );
+                    let mut intersected = IntersectedType::new(resolved.conceptual_type);
+                    intersected.min_max = resolved.config.bounds;
+                    intersected.float_bounds = resolved.config.float_bounds;
+                    intersected
+                }
+            }
+        }
+        Type2::UintValue { value, .. } => {
+            IntersectedType::new(ConceptualRustType::Fixed(FixedValue::Uint(*value)))
+        }
+        Type2::IntValue { value, .. } => {
+            IntersectedType::new(ConceptualRustType::Fixed(FixedValue::Nint(*value)))
+        }
+        Type2::FloatValue { value, .. } => {
+            IntersectedType::new(ConceptualRustType::Fixed(FixedValue::Float(*value)))
+        }
+        Type2::TextValue { value, .. } => IntersectedType::new(ConceptualRustType::Fixed(
+            FixedValue::Text(value.to_string()),
+        )),
+        _ => panic!(
+            ".and / .within only supported on primitives / values. Found {:?}",
+            type2
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn register_ranged_type(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type_name: &RustIdent,
+    outer_tag: Option<usize>,
+    rule_metadata: RuleMetadata,
+    primitive: Primitive,
+    min_max: (Option<i128>, Option<i128>),
+    cli: &Cli,
+) {
+    // when declared top-level we make a new type as the default behavior like before
+    let mut ranged_type = range_to_primitive(min_max.0, min_max.1, primitive);
+    if ranged_type.config.bounds.is_some() || rule_metadata.newtype.is_some() {
+        // without bounds since passed in other param
+        ranged_type.config.bounds = None;
+        // has non-rust-primitive matching bounds
+        types.register_rust_struct(
+            parent_visitor,
+            RustStruct::new_wrapper(
+                type_name.clone(),
+                outer_tag,
+                Some(&rule_metadata),
+                ranged_type,
+                Some(min_max),
+            ),
+            cli,
+        );
+    } else {
+        // matches to known rust type e.g. u32, i16, etc so just make an alias
+        types.register_type_alias(
+            type_name.clone(),
+            AliasInfo::new_from_metadata(ranged_type.tag_if(outer_tag), rule_metadata),
+        );
+    }
+}
+
+// float bounds are always checked at runtime so unlike with ints there's no primitive to alias
+#[allow(clippy::too_many_arguments)]
+fn register_float_ranged_type(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type_name: &RustIdent,
+    outer_tag: Option<usize>,
+    rule_metadata: RuleMetadata,
+    primitive: Primitive,
+    float_bounds: FloatBounds,
+    cli: &Cli,
+) {
+    types.register_rust_struct(
+        parent_visitor,
+        RustStruct::new_wrapper(
+            type_name.clone(),
+            outer_tag,
+            Some(&rule_metadata),
+            RustType::from(ConceptualRustType::Primitive(primitive))
+                .with_float_bounds(float_bounds),
+            None,
+        ),
+        cli,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn register_intersected_type(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type_name: &RustIdent,
+    outer_tag: Option<usize>,
+    rule_metadata: RuleMetadata,
+    intersected: IntersectedType,
+    cli: &Cli,
+) {
+    match (
+        intersected.base,
+        intersected.regexp,
+        intersected.bits,
+        intersected.excluded,
+    ) {
+        (ConceptualRustType::Primitive(p), None, None, None)
+            if intersected.float_bounds.is_some() =>
+        {
+            register_float_ranged_type(
+                types,
+                parent_visitor,
+                type_name,
+                outer_tag,
+                rule_metadata,
+                p,
+                intersected.float_bounds.unwrap(),
+                cli,
+            )
+        }
+        // nothing that needs runtime checks outside of ranges so treat it as if it was declared with one
+        (ConceptualRustType::Primitive(p), None, None, None) => match intersected.min_max {
+            Some(min_max) => register_ranged_type(
+                types,
+                parent_visitor,
+                type_name,
+                outer_tag,
+                rule_metadata,
+                p,
+                min_max,
+                cli,
+            ),
+            None => types.register_type_alias(
+                type_name.clone(),
+                AliasInfo::new_from_metadata(
+                    RustType::from(ConceptualRustType::Primitive(p)).tag_if(outer_tag),
+                    rule_metadata,
+                ),
+            ),
+        },
+        // can't be expressed as a single control operator so we check all of them at runtime
+        (ConceptualRustType::Primitive(p), regexp, bits, excluded) => types.register_rust_struct(
+            parent_visitor,
+            RustStruct::new_constrained_wrapper(
+                type_name.clone(),
+                outer_tag,
+                Some(&rule_metadata),
+                match intersected.float_bounds {
+                    Some(float_bounds) => RustType::from(ConceptualRustType::Primitive(p))
+                        .with_float_bounds(float_bounds),
+                    None => ConceptualRustType::Primitive(p).into(),
+                },
+                intersected.min_max,
+                regexp,
+                bits,
+                excluded,
+            ),
+            cli,
+        ),
+        (base, _, _, _) => types.register_type_alias(
+            type_name.clone(),
+            AliasInfo::new_from_metadata(RustType::from(base).tag_if(outer_tag), rule_metadata),
+        ),
+    }
+}
+
 fn range_to_primitive(low: Option<i128>, high: Option<i128>, primitive: Primitive) -> RustType {
     match (low, high) {
         (Some(l), Some(h)) if l == u8::MIN as i128 && h == u8::MAX as i128 => {
@@ -477,6 +1492,23 @@ fn parse_type(
     if rule_metadata.used_as_key {
         types.mark_used_as_key(type_name.clone());
     }
+    if is_intersection_operator(type1.operator.as_ref()) {
+        assert!(
+            generic_params.is_none(),
+            "Generics combined with .and / .within not supported"
+        );
+        let intersected = type1_to_intersected(types, parent_visitor, type1, cli);
+        register_intersected_type(
+            types,
+            parent_visitor,
+            type_name,
+            outer_tag,
+            rule_metadata,
+            intersected,
+            cli,
+        );
+        return;
+    }
     match &type1.type2 {
         Type2::Typename {
             ident,
@@ -510,53 +1542,113 @@ fn parse_type(
                             "Generics combined with range specifiers not supported"
                         );
                         match control {
-                            ControlOperator::Range(min_max) => {
-                                // when declared top-level we make a new type as the default behavior like before
-                                let mut ranged_type = range_to_primitive(
-                                    min_max.0,
-                                    min_max.1,
+                            ControlOperator::Range(min_max) => register_ranged_type(
+                                types,
+                                parent_visitor,
+                                type_name,
+                                outer_tag,
+                                rule_metadata,
+                                ident_to_primitive(&cddl_ident).unwrap(),
+                                min_max,
+                                cli,
+                            ),
+                            ControlOperator::FloatRange(float_bounds) => {
+                                register_float_ranged_type(
+                                    types,
+                                    parent_visitor,
+                                    type_name,
+                                    outer_tag,
+                                    rule_metadata,
                                     ident_to_primitive(&cddl_ident).unwrap(),
-                                );
-                                if ranged_type.config.bounds.is_some()
-                                    || rule_metadata.newtype.is_some()
-                                {
-                                    // without bounds since passed in other param
-                                    ranged_type.config.bounds = None;
-                                    // has non-rust-primitive matching bounds
-                                    types.register_rust_struct(
-                                        parent_visitor,
-                                        RustStruct::new_wrapper(
-                                            type_name.clone(),
-                                            outer_tag,
-                                            Some(&rule_metadata),
-                                            ranged_type,
-                                            Some(min_max),
-                                        ),
-                                        cli,
-                                    );
-                                } else {
-                                    // matches to known rust type e.g. u32, i16, etc so just make an alias
+                                    float_bounds,
+                                    cli,
+                                )
+                            }
+                            ControlOperator::CBOR(ty) => match ident_to_primitive(&cddl_ident) {
+                                Some(Primitive::Bytes) => {
                                     types.register_type_alias(
                                         type_name.clone(),
                                         AliasInfo::new_from_metadata(
-                                            ranged_type.tag_if(outer_tag),
+                                            ty.as_bytes().tag_if(outer_tag),
                                             rule_metadata,
                                         ),
                                     );
                                 }
-                            }
-                            ControlOperator::CBOR(ty) => match ident_to_primitive(&cddl_ident) {
+                                _ => panic!(".cbor is only allowed on bytes as per CDDL spec"),
+                            },
+                            ControlOperator::CBORSeq(ty) => match ident_to_primitive(&cddl_ident) {
                                 Some(Primitive::Bytes) => {
                                     types.register_type_alias(
                                         type_name.clone(),
                                         AliasInfo::new_from_metadata(
-                                            ty.as_bytes().tag_if(outer_tag),
+                                            ty.as_cbor_seq().tag_if(outer_tag),
                                             rule_metadata,
                                         ),
                                     );
                                 }
-                                _ => panic!(".cbor is only allowed on bytes as per CDDL spec"),
+                                _ => panic!(".cborseq is only allowed on bytes as per CDDL spec"),
                             },
+                            ControlOperator::Regexp(regexp) => {
+                                match ident_to_primitive(&cddl_ident) {
+                                    Some(Primitive::Str) => {
+                                        types.register_rust_struct(
+                                            parent_visitor,
+                                            RustStruct::new_regexp_wrapper(
+                                                type_name.clone(),
+                                                outer_tag,
+                                                Some(&rule_metadata),
+                                                regexp,
+                                            ),
+                                            cli,
+                                        );
+                                    }
+                                    _ => panic!(".regexp is only allowed on text as per CDDL spec"),
+                                }
+                            }
+                            ControlOperator::Bits(bits) => {
+                                let wrapped = match ident_to_primitive(&cddl_ident) {
+                                    Some(Primitive::U64) => {
+                                        assert!(
+                                            bits.iter().all(|(_name, bit)| *bit < u64::BITS as u64),
+                                            "uint .bits only supports bit positions that fit in a u64"
+                                        );
+                                        Primitive::U64
+                                    }
+                                    Some(Primitive::Bytes) => Primitive::Bytes,
+                                    _ => panic!(
+                                        ".bits is only allowed on uint or bytes as per CDDL spec"
+                                    ),
+                                };
+                                types.register_rust_struct(
+                                    parent_visitor,
+                                    RustStruct::new_bits_wrapper(
+                                        type_name.clone(),
+                                        outer_tag,
+                                        Some(&rule_metadata),
+                                        ConceptualRustType::Primitive(wrapped).into(),
+                                        bits,
+                                    ),
+                                    cli,
+                                );
+                            }
+                            ControlOperator::NotEqual(value) => {
+                                let base = match ident_to_primitive(&cddl_ident) {
+                                    Some(Primitive::Bool) | None => {
+                                        panic!(".ne is only supported on numbers, text and bytes")
+                                    }
+                                    Some(p) => p,
+                                };
+                                register_intersected_type(
+                                    types,
+                                    parent_visitor,
+                                    type_name,
+                                    outer_tag,
+                                    rule_metadata,
+                                    IntersectedType::new(ConceptualRustType::Primitive(base))
+                                        .apply_control(ControlOperator::NotEqual(value)),
+                                    cli,
+                                );
+                            }
                             ControlOperator::Default(default_value) => {
                                 let default_type =
                                     rust_type_from_type2(types, parent_visitor, &type1.type2, cli)
@@ -571,21 +1663,58 @@ fn parse_type(
                     }
                     None => {
                         let mut concrete_type = types.new_type(&cddl_ident, cli).tag_if(outer_tag);
-                        if let ConceptualRustType::Alias(_ident, ty) = concrete_type.conceptual_type
+                        // reserved aliases (e.g. biguint) are kept for their (de)serialization
+                        if let ConceptualRustType::Alias(AliasIdent::Rust(_), ty) =
+                            concrete_type.conceptual_type
                         {
                             concrete_type.conceptual_type = *ty;
                         };
-                        match &generic_params {
-                            Some(_params) => {
-                                // this should be the only situation where you need this as otherwise the params would be unbound
-                                todo!("generics on defined types e.g. foo<T, U> = [T, U], bar<V> = foo<V, uint>");
-                                // TODO: maybe you could do this by resolving it here then storing the resolved one as GenericDef
-                            }
+                        match generic_params {
+                            Some(params) => match generic_args {
+                                Some(arg) => {
+                                    // This is for generics defined via other generics e.g.
+                                    // foo<T, U> = [T, U], bar<V> = foo<V, uint>
+                                    parse_generic_rule(
+                                        types,
+                                        parent_visitor,
+                                        &RustIdent::new(cddl_ident.clone()),
+                                        cli,
+                                    );
+                                    let generic_args = arg
+                                        .args
+                                        .iter()
+                                        .map(|a| {
+                                            rust_type_from_type1(types, parent_visitor, &a.arg, cli)
+                                        })
+                                        .collect();
+                                    types.register_generic_def(GenericDef::new_instance(
+                                        params,
+                                        GenericInstance::new(
+                                            type_name.clone(),
+                                            RustIdent::new(cddl_ident.clone()),
+                                            generic_args,
+                                        ),
+                                    ))
+                                }
+                                // e.g. foo<T> = T or foo<T> = uint (unused param)
+                                None => types.register_generic_def(GenericDef::new_alias(
+                                    type_name.clone(),
+                                    params,
+                                    concrete_type,
+                                    rule_metadata,
+                                )),
+                            },
                             None => {
                                 match generic_args {
                                     Some(arg) => {
                                         // This is for named generic instances such as:
                                         // foo = bar<text>
+                                        parse_generic_rule(
+                                            types,
+                                            parent_visitor,
+                                            &RustIdent::new(cddl_ident.clone()),
+                                            cli,
+                                        );
                                         let generic_args = arg
                                             .args
                                             .iter()
@@ -662,11 +1791,24 @@ fn parse_type(
                 cli,
             );
         }
-        Type2::TaggedData { tag, t, .. } => {
+        Type2::TaggedData { tag: None, t, .. } => {
+            if outer_tag.is_some() {
+                panic!("doubly nested tags are not supported");
+            }
+            types.register_type_alias(
+                type_name.clone(),
+                AliasInfo::new_from_metadata(
+                    any_tag_rust_type(types, parent_visitor, t, cli),
+                    rule_metadata,
+                ),
+            );
+        }
+        Type2::TaggedData {
+            tag: Some(tag), t, ..
+        } => {
             if outer_tag.is_some() {
                 panic!("doubly nested tags are not supported");
             }
-            let tag_unwrap = tag.expect("not sure what empty tag here would mean - unsupported");
             match t.type_choices.len() {
                 1 => {
                     let inner_type = &t.type_choices.first().unwrap();
@@ -675,7 +1817,7 @@ fn parse_type(
                         parent_visitor,
                         type_name,
                         inner_type,
-                        Some(tag_unwrap),
+                        Some(*tag),
                         generic_params,
                         cli,
                     );
@@ -686,7 +1828,7 @@ fn parse_type(
                         parent_visitor,
                         type_name,
                         &t.type_choices,
-                        *tag,
+                        Some(*tag),
                         generic_params,
                         cli,
                     );
@@ -706,6 +1848,18 @@ fn parse_type(
                 Some(ControlOperator::Range(min_max)) => {
                     range_to_primitive(min_max.0, min_max.1, Primitive::I64)
                 }
+                Some(ControlOperator::FloatRange(float_bounds)) => {
+                    return register_float_ranged_type(
+                        types,
+                        parent_visitor,
+                        type_name,
+                        outer_tag,
+                        rule_metadata,
+                        Primitive::Float,
+                        float_bounds,
+                        cli,
+                    );
+                }
                 _ => fallback_type.into(),
             };
             types.register_type_alias(
@@ -725,6 +1879,18 @@ fn parse_type(
                 Some(ControlOperator::Range(min_max)) => {
                     range_to_primitive(min_max.0, min_max.1, Primitive::U64)
                 }
+                Some(ControlOperator::FloatRange(float_bounds)) => {
+                    return register_float_ranged_type(
+                        types,
+                        parent_visitor,
+                        type_name,
+                        outer_tag,
+                        rule_metadata,
+                        Primitive::Float,
+                        float_bounds,
+                        cli,
+                    );
+                }
                 _ => fallback_type.into(),
             };
             types.register_type_alias(
@@ -751,10 +1917,19 @@ fn parse_type(
                 .operator
                 .as_ref()
                 .map(|op| parse_control_operator(types, parent_visitor, &type1.type2, op, cli));
-            // We end up here with ranges like foo = 0..5 which is why we're not just reporting a fixed value
+            // We end up here with ranges like foo = 0.5..1.5 which is why we're not just reporting a fixed value
             let base_type = match control {
-                Some(ControlOperator::Range(min_max)) => {
-                    range_to_primitive(min_max.0, min_max.1, Primitive::F64)
+                Some(ControlOperator::FloatRange(float_bounds)) => {
+                    return register_float_ranged_type(
+                        types,
+                        parent_visitor,
+                        type_name,
+                        outer_tag,
+                        rule_metadata,
+                        Primitive::Float,
+                        float_bounds,
+                        cli,
+                    );
                 }
                 _ => fallback_type.into(),
             };
@@ -763,6 +1938,96 @@ fn parse_type(
                 AliasInfo::new_from_metadata(base_type.tag_if(outer_tag), rule_metadata),
             );
         }
+        Type2::ChoiceFromInlineGroup { .. } | Type2::ChoiceFromGroup { .. } => {
+            // foo = &(a: 0, b: 1) is just another way of writing the type choice foo = 0 / 1
+            parse_type_choices(
+                types,
+                parent_visitor,
+                type_name,
+                std::slice::from_ref(type_choice),
+                outer_tag,
+                generic_params,
+                cli,
+            );
+        }
+        Type2::DataMajorType { mt, constraint, .. } => {
+            match (*mt, constraint) {
+                // #4 / #5 are the same as [* any] / { * any => any }
+                (4, None) => {
+                    let any_type = types.new_type(&CDDLIdent::new("any"), cli);
+                    types.register_rust_struct(
+                        parent_visitor,
+                        RustStruct::new_array(
+                            type_name.clone(),
+                            outer_tag,
+                            Some(&rule_metadata),
+                            any_type,
+                        ),
+                        cli,
+                    );
+                }
+                (5, None) => {
+                    let any_type = types.new_type(&CDDLIdent::new("any"), cli);
+                    types.register_rust_struct(
+                        parent_visitor,
+                        RustStruct::new_table(
+                            type_name.clone(),
+                            outer_tag,
+                            Some(&rule_metadata),
+                            any_type.clone(),
+                            any_type,
+                        ),
+                        cli,
+                    );
+                }
+                // exact lengths are checked by a wrapper like with bytes .size 4 or [4*4 any]
+                (2..=5, Some(len)) => {
+                    let len = *len as u64;
+                    let major_type = major_type_to_rust_type(types, *mt, Some(len), cli);
+                    let rust_struct = match major_type.conceptual_type {
+                        ConceptualRustType::Array(element_type) if cli.fixed_size_arrays => {
+                            RustStruct::new_fixed_size_array_wrapper(
+                                type_name.clone(),
+                                outer_tag,
+                                Some(&rule_metadata),
+                                *element_type,
+                                usize::try_from(len).unwrap_or_else(|_| {
+                                    panic!("{}: invalid fixed array length {}", type_name, len)
+                                }),
+                            )
+                        }
+                        conceptual_type => RustStruct::new_wrapper(
+                            type_name.clone(),
+                            outer_tag,
+                            Some(&rule_metadata),
+                            conceptual_type.into(),
+                            major_type.config.bounds,
+                        ),
+                    };
+                    types.register_rust_struct(parent_visitor, rust_struct, cli);
+                }
+                _ => {
+                    let major_type =
+                        major_type_to_rust_type(types, *mt, constraint.map(|c| c as u64), cli);
+                    types.register_type_alias(
+                        type_name.clone(),
+                        AliasInfo::new_from_metadata(major_type.tag_if(outer_tag), rule_metadata),
+                    );
+                }
+            }
+        }
+        Type2::Any { .. } => {
+            // # is the same as the prelude's any
+            types.register_type_alias(
+                type_name.clone(),
+                AliasInfo::new_from_metadata(
+                    types
+                        .new_type(&CDDLIdent::new("any"), cli)
+                        .tag_if(outer_tag),
+                    rule_metadata,
+                ),
+            );
+        }
         x => {
             panic!("\nignored typename {} -> {:?}\n", type_name, x);
         }
@@ -779,21 +2044,37 @@ pub fn create_variants_from_type_choices(
     let mut variant_names_used = BTreeMap::<String, u32>::new();
     type_choices
         .iter()
-        .map(|choice| {
-            let rust_type = rust_type_from_type1(types, parent_visitor, &choice.type1, cli);
-            let rule_metadata = RuleMetadata::from(choice.type1.comments_after_type.as_ref());
-            let base_name = match &rule_metadata {
-                RuleMetadata {
-                    name: Some(name), ..
-                } => convert_to_camel_case(name),
-                _ => rust_type.for_variant().to_string(),
-            };
+        .flat_map(|choice| match &choice.type1 {
+            // a choice from a group e.g. &(red: 0, green: 1) contributes one variant per value
+            Type1 {
+                type2: type2 @ (Type2::ChoiceFromInlineGroup { .. } | Type2::ChoiceFromGroup { .. }),
+                operator: None,
+                ..
+            } => choice_from_group_variants(types, type2)
+                .into_iter()
+                .map(|(name, rust_type)| (name, rust_type, None))
+                .collect(),
+            _ => {
+                let rust_type = rust_type_from_type1(types, parent_visitor, &choice.type1, cli);
+                let rule_metadata = RuleMetadata::from(choice.type1.comments_after_type.as_ref());
+                let base_name = match &rule_metadata {
+                    RuleMetadata {
+                        name: Some(name), ..
+                    } => convert_to_camel_case(name),
+                    _ => rust_type.for_variant().to_string(),
+                };
+                vec![(base_name, rust_type, rule_metadata.comment)]
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|(base_name, rust_type, comment)| {
             let variant_name = append_number_if_duplicate(&mut variant_names_used, base_name);
             EnumVariant::new(
                 VariantIdent::new_custom(variant_name),
                 rust_type,
                 false,
-                rule_metadata.comment.clone(),
+                comment,
             )
         })
         .collect()
@@ -803,7 +2084,8 @@ pub fn create_variants_from_type_choices(
 /// instead of treating all groups as structs.
 enum GroupParsingType {
     /// Fields are the same e.g. field: [* uint]
-    HomogenousArray(RustType),
+    /// Contains the element type and the bounds on the element count if any e.g. field: [1*3 uint]
+    HomogenousArray(RustType, Option<(Option<i128>, Option<i128>)>),
     /// Pairs are the same e.g. field:{ *text => uint }
     HomogenousMap(RustType, RustType),
     /// Fields are different - needs new struct created e.g. field: [a: uint, b: bstr]
@@ -838,18 +2120,12 @@ fn parse_group_type<'a>(
                     ),
                     _ => panic!("UNSUPPORTED_ARRAY_ELEMENT<{:?}>", entry),
                 };
-                let bounds = occur.as_ref().map(|o| match o.occur {
-                    Occur::ZeroOrMore { .. } => (None, None),
-                    Occur::Exact { lower, upper, .. } => (
-                        lower.filter(|l| *l != 0).map(|i| i as i128),
-                        upper.map(|i| i as i128),
-                    ),
-                    Occur::Optional { .. } => (None, Some(1)),
-                    Occur::OneOrMore { .. } => (Some(1), None),
-                });
+                let bounds = occur.as_ref().map(|o| occurrence_bounds(&o.occur));
                 match bounds {
                     // no bounds
-                    Some((None, None)) => return GroupParsingType::HomogenousArray(elem_type),
+                    Some((None, None)) => {
+                        return GroupParsingType::HomogenousArray(elem_type, None)
+                    }
                     None | Some((Some(1), Some(1))) => {
                         // if the only element is a basic group we don't need to create a new group but can just
                         // change how it is (de)serialized
@@ -862,8 +2138,9 @@ fn parse_group_type<'a>(
                         }
                         // fall-through generic case. this is a general 1-element struct that needs creating
                     }
+                    // these bounds are on the number of elements, not on the elements themselves
                     Some(bounds) => {
-                        return GroupParsingType::HomogenousArray(elem_type.with_bounds(bounds))
+                        return GroupParsingType::HomogenousArray(elem_type, Some(bounds))
                     }
                 }
             }
@@ -873,13 +2150,17 @@ fn parse_group_type<'a>(
             // struct: { x: int, y: int }, etc
             // table: { * int => tstr }, etc
             // this assumes that all maps representing tables are homogenous
-            // and contain no other fields. I am not sure if this is a guarantee in
-            // cbor but I would hope that the cddl specs we are using follow this.
+            // and contain no other fields. Structs can however have a catch-all
+            // table entry for any other keys e.g. { 1: uint, * uint => any }
+            // which is handled in parse_record_from_group_choice()
             if group_choice.group_entries.len() == 1 {
                 match group_choice.group_entries.first() {
                     Some((GroupEntry::ValueMemberKey { ge, .. }, _)) => {
                         match &ge.member_key {
-                            Some(MemberKey::Type1 { t1, .. }) => {
+                            // literal keys e.g. { -1 => bstr } are a 1-element struct below
+                            Some(MemberKey::Type1 { t1, .. })
+                                if type2_to_map_key(&t1.type2).is_none() =>
+                            {
                                 // TODO: Do we need to handle cuts for what we're doing?
                                 // Does the range control operator matter?
                                 let key_type = rust_type_from_type1(types, parent_visitor, t1, cli);
@@ -887,7 +2168,7 @@ fn parse_group_type<'a>(
                                     rust_type(types, parent_visitor, &ge.entry_type, cli);
                                 return GroupParsingType::HomogenousMap(key_type, value_type);
                             }
-                            Some(MemberKey::Value { .. }) => {
+                            Some(MemberKey::Value { .. } | MemberKey::Type1 { .. }) => {
                                 // has a fixed value - this is just a 1-element struct
                             }
                             _ => panic!("unsupported table map key (1): {:?}", ge),
@@ -909,6 +2190,11 @@ fn parse_group_type<'a>(
 fn type2_is_null(t2: &Type2) -> bool {
     match t2 {
         Type2::Typename { ident, .. } => ident.ident == "null" || ident.ident == "nil",
+        Type2::DataMajorType {
+            mt: 7,
+            constraint: Some(22),
+            ..
+        } => true,
         _ => false,
     }
 }
@@ -917,6 +2203,7 @@ fn type_to_field_name(t: &Type) -> Option<String> {
     let type2_to_field_name = |t2: &Type2| match t2 {
         Type2::Typename { ident, .. } => Some(ident.to_string()),
         Type2::TextValue { value, .. } => Some(value.to_string()),
+        Type2::Unwrap { ident, .. } => Some(ident.to_string()),
         Type2::Array { group, .. } => match group.group_choices.len() {
             1 => {
                 let entries = &group.group_choices.first().unwrap().group_entries;
@@ -1005,13 +2292,13 @@ fn group_entry_to_field_name(
                         RuleMetadata {
                             name: Some(name), ..
                         } => name,
-                        _ => format!("key_{value}"),
+                        _ => map_key_to_field_name(&group_entry_to_key(entry).unwrap()),
                     }
                 }
                 MemberKey::Bareword { ident, .. } => ident.to_string(),
-                MemberKey::Type1 { t1, .. } => match t1.type2 {
-                    Type2::UintValue { value, .. } => format!("key_{value}"),
-                    _ => panic!(
+                MemberKey::Type1 { t1, .. } => match type2_to_map_key(&t1.type2) {
+                    Some(key) => map_key_to_field_name(&key),
+                    None => panic!(
                         "Encountered Type1 member key in multi-field map - not supported: {:?}",
                         entry
                     ),
@@ -1056,6 +2343,20 @@ fn group_entry_to_field_name(
     append_number_if_duplicate(already_generated, field_name)
 }
 
+// e.g. key_1 for 1, key_neg_1 for -1, key_bytes_00ff for h'00ff' and key_tag42_1 for #6.42(1)
+fn map_key_to_field_name(key: &MapKey) -> String {
+    let value = match &key.value {
+        FixedValue::Nint(x) => format!("neg_{}", x.unsigned_abs()),
+        FixedValue::Text(s) => s.clone(),
+        FixedValue::Bytes(bytes) => format!("bytes_{}", to_hex(bytes)),
+        other => other.to_primitive_str_assign(),
+    };
+    match key.tag {
+        Some(tag) => format!("key_tag{tag}_{value}"),
+        None => format!("key_{value}"),
+    }
+}
+
 // Only returns Some(String) if there was an explicit field name provided, otherwise None.
 // If you need to try and make one using the type/etc, then try group_entry_to_field_name()
 // Also does not do any CamelCase or snake_case formatting.
@@ -1092,47 +2393,255 @@ fn group_entry_rule_metadata(entry: &GroupEntry, optional_comma: &OptionalComma)
             group, group
         ),
     };
-    let combined_comments =
-        combine_comments(entry_trailing_comments, &optional_comma.trailing_comments);
-    metadata_from_comments(&combined_comments.unwrap_or_default())
+    let combined_comments =
+        combine_comments(entry_trailing_comments, &optional_comma.trailing_comments);
+    metadata_from_comments(&combined_comments.unwrap_or_default())
+}
+
+fn rust_type_from_type1(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type1: &Type1,
+    cli: &Cli,
+) -> RustType {
+    if is_intersection_operator(type1.operator.as_ref()) {
+        let intersected = type1_to_intersected(types, parent_visitor, type1, cli);
+        assert!(
+            intersected.regexp.is_none() && intersected.bits.is_none(),
+            ".and / .within resulting in .regexp or .bits must be declared as its own type e.g. `foo = (tstr .regexp \"...\") .and (tstr .size 8)`"
+        );
+        if intersected.excluded.is_some() {
+            return register_inline_intersected_type(
+                types,
+                parent_visitor,
+                type1,
+                intersected,
+                cli,
+            );
+        }
+        return match (intersected.base, intersected.min_max) {
+            (ConceptualRustType::Primitive(p), Some((low, high))) => {
+                range_to_primitive(low, high, p)
+            }
+            (base, _) => base.into(),
+        };
+    }
+    let control = type1
+        .operator
+        .as_ref()
+        .map(|op| parse_control_operator(types, parent_visitor, &type1.type2, op, cli));
+    let base_type = rust_type_from_type2(types, parent_visitor, &type1.type2, cli);
+    // println!("type1: {:#?}", type1);
+    match control {
+        Some(ControlOperator::CBOR(ty)) => {
+            assert!(matches!(
+                base_type.conceptual_type.resolve_alias_shallow(),
+                ConceptualRustType::Primitive(Primitive::Bytes)
+            ));
+            ty.as_bytes()
+        }
+        Some(ControlOperator::CBORSeq(ty)) => {
+            assert!(matches!(
+                base_type.conceptual_type.resolve_alias_shallow(),
+                ConceptualRustType::Primitive(Primitive::Bytes)
+            ));
+            ty.as_cbor_seq()
+        }
+        Some(ControlOperator::Range((low, high))) => match &type1.type2 {
+            Type2::Typename { ident, .. } => {
+                match ident_to_primitive(&CDDLIdent::new(ident.to_string())) {
+                    Some(p) => range_to_primitive(low, high, p),
+                    None => base_type.with_bounds((low, high)),
+                }
+            }
+            // the base value will be a constant due to incomplete parsing earlier for explicit ranges
+            // e.g. foo = 0..255
+            Type2::IntValue { .. } => range_to_primitive(low, high, Primitive::I64),
+            Type2::UintValue { .. } => range_to_primitive(low, high, Primitive::U64),
+            _ => base_type.with_bounds((low, high)),
+        },
+        Some(ControlOperator::FloatRange(float_bounds)) => match &type1.type2 {
+            Type2::Typename { .. } => base_type.with_float_bounds(float_bounds),
+            // the base value will be a constant for explicit ranges e.g. foo = 0.5..1.5
+            _ => RustType::from(ConceptualRustType::Primitive(Primitive::Float))
+                .with_float_bounds(float_bounds),
+        },
+        Some(ControlOperator::Default(default_value)) => base_type.default(default_value),
+        Some(ControlOperator::Bits(_)) => panic!(
+            ".bits must be declared as its own type e.g. `foo = uint .bits &(a: 0, b: 1)` instead of being used inline"
+        ),
+        Some(ControlOperator::NotEqual(value)) => {
+            let base = match base_type.conceptual_type.resolve_alias_shallow() {
+                ConceptualRustType::Primitive(p)
+                    if !matches!(p, Primitive::Bool) && base_type.encodings.is_empty() =>
+                {
+                    *p
+                }
+                _ => panic!(".ne is only supported on numbers, text and bytes"),
+            };
+            register_inline_intersected_type(
+                types,
+                parent_visitor,
+                type1,
+                IntersectedType::new(ConceptualRustType::Primitive(base))
+                    .apply_control(ControlOperator::NotEqual(value)),
+                cli,
+            )
+        }
+        Some(ControlOperator::Regexp(regexp)) => panic!(
+            ".regexp \"{}\" must be declared as its own type e.g. `foo = tstr .regexp \"...\"` instead of being used inline",
+            regexp
+        ),
+        None => base_type,
+    }
+}
+
+// Inline types with checks that need somewhere to live e.g. foo = { a: uint .ne 0 } get their own wrapper.
+// Like inline maps it's named after where it's defined (FooA here) unless given a name via @name
+fn register_inline_intersected_type(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type1: &Type1,
+    intersected: IntersectedType,
+    cli: &Cli,
+) -> RustType {
+    let rule_metadata = RuleMetadata::from(
+        get_comment_after(parent_visitor, &CDDLType::from(type1), None).as_ref(),
+    );
+    let name = rule_metadata
+        .name
+        .clone()
+        .unwrap_or_else(|| inline_type_name(parent_visitor, &CDDLType::from(type1)));
+    let cddl_ident = CDDLIdent::new(name);
+    let rust_ident = RustIdent::new(cddl_ident.clone());
+    types.register_inline_ident(&rust_ident, type1.to_string());
+    register_intersected_type(
+        types,
+        parent_visitor,
+        &rust_ident,
+        None,
+        rule_metadata,
+        intersected,
+        cli,
+    );
+    types.new_type(&cddl_ident, cli)
+}
+
+// Group choices used directly as a type e.g. foo = [bar: [0, uint // 1, text]] are generated as an anonymous
+// group choice enum. Like inline maps the enum is named after where it's defined (FooBar here) unless given
+// a name via @name, and its variants are named after their choices (see inline_group_choice_variant_names())
+fn parse_inline_group_choices(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    type2: &Type2,
+    group: &Group,
+    rep: Representation,
+    cli: &Cli,
+) -> RustType {
+    let rule_metadata = RuleMetadata::from(
+        get_comment_after(parent_visitor, &CDDLType::from(type2), None).as_ref(),
+    );
+    let name = rule_metadata
+        .name
+        .clone()
+        .unwrap_or_else(|| inline_type_name(parent_visitor, &CDDLType::from(type2)));
+    let cddl_ident = CDDLIdent::new(name);
+    let rust_ident = RustIdent::new(cddl_ident.clone());
+    types.register_inline_ident(&rust_ident, type2.to_string());
+    let variant_names = inline_group_choice_variant_names(types, parent_visitor, group, cli);
+    parse_group_with_variant_names(
+        types,
+        parent_visitor,
+        group,
+        &rust_ident,
+        rep,
+        None,
+        None,
+        &rule_metadata,
+        Some(&variant_names),
+        cli,
+    );
+    types.new_type(&cddl_ident, cli)
+}
+
+// Variants are named after their explicit field names if present, otherwise the types of their
+// non-fixed fields e.g. [0, uint // 1, text] -> U64 / Text and { a: uint // b: text } -> A / B.
+// Fixed values are only included when needed to tell them apart e.g. [0, uint // 1, uint] -> I0U64 / I1U64
+fn inline_group_choice_variant_names(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    group: &Group,
+    cli: &Cli,
+) -> Vec<String> {
+    let names_without_fixed = group
+        .group_choices
+        .iter()
+        .map(|group_choice| {
+            inline_group_choice_name(types, parent_visitor, group_choice, false, cli)
+        })
+        .collect::<Vec<_>>();
+    let names = if names_without_fixed
+        .iter()
+        .enumerate()
+        .any(|(i, name)| name.is_empty() || names_without_fixed[..i].contains(name))
+    {
+        group
+            .group_choices
+            .iter()
+            .map(|group_choice| {
+                inline_group_choice_name(types, parent_visitor, group_choice, true, cli)
+            })
+            .collect()
+    } else {
+        names_without_fixed
+    };
+    let mut names_used = BTreeMap::<String, u32>::new();
+    names
+        .into_iter()
+        .map(|name| {
+            let name = if name.is_empty() {
+                String::from("Empty")
+            } else {
+                name
+            };
+            append_number_if_duplicate(&mut names_used, name)
+        })
+        .collect()
 }
 
-fn rust_type_from_type1(
+// explicit field names if present, otherwise the types of the fields (skipping fixed values unless {include_fixed})
+fn inline_group_choice_name(
     types: &mut IntermediateTypes,
     parent_visitor: &ParentVisitor,
-    type1: &Type1,
+    group_choice: &GroupChoice,
+    include_fixed: bool,
     cli: &Cli,
-) -> RustType {
-    let control = type1
-        .operator
-        .as_ref()
-        .map(|op| parse_control_operator(types, parent_visitor, &type1.type2, op, cli));
-    let base_type = rust_type_from_type2(types, parent_visitor, &type1.type2, cli);
-    // println!("type1: {:#?}", type1);
-    match control {
-        Some(ControlOperator::CBOR(ty)) => {
-            assert!(matches!(
-                base_type.conceptual_type.resolve_alias_shallow(),
-                ConceptualRustType::Primitive(Primitive::Bytes)
-            ));
-            ty.as_bytes()
-        }
-        Some(ControlOperator::Range((low, high))) => match &type1.type2 {
-            Type2::Typename { ident, .. } => {
-                match ident_to_primitive(&CDDLIdent::new(ident.to_string())) {
-                    Some(p) => range_to_primitive(low, high, p),
-                    None => base_type.with_bounds((low, high)),
+) -> String {
+    let mut parts = Vec::new();
+    for (group_entry, _) in group_choice.group_entries.iter() {
+        let explicit_name = match group_entry {
+            GroupEntry::ValueMemberKey { ge, .. } => match &ge.member_key {
+                Some(MemberKey::Bareword { ident, .. }) => Some(ident.to_string()),
+                Some(MemberKey::Value {
+                    value: cddl::token::Value::TEXT(text),
+                    ..
+                }) => Some(text.to_string()),
+                _ => None,
+            },
+            GroupEntry::TypeGroupname { ge, .. } => Some(ge.name.to_string()),
+            GroupEntry::InlineGroup { .. } => None,
+        };
+        match explicit_name {
+            Some(name) => parts.push(convert_to_camel_case(&name)),
+            None => {
+                let ty = group_entry_to_type(types, parent_visitor, group_entry, cli);
+                if include_fixed || !ty.is_fixed_value() {
+                    parts.push(ty.for_variant().to_string());
                 }
             }
-            // the base value will be a constant due to incomplete parsing earlier for explicit ranges
-            // e.g. foo = 0..255
-            Type2::IntValue { .. } => range_to_primitive(low, high, Primitive::I64),
-            Type2::UintValue { .. } => range_to_primitive(low, high, Primitive::U64),
-            _ => base_type.with_bounds((low, high)),
-        },
-        Some(ControlOperator::Default(default_value)) => base_type.default(default_value),
-        None => base_type,
+        }
     }
+    parts.concat()
 }
 
 fn rust_type_from_type2(
@@ -1141,7 +2650,6 @@ fn rust_type_from_type2(
     type2: &Type2,
     cli: &Cli,
 ) -> RustType {
-    // TODO: socket plugs (used in hash type)
     match &type2 {
         Type2::UintValue { value, .. } => {
             ConceptualRustType::Fixed(FixedValue::Uint(*value)).into()
@@ -1178,6 +2686,7 @@ fn rust_type_from_type2(
                     let instance_cddl_ident = CDDLIdent::new(format!("{cddl_ident}_{args_name}"));
                     let instance_ident = RustIdent::new(instance_cddl_ident.clone());
                     let generic_ident = RustIdent::new(cddl_ident);
+                    parse_generic_rule(types, parent_visitor, &generic_ident, cli);
                     types.register_generic_instance(GenericInstance::new(
                         instance_ident,
                         generic_ident,
@@ -1185,11 +2694,14 @@ fn rust_type_from_type2(
                     ));
                     types.new_type(&instance_cddl_ident, cli)
                 }
+                // sockets need their prefix so we can tell if they were never plugged
+                None if ident.socket.is_some() => {
+                    types.new_type(&CDDLIdent::new(ident.to_string()), cli)
+                }
                 None => types.new_type(&cddl_ident, cli),
             }
         }
         Type2::Array { group, .. } => {
-            // TODO: support for group choices in arrays?
             match group.group_choices.len() {
                 1 => {
                     let group_choice = &group.group_choices.first().unwrap();
@@ -1200,18 +2712,24 @@ fn rust_type_from_type2(
                         Representation::Array,
                         cli,
                     ) {
-                        GroupParsingType::HomogenousArray(element_type) => {
+                        GroupParsingType::HomogenousArray(mut element_type, bounds) => {
                             if let ConceptualRustType::Rust(element_ident) =
                                 &element_type.conceptual_type
                             {
-                                types.set_rep_if_plain_group(
+                                let rep_ident = types.set_rep_if_plain_group(
                                     parent_visitor,
                                     element_ident,
                                     Representation::Array,
                                     cli,
                                 );
+                                element_type.conceptual_type = ConceptualRustType::Rust(rep_ident);
+                            }
+                            let array_type: RustType =
+                                ConceptualRustType::Array(Box::new(element_type)).into();
+                            match bounds {
+                                Some(bounds) => array_type.with_bounds(bounds),
+                                None => array_type,
                             }
-                            ConceptualRustType::Array(Box::new(element_type)).into()
                         }
                         GroupParsingType::HomogenousMap(_, _) => unreachable!(),
                         GroupParsingType::Heterogenous => {
@@ -1242,8 +2760,15 @@ fn rust_type_from_type2(
                         GroupParsingType::WrappedBasicGroup(basic_type) => basic_type,
                     }
                 }
-                // array of elements with choices: enums?
-                _ => unimplemented!("group choices in array type not supported"),
+                // array of elements with choices: anonymous group choice enum
+                _ => parse_inline_group_choices(
+                    types,
+                    parent_visitor,
+                    type2,
+                    group,
+                    Representation::Array,
+                    cli,
+                ),
             }
         }
         Type2::Map { group, .. } => {
@@ -1267,21 +2792,104 @@ fn rust_type_from_type2(
                             //types.register_rust_struct(RustStruct::new_table(table_type_ident, None, key_type.clone(), value_type.clone()));
                             ConceptualRustType::Map(Box::new(key_type), Box::new(value_type)).into()
                         }
-                        _ => unimplemented!("TODO: non-table types as types: {:?}", group),
+                        GroupParsingType::Heterogenous => {
+                            // Struct-type map defined inline e.g. foo = { meta: { name: tstr } }
+                            // becomes its own record named after where it's defined (FooMeta here)
+                            let rule_metadata = RuleMetadata::from(
+                                get_comment_after(parent_visitor, &CDDLType::from(type2), None)
+                                    .as_ref(),
+                            );
+                            let name = rule_metadata.name.clone().unwrap_or_else(|| {
+                                inline_type_name(parent_visitor, &CDDLType::from(type2))
+                            });
+                            let cddl_ident = CDDLIdent::new(name);
+                            let rust_ident = RustIdent::new(cddl_ident.clone());
+                            types.register_inline_ident(&rust_ident, type2.to_string());
+                            parse_group(
+                                types,
+                                parent_visitor,
+                                group,
+                                &rust_ident,
+                                Representation::Map,
+                                None,
+                                None,
+                                &rule_metadata,
+                                cli,
+                            );
+                            types.new_type(&cddl_ident, cli)
+                        }
+                        GroupParsingType::HomogenousArray(_, _)
+                        | GroupParsingType::WrappedBasicGroup(_) => unreachable!(),
                     }
                 }
-                _ => unimplemented!(
-                    "group choices in inlined map types not allowed: {:?}",
-                    group
+                _ => parse_inline_group_choices(
+                    types,
+                    parent_visitor,
+                    type2,
+                    group,
+                    Representation::Map,
+                    cli,
                 ),
             }
         }
-        // unsure if we need to handle the None case - when does this happen?
-        Type2::TaggedData { tag, t, .. } => {
-            let tag_unwrap = tag.expect("tagged data without tag not supported");
-            rust_type(types, parent_visitor, t, cli).tag(tag_unwrap)
-        }
+        Type2::TaggedData { tag, t, .. } => match tag {
+            Some(tag) => rust_type(types, parent_visitor, t, cli).tag(*tag),
+            None => any_tag_rust_type(types, parent_visitor, t, cli),
+        },
         Type2::ParenthesizedType { pt, .. } => rust_type(types, parent_visitor, pt, cli),
+        Type2::DataMajorType { mt, constraint, .. } => {
+            major_type_to_rust_type(types, *mt, constraint.map(|c| c as u64), cli)
+        }
+        Type2::ChoiceFromInlineGroup { .. } | Type2::ChoiceFromGroup { .. } => {
+            // e.g. foo = { color: &(red: 0, green: 1) } becomes its own enum (FooColor here)
+            let rule_metadata = RuleMetadata::from(
+                get_comment_after(parent_visitor, &CDDLType::from(type2), None).as_ref(),
+            );
+            let name = rule_metadata
+                .name
+                .clone()
+                .unwrap_or_else(|| inline_type_name(parent_visitor, &CDDLType::from(type2)));
+            let cddl_ident = CDDLIdent::new(name);
+            let rust_ident = RustIdent::new(cddl_ident.clone());
+            types.register_inline_ident(&rust_ident, type2.to_string());
+            let mut variant_names_used = BTreeMap::<String, u32>::new();
+            let variants = choice_from_group_variants(types, type2)
+                .into_iter()
+                .map(|(base_name, rust_type)| {
+                    let variant_name =
+                        append_number_if_duplicate(&mut variant_names_used, base_name);
+                    EnumVariant::new(
+                        VariantIdent::new_custom(variant_name),
+                        rust_type,
+                        false,
+                        None,
+                    )
+                })
+                .collect();
+            types.register_rust_struct(
+                parent_visitor,
+                RustStruct::new_type_choice(rust_ident, None, Some(&rule_metadata), variants, cli),
+                cli,
+            );
+            types.new_type(&cddl_ident, cli)
+        }
+        Type2::Any { .. } => types.new_type(&CDDLIdent::new("any"), cli),
+        Type2::Unwrap {
+            ident,
+            generic_args,
+            ..
+        } => {
+            if generic_args.is_some() {
+                panic!(
+                    "~{}: unwrapping generic instances is not supported. Only non-generic array/map definitions can be unwrapped",
+                    ident
+                );
+            }
+            // ~foo embeds foo's contents which we treat as a plain group containing the same fields
+            let plain_group_ident =
+                types.unwrapped_plain_group(&RustIdent::new(CDDLIdent::new(ident.to_string())));
+            ConceptualRustType::Rust(plain_group_ident).into()
+        }
         _ => {
             panic!("Ignoring Type2: {:?}", type2);
         }
@@ -1350,16 +2958,41 @@ fn rust_type(
     }
 }
 
-fn group_entry_optional(entry: &GroupEntry) -> bool {
+/// (min, max) number of times something with this occurrence indicator can appear
+fn occurrence_bounds(occur: &Occur) -> (Option<i128>, Option<i128>) {
+    match occur {
+        Occur::ZeroOrMore { .. } => (None, None),
+        Occur::Exact { lower, upper, .. } => (
+            lower.filter(|l| *l != 0).map(|i| i as i128),
+            upper.map(|i| i as i128),
+        ),
+        Occur::Optional { .. } => (None, Some(1)),
+        Occur::OneOrMore { .. } => (Some(1), None),
+    }
+}
+
+/// None if the entry has no occurrence indicator
+fn group_entry_occurrence_bounds(entry: &GroupEntry) -> Option<(Option<i128>, Option<i128>)> {
     let occur = match entry {
         GroupEntry::ValueMemberKey { ge, .. } => &ge.occur,
         GroupEntry::TypeGroupname { ge, .. } => &ge.occur,
         GroupEntry::InlineGroup { .. } => panic!("inline group entries are not implemented"),
     };
-    occur
-        .as_ref()
-        .map(|o| matches!(o.occur, Occur::Optional { .. }))
-        .unwrap_or(false)
+    occur.as_ref().map(|o| occurrence_bounds(&o.occur))
+}
+
+fn group_entry_optional(entry: &GroupEntry) -> bool {
+    group_entry_occurrence_bounds(entry) == Some((None, Some(1)))
+}
+
+/// Whether this array entry can appear a variable number of times e.g. * text or 1* bytes
+/// in which case it is represented as a Vec of all its (embedded) occurrences.
+/// Returns the bounds on the number of occurrences if so.
+fn group_entry_embedded_seq_bounds(entry: &GroupEntry) -> Option<(Option<i128>, Option<i128>)> {
+    match group_entry_occurrence_bounds(entry) {
+        None | Some((None, Some(1))) | Some((Some(1), Some(1))) => None,
+        bounds => bounds,
+    }
 }
 
 fn group_entry_to_type(
@@ -1387,30 +3020,111 @@ fn group_entry_to_type(
     }
 }
 
-fn group_entry_to_key(entry: &GroupEntry) -> Option<FixedValue> {
+fn group_socket_ident(entry: &GroupEntry) -> Option<RustIdent> {
+    match entry {
+        GroupEntry::TypeGroupname { ge, .. }
+            if ge.name.socket == Some(token::SocketPlug::GROUP) =>
+        {
+            Some(RustIdent::new(CDDLIdent::new(ge.name.to_string())))
+        }
+        _ => None,
+    }
+}
+
+fn is_unplugged_group_socket(types: &IntermediateTypes, entry: &GroupEntry) -> bool {
+    group_socket_ident(entry)
+        .map(|ident| !types.is_plain_group(&ident))
+        .unwrap_or(false)
+}
+
+// the choices of a group socket if it was plugged multiple times and is the only thing in the group
+fn group_socket_choices<'a>(types: &IntermediateTypes<'a>, group: &Group) -> Option<Group<'a>> {
+    match group.group_choices.as_slice() {
+        [group_choice] => match group_choice.group_entries.as_slice() {
+            [(group_entry, _)] => group_socket_ident(group_entry)
+                .and_then(|ident| types.plain_group(&ident))
+                .and_then(PlainGroupInfo::group)
+                .filter(|socket_group| socket_group.group_choices.len() > 1)
+                .cloned(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn group_entry_to_key(entry: &GroupEntry) -> Option<MapKey> {
     match entry {
         GroupEntry::ValueMemberKey { ge, .. } => match ge.member_key.as_ref()? {
-            MemberKey::Value { value, .. } => match value {
-                cddl::token::Value::UINT(x) => Some(FixedValue::Uint(*x)),
-                cddl::token::Value::INT(x) => Some(FixedValue::Nint(*x)),
-                cddl::token::Value::TEXT(x) => Some(FixedValue::Text(x.to_string())),
-                cddl::token::Value::FLOAT(x) => Some(FixedValue::Float(*x)),
-                _ => panic!("unsupported map identifier(1): {:?}", value),
-            },
-            MemberKey::Bareword { ident, .. } => Some(FixedValue::Text(ident.to_string())),
-            MemberKey::Type1 { t1, .. } => match &t1.type2 {
-                Type2::UintValue { value, .. } => Some(FixedValue::Uint(*value)),
-                Type2::IntValue { value, .. } => Some(FixedValue::Nint(*value)),
-                Type2::TextValue { value, .. } => Some(FixedValue::Text(value.to_string())),
-                Type2::FloatValue { value, .. } => Some(FixedValue::Float(*value)),
-                _ => panic!("unsupported map identifier(2): {:?}", entry),
-            },
+            // keys written with : are always cut
+            MemberKey::Value { value, .. } => Some(
+                match value {
+                    token::Value::UINT(x) => MapKey::new(FixedValue::Uint(*x)),
+                    token::Value::INT(x) => MapKey::new(FixedValue::Nint(*x)),
+                    token::Value::TEXT(x) => MapKey::new(FixedValue::Text(x.to_string())),
+                    token::Value::FLOAT(x) => MapKey::new(FixedValue::Float(*x)),
+                    token::Value::BYTE(token::ByteValue::UTF8(x)) => {
+                        MapKey::new(FixedValue::Bytes(x.to_vec()))
+                    }
+                    token::Value::BYTE(token::ByteValue::B16(x)) => {
+                        MapKey::new(FixedValue::Bytes(decode_b16(x)))
+                    }
+                    _ => panic!("unsupported map identifier(1): {:?}", value),
+                }
+                .cut(true),
+            ),
+            MemberKey::Bareword { ident, .. } => {
+                Some(MapKey::new(FixedValue::Text(ident.to_string())).cut(true))
+            }
+            MemberKey::Type1 { t1, is_cut, .. } => Some(
+                type2_to_map_key(&t1.type2)
+                    .unwrap_or_else(|| panic!("unsupported map identifier(2): {:?}", entry))
+                    .cut(*is_cut),
+            ),
             MemberKey::NonMemberKey { .. } => panic!("Please open a github issue with repro steps"),
         },
         _ => None,
     }
 }
 
+// literal map keys e.g. 1, -1, "foo", h'00' or #6.42("foo"). None if it's a type e.g. the uint in * uint => any
+fn type2_to_map_key(type2: &Type2) -> Option<MapKey> {
+    match type2 {
+        Type2::UintValue { .. }
+        | Type2::IntValue { .. }
+        | Type2::TextValue { .. }
+        | Type2::FloatValue { .. }
+        | Type2::UTF8ByteString { .. }
+        | Type2::B16ByteString { .. } => Some(MapKey::new(type2_to_fixed_value(type2))),
+        Type2::TaggedData { tag, t, .. } => match t.type_choices.as_slice() {
+            // #6(..) without a tag number can't be a literal key
+            [choice] if choice.type1.operator.is_none() => {
+                tag.and_then(|tag| type2_to_map_key(&choice.type1.type2).map(|key| key.tag(tag)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// the catch-all table entry of a map struct e.g. the * uint => any in { 1: uint, * uint => any }
+fn group_entry_is_map_catch_all(entry: &GroupEntry) -> bool {
+    match entry {
+        GroupEntry::ValueMemberKey { ge, .. } => {
+            let zero_or_more = ge
+                .occur
+                .as_ref()
+                .map(|o| matches!(o.occur, Occur::ZeroOrMore { .. }))
+                .unwrap_or(false);
+            let non_literal_key = match &ge.member_key {
+                Some(MemberKey::Type1 { t1, .. }) => type2_to_map_key(&t1.type2).is_none(),
+                _ => false,
+            };
+            zero_or_more && non_literal_key
+        }
+        _ => false,
+    }
+}
+
 fn parse_record_from_group_choice(
     types: &mut IntermediateTypes,
     rep: Representation,
@@ -1419,11 +3133,30 @@ fn parse_record_from_group_choice(
     cli: &Cli,
 ) -> RustRecord {
     let mut generated_fields = BTreeMap::<String, u32>::new();
-    let fields = group_choice
+    let mut extra = None;
+    let fields: Vec<RustField> = group_choice
         .group_entries
         .iter()
         .enumerate()
-        .map(|(index, (group_entry, optional_comma))| {
+        .filter_map(|(index, (group_entry, optional_comma))| {
+            if rep == Representation::Map && group_entry_is_map_catch_all(group_entry) {
+                if let GroupEntry::ValueMemberKey { ge, .. } = group_entry {
+                    if let Some(MemberKey::Type1 { t1, .. }) = &ge.member_key {
+                        assert!(
+                            extra.is_none(),
+                            "only one catch-all table entry is supported per map: {}",
+                            group_choice
+                        );
+                        let key_type = rust_type_from_type1(types, parent_visitor, t1, cli);
+                        let value_type = rust_type(types, parent_visitor, &ge.entry_type, cli);
+                        extra = Some(RustType::new(ConceptualRustType::Map(
+                            Box::new(key_type),
+                            Box::new(value_type),
+                        )));
+                    }
+                }
+                return None;
+            }
             let field_name = group_entry_to_field_name(
                 group_entry,
                 index,
@@ -1432,21 +3165,62 @@ fn parse_record_from_group_choice(
             );
             let rule_metadata = group_entry_rule_metadata(group_entry, optional_comma);
             // does not exist for fixed values importantly
-            let field_type = group_entry_to_type(types, parent_visitor, group_entry, cli);
+            let mut field_type = group_entry_to_type(types, parent_visitor, group_entry, cli);
             if let ConceptualRustType::Rust(ident) = &field_type.conceptual_type {
-                types.set_rep_if_plain_group(parent_visitor, ident, rep, cli);
+                let rep_ident = types.set_rep_if_plain_group(parent_visitor, ident, rep, cli);
+                field_type.conceptual_type = ConceptualRustType::Rust(rep_ident);
             }
             let optional_field = group_entry_optional(group_entry);
+            if rep == Representation::Array {
+                if let Some(bounds) = group_entry_embedded_seq_bounds(group_entry) {
+                    if field_type.is_basic(types) || field_type.is_fixed_value() {
+                        panic!(
+                            "{}: variable occurrences of plain groups / fixed values within arrays are not supported: {}",
+                            field_name, group_choice
+                        );
+                    }
+                    let seq_type = RustType::new(ConceptualRustType::Array(Box::new(field_type)))
+                        .as_embedded_seq();
+                    field_type = match bounds {
+                        (None, None) => seq_type,
+                        bounds => seq_type.with_bounds(bounds),
+                    };
+                }
+            }
             let key = match rep {
                 Representation::Map => {
-                    Some(group_entry_to_key(group_entry).expect("map fields need keys"))
+                    // ~foo entries are embedded as a plain group so they also land in this branch
+                    if field_type.is_basic(types) {
+                        // plain groups embed their own keys/values directly into the map
+                        assert!(
+                            !optional_field,
+                            "{}: optional plain groups within maps are not supported",
+                            field_name
+                        );
+                        None
+                    } else {
+                        Some(group_entry_to_key(group_entry).expect("map fields need keys"))
+                    }
                 }
                 Representation::Array => None,
             };
-            RustField::new(field_name, field_type, optional_field, key, rule_metadata)
+            Some(RustField::new(
+                field_name,
+                field_type,
+                optional_field,
+                key,
+                rule_metadata,
+            ))
         })
         .collect();
-    RustRecord { rep, fields }
+    if extra.is_some() {
+        assert!(
+            fields.iter().all(|field| field.name != "extra"),
+            "field name extra conflicts with the catch-all table entry: {}",
+            group_choice
+        );
+    }
+    RustRecord { rep, fields, extra }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1470,21 +3244,31 @@ fn parse_group_choice(
         rule_metadata
     };
     let rust_struct = match parse_group_type(types, parent_visitor, group_choice, rep, cli) {
-        GroupParsingType::HomogenousArray(element_type) => {
-            if rule_metadata.newtype.is_some() {
+        GroupParsingType::HomogenousArray(element_type, bounds) => match bounds {
+            Some((Some(min), Some(max))) if min == max && cli.fixed_size_arrays => {
+                RustStruct::new_fixed_size_array_wrapper(
+                    name.clone(),
+                    tag,
+                    Some(&rule_metadata),
+                    element_type,
+                    usize::try_from(min)
+                        .unwrap_or_else(|_| panic!("{}: invalid fixed array length {}", name, min)),
+                )
+            }
+            _ if rule_metadata.newtype.is_some() || bounds.is_some() => {
                 // generate newtype over array
+                // bounded arrays need one too so the element count can be checked on creation
                 RustStruct::new_wrapper(
                     name.clone(),
                     tag,
                     Some(&rule_metadata),
                     ConceptualRustType::Array(Box::new(element_type)).into(),
-                    None,
+                    bounds,
                 )
-            } else {
-                // Array - homogeneous element type with proper occurence operator
-                RustStruct::new_array(name.clone(), tag, Some(&rule_metadata), element_type)
             }
-        }
+            // Array - homogeneous element type with proper occurence operator
+            _ => RustStruct::new_array(name.clone(), tag, Some(&rule_metadata), element_type),
+        },
         GroupParsingType::HomogenousMap(key_type, value_type) => {
             if rule_metadata.newtype.is_some() {
                 // generate newtype over map
@@ -1536,6 +3320,77 @@ pub fn parse_group(
     parent_rule_metadata: &RuleMetadata,
     cli: &Cli,
 ) {
+    parse_group_with_variant_names(
+        types,
+        parent_visitor,
+        group,
+        name,
+        rep,
+        tag,
+        generic_params,
+        parent_rule_metadata,
+        None,
+        cli,
+    )
+}
+
+// {variant_names} overrides the default names of group choice variants (e.g. Foo0, Foo1)
+// for the choices without a @name
+#[allow(clippy::too_many_arguments)]
+fn parse_group_with_variant_names(
+    types: &mut IntermediateTypes,
+    parent_visitor: &ParentVisitor,
+    group: &Group,
+    name: &RustIdent,
+    rep: Representation,
+    tag: Option<usize>,
+    generic_params: Option<Vec<RustIdent>>,
+    parent_rule_metadata: &RuleMetadata,
+    variant_names: Option<&[String]>,
+    cli: &Cli,
+) {
+    // group sockets that were never plugged are open extension points that contain nothing (yet)
+    if group.group_choices.iter().any(|group_choice| {
+        group_choice
+            .group_entries
+            .iter()
+            .any(|(group_entry, _)| is_unplugged_group_socket(types, group_entry))
+    }) {
+        let mut plugged_group = group.clone();
+        for group_choice in plugged_group.group_choices.iter_mut() {
+            group_choice
+                .group_entries
+                .retain(|(group_entry, _)| !is_unplugged_group_socket(types, group_entry));
+        }
+        return parse_group_with_variant_names(
+            types,
+            parent_visitor,
+            &plugged_group,
+            name,
+            rep,
+            tag,
+            generic_params,
+            parent_rule_metadata,
+            variant_names,
+            cli,
+        );
+    }
+    // a group socket that was plugged multiple times is a group choice in its own right
+    // so when it's the entire group e.g. foo = [$$foo] we can treat it as if it were inlined
+    if let Some(socket_group) = group_socket_choices(types, group) {
+        return parse_group_with_variant_names(
+            types,
+            parent_visitor,
+            &socket_group,
+            name,
+            rep,
+            tag,
+            generic_params,
+            parent_rule_metadata,
+            variant_names,
+            cli,
+        );
+    }
     if group.group_choices.len() == 1 {
         // Handle simple (no choices) group.
         parse_group_choice(
@@ -1550,9 +3405,6 @@ pub fn parse_group(
             cli,
         );
     } else {
-        if generic_params.is_some() {
-            todo!("{}: generic group choices not supported", name);
-        }
         assert!(parent_rule_metadata.newtype.is_none());
         // Generate Enum object that is not exposed to wasm, since wasm can't expose
         // fully featured rust enums via wasm_bindgen
@@ -1560,7 +3412,11 @@ pub fn parse_group(
         // TODO: We don't support generating SerializeEmbeddedGroup for group choices which is necessary for plain groups
         // It would not be as trivial to add as we do the outer group's array/map tag writing inside the variant match
         // to avoid having to always generate SerializeEmbeddedGroup when not necessary.
-        assert!(!types.is_plain_group(name));
+        assert!(
+            !types.is_plain_group(name),
+            "{}: plain groups with choices (e.g. group sockets plugged multiple times) can only be used as the entire contents of an array/map",
+            name
+        );
 
         // Handle group with choices by generating an enum then generating a group for every choice
         let mut variants_names_used = BTreeMap::<String, u32>::new();
@@ -1573,28 +3429,39 @@ pub fn parse_group(
                     RuleMetadata::from(group_choice.comments_before_grpchoice.as_ref());
                 // If we're a 1-element we should just wrap that type in the variant rather than
                 // define a new struct just for each variant.
-                // TODO: handle map-based enums? It would require being able to extract the key logic
-                // We might end up doing this anyway to support table-maps in choices though.
-                if group_choice.group_entries.len() == 1 {
+                // Map entries with keys can't be wrapped directly as we'd lose the key so those go through
+                // the general case below instead.
+                let single_keyless_entry = match group_choice.group_entries.as_slice() {
+                    [(GroupEntry::ValueMemberKey { ge, .. }, _)] => {
+                        rep == Representation::Array || ge.member_key.is_none()
+                    }
+                    [_] => true,
+                    _ => false,
+                };
+                if single_keyless_entry {
                     let group_entry = &group_choice.group_entries.first().unwrap().0;
-                    let ty = group_entry_to_type(types, parent_visitor, group_entry, cli);
+                    let mut ty = group_entry_to_type(types, parent_visitor, group_entry, cli);
                     let serialize_as_embedded =
                         if let ConceptualRustType::Rust(ident) = &ty.conceptual_type {
                             // we might need to generate it if not used elsewhere
-                            types.set_rep_if_plain_group(parent_visitor, ident, rep, cli);
+                            let rep_ident =
+                                types.set_rep_if_plain_group(parent_visitor, ident, rep, cli);
+                            ty.conceptual_type = ConceptualRustType::Rust(rep_ident.clone());
                             // manual match in case we expand operaitons later
-                            types.is_plain_group(ident)
+                            types.is_plain_group(&rep_ident)
                                 && !ty.encodings.iter().any(|enc| match enc {
                                     CBOREncodingOperation::Tagged(_) => true,
                                     CBOREncodingOperation::CBORBytes => true,
+                                    CBOREncodingOperation::CBORSeqBytes => true,
                                 })
                         } else {
                             false
                         };
                     let ident_name = rule_metadata.name.unwrap_or_else(|| {
-                        match group_entry_to_raw_field_name(group_entry) {
-                            Some(name) => name,
-                            None => append_number_if_duplicate(
+                        match (variant_names, group_entry_to_raw_field_name(group_entry)) {
+                            (Some(variant_names), _) => variant_names[i].clone(),
+                            (None, Some(name)) => name,
+                            (None, None) => append_number_if_duplicate(
                                 &mut variants_names_used,
                                 ty.for_variant().to_string(),
                             ),
@@ -1619,9 +3486,21 @@ pub fn parse_group(
                     //     EnumVariant::new(variant_name.clone(), RustType::Rust(variant_name), true)
                     // },
                 } else {
-                    let ident_name = rule_metadata.name.unwrap_or_else(|| format!("{name}{i}"));
+                    // the variant is named directly when given one but its type still needs a unique name
+                    let custom_variant_name = match (&rule_metadata.name, variant_names) {
+                        (None, Some(variant_names)) => Some(variant_names[i].clone()),
+                        _ => None,
+                    };
+                    let ident_name = match (rule_metadata.name, &custom_variant_name) {
+                        (Some(ident_name), _) => ident_name,
+                        (None, Some(variant_name)) => format!("{name}{variant_name}"),
+                        (None, None) => format!("{name}{i}"),
+                    };
                     // General case, GroupN type identifiers and generate group choice since it's inlined here
                     let variant_name = RustIdent::new(CDDLIdent::new(ident_name));
+                    if custom_variant_name.is_some() {
+                        types.register_inline_ident(&variant_name, group_choice.to_string());
+                    }
                     types.mark_plain_group(
                         variant_name.clone(),
                         PlainGroupInfo::new(None, RuleMetadata::default()),
@@ -1637,9 +3516,15 @@ pub fn parse_group(
                         None,
                         cli,
                     );
-                    let name = VariantIdent::new_rust(variant_name.clone());
+                    let name = match custom_variant_name {
+                        Some(custom_variant_name) => VariantIdent::new_custom(custom_variant_name),
+                        None => VariantIdent::new_rust(variant_name.clone()),
+                    };
                     let variant_ident = ConceptualRustType::Rust(variant_name.clone());
-                    if EnumVariant::can_embed_fields(types, &variant_ident) {
+                    // inlined variants are only supported for arrays as map keys would need to be handled
+                    if rep == Representation::Array
+                        && EnumVariant::can_embed_fields(types, &variant_ident)
+                    {
                         let embedded_record =
                             match types.remove_rust_struct(&variant_name).unwrap().variant {
                                 RustStructType::Record(record) => record,
@@ -1667,11 +3552,12 @@ pub fn parse_group(
             ),
             parent_rule_metadata,
         );
-        types.register_rust_struct(
-            parent_visitor,
-            RustStruct::new_group_choice(name.clone(), tag, Some(&rule_metadata), variants, rep),
-            cli,
-        );
+        let rust_struct =
+            RustStruct::new_group_choice(name.clone(), tag, Some(&rule_metadata), variants, rep);
+        match generic_params {
+            Some(params) => types.register_generic_def(GenericDef::new(params, rust_struct)),
+            None => types.register_rust_struct(parent_visitor, rust_struct, cli),
+        };
     }
 }
 
@@ -1882,7 +3768,6 @@ fn get_comment_after<'a>(
     }
 }
 
-#[allow(unused)]
 fn get_rule_name<'a>(
     parent_visitor: &'a ParentVisitor,
     cddl_type: &CDDLType<'a, '_>,
@@ -1900,3 +3785,79 @@ fn get_rule_name<'a>(
         other => get_rule_name(parent_visitor, other.parent(parent_visitor).unwrap()),
     }
 }
+
+// Name for an anonymous type based on the rule and fields it's defined within
+// e.g. the inner map in foo = { meta: { name: tstr } } becomes foo_meta
+fn inline_type_name<'a>(parent_visitor: &'a ParentVisitor, cddl_type: &CDDLType<'a, '_>) -> String {
+    match cddl_type {
+        CDDLType::Rule(_) | CDDLType::TypeRule(_) | CDDLType::GroupRule(_) => {
+            get_rule_name(parent_visitor, cddl_type).to_string()
+        }
+        CDDLType::GroupEntry(entry) => {
+            let parent = cddl_type.parent(parent_visitor).unwrap();
+            let field_name = match entry {
+                // literal keys are named the same way as the field they're the value of e.g. key_neg_1 for -1
+                GroupEntry::ValueMemberKey { ge, .. } => match ge.member_key.as_ref() {
+                    Some(MemberKey::Bareword { ident, .. }) => Some(ident.to_string()),
+                    Some(MemberKey::Value { .. }) => {
+                        group_entry_to_key(entry).map(|key| map_key_to_field_name(&key))
+                    }
+                    Some(MemberKey::Type1 { t1, .. }) => {
+                        type2_to_map_key(&t1.type2).map(|key| map_key_to_field_name(&key))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+            // text keys can contain anything so only keep what's valid in an identifier
+            .map(|field_name| {
+                field_name
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>()
+            });
+            // fall back to the position for keyless entries the same way fields are named
+            let field_name = field_name.unwrap_or_else(|| match parent {
+                CDDLType::GroupChoice(group_choice) => {
+                    let index = group_choice
+                        .group_entries
+                        .iter()
+                        .position(|(group_entry, _)| std::ptr::eq(group_entry, *entry))
+                        .unwrap();
+                    format!("index_{index}")
+                }
+                _ => unreachable!(),
+            });
+            format!(
+                "{}_{}",
+                inline_type_name(parent_visitor, parent),
+                field_name
+            )
+        }
+        // each choice needs its own name e.g. foo = { a: uint } / { b: text } -> foo_choice_0 / foo_choice_1
+        // but optional types (e.g. foo = { a: uint } / null) only have the one
+        CDDLType::TypeChoice(type_choice) => {
+            let parent = cddl_type.parent(parent_visitor).unwrap();
+            let name = inline_type_name(parent_visitor, parent);
+            match parent {
+                CDDLType::Type(t)
+                    if t.type_choices
+                        .iter()
+                        .filter(|tc| !type2_is_null(&tc.type1.type2))
+                        .count()
+                        > 1 =>
+                {
+                    let index = t
+                        .type_choices
+                        .iter()
+                        .position(|tc| std::ptr::eq(tc, *type_choice))
+                        .unwrap();
+                    format!("{name}_choice_{index}")
+                }
+                _ => name,
+            }
+        }
+        CDDLType::CDDL(_) => panic!("Cannot name an anonymous type outside of a rule"),
+        other => inline_type_name(parent_visitor, other.parent(parent_visitor).unwrap()),
+    }
+}
@@ -275,6 +275,58 @@ fn rust_wasm_split() {
     run_test("rust-wasm-split", &[], None, &[], &[], false, &[]);
 }
 
+#[test]
+fn date_time() {
+    run_test(
+        "date-time",
+        &["--typed-date-time=true"],
+        None,
+        &[],
+        &[],
+        false,
+        &[],
+    );
+}
+
+#[test]
+fn date_time_preserve() {
+    run_test(
+        "date-time-preserve",
+        &["--typed-date-time=true", "--preserve-encodings=true"],
+        None,
+        &[],
+        &[],
+        false,
+        &[],
+    );
+}
+
+#[test]
+fn fixed_size_arrays() {
+    run_test(
+        "fixed-size-arrays",
+        &["--fixed-size-arrays=true"],
+        None,
+        &[],
+        &[],
+        false,
+        &[],
+    );
+}
+
+#[test]
+fn fixed_size_arrays_preserve() {
+    run_test(
+        "fixed-size-arrays",
+        &["--fixed-size-arrays=true", "--preserve-encodings=true"],
+        Some("preserve"),
+        &[],
+        &[],
+        false,
+        &[],
+    );
+}
+
 #[test]
 fn multifile() {
     use std::str::FromStr;
@@ -79,42 +79,40 @@ pub fn convert_to_camel_case(ident: &str) -> String {
     camel_case
 }
 
+// lowercase hex without any prefix e.g. 00ff
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
 pub fn cddl_prelude(name: &str) -> Option<&str> {
     match name {
         // custom implemented types like uint, bool, etc
         // are handled in the alias system and shouldn't reach here
         "uint" | "nint" | "int" | "bool" | "tstr" | "text" |
-        "bstr" | "bytes" | "null" | "nil" | "true"  | "false" |
+        "bstr" | "bytes" | "null" | "nil" | "undefined" | "true"  | "false" |
         "float16" | // #7.25
         "float32" | // #7.26
         "float64" | // #7.27
         "float16-32" | // float16 / float32
         "float32-64" | // float32 / float64
-        "float" => unreachable!("{} should be handled by the alias system instead", name),
+        "float" |
+        "biguint" | "bignint" | "bigint" | "integer" | "unsigned" |
+        "decfrac" | // #6.4([e10: int, m: integer])
+        "bigfloat" | // #6.5([e2: int, m: integer])
+        "any" => unreachable!("{} should be handled by the alias system instead", name),
         "tdate" => Some("#6.0(tstr)"),
         "time" => Some("#6.1(number)"),
         "number" => Some("int / float"),
-        "biguint" => Some("#6.2(bstr)"),
-        "bignint" => Some("#6.3(bstr)"),
-        "bigint" => Some("biguint / bignint"),
-        "integer" => Some("int / bigint"),
-        "unsigned" => Some("uint / biguint"),
-        "decfrac" => Some("#6.4([e10: int), m: integer])"),
-        "bigfloat" => Some("#6.5([e2: int), m: integer])"),
         "encoded-cbor" => Some("#6.24(bstr)"),
         "uri" => Some("#6.32(tstr)"),
         "b64url" => Some("#6.33(tstr)"),
         "b64legacy" => Some("#6.34(tstr)"),
         "regexp" => Some("#6.35(tstr)"),
         "mime-message" => Some("#6.36(tstr)"),
-        // TODO: we don't support any (yet) - could we use message-signing's code?
-        "any" | // #
-        "cbor-any" | // #6.55799(any)
-        "eb64url" | // #6.21(any)
-        "eb64legacy" | // #6.22(any)
-        "eb16" | // #6.23(any)"),
-        // TODO: nor undefined (yet)
-        "undefined" => panic!("unsupported cddl prelude type: {}", name), // #7.23
+        "cbor-any" => Some("#6.55799(any)"),
+        "eb64url" => Some("#6.21(any)"),
+        "eb64legacy" => Some("#6.22(any)"),
+        "eb16" => Some("#6.23(any)"),
         _ => None,
     }
 }
@@ -123,6 +121,7 @@ pub fn cddl_prelude(name: &str) -> Option<&str> {
 pub fn is_identifier_reserved(name: &str) -> bool {
     match name {
         // These are all possible reserved identifiers, even if we don't support them
+        "any"        |
         "uint"       |
         "int"        |
         "nint"       |
@@ -0,0 +1,638 @@
+use crate::error::*;
+use crate::serialization::*;
+use cbor_event::de::Deserializer;
+use cbor_event::se::Serializer;
+use std::convert::TryFrom;
+use std::io::{BufRead, Seek, Write};
+use std::str::FromStr;
+
+/// How an integer was encoded: as a regular uint/nint or as a tag 2/3 bignum
+#[derive(Clone, Debug)]
+pub enum BigIntEncoding {
+    Int(cbor_event::Sz),
+    Bignum {
+        tag: cbor_event::Sz,
+        bytes: cbor_event::StringLenSz,
+    },
+}
+
+/// How a decfrac (tag 4) or bigfloat (tag 5) was encoded. The mantissa keeps its own encoding
+#[derive(Clone, Debug)]
+pub struct BigFractionEncoding {
+    pub tag: cbor_event::Sz,
+    pub len: cbor_event::LenSz,
+    pub exponent: cbor_event::Sz,
+}
+
+#[derive(Clone, Debug)]
+pub enum BigIntError {
+    /// Value doesn't fit into the requested type
+    OutOfBounds,
+    Parsing(num_bigint::ParseBigIntError),
+    /// Not a decimal number e.g. `-12.34` or `1.5e3`
+    InvalidDecimal,
+    /// Decimal number can't be represented exactly as a bigfloat e.g. `0.1`
+    Inexact,
+}
+
+impl std::fmt::Display for BigIntError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "value out of bounds"),
+            Self::Parsing(e) => write!(f, "invalid integer: {}", e),
+            Self::InvalidDecimal => write!(f, "invalid decimal number"),
+            Self::Inexact => write!(f, "not exactly representable in base 2"),
+        }
+    }
+}
+
+impl std::error::Error for BigIntError {}
+
+fn fit_int_sz(x: u64, sz: cbor_event::Sz) -> cbor_event::Sz {
+    let max = match sz {
+        cbor_event::Sz::Inline => 23u64,
+        cbor_event::Sz::One => u8::MAX as u64,
+        cbor_event::Sz::Two => u16::MAX as u64,
+        cbor_event::Sz::Four => u32::MAX as u64,
+        cbor_event::Sz::Eight => u64::MAX,
+    };
+    if x <= max {
+        sz
+    } else {
+        cbor_event::Sz::canonical(x)
+    }
+}
+
+// Preferred serialization: a uint/nint when it fits in 64 bits and a tag 2/3 bignum otherwise.
+// The encoding (if any) is only followed when it can still represent the value.
+fn write_integer<'se, W: Write>(
+    serializer: &'se mut Serializer<W>,
+    num: &num_bigint::BigInt,
+    encoding: Option<&BigIntEncoding>,
+) -> cbor_event::Result<&'se mut Serializer<W>> {
+    let negative = num.sign() == num_bigint::Sign::Minus;
+    // as encoded in CBOR i.e. |x + 1| for a negative x
+    let magnitude = if negative {
+        (-num - 1).into_parts().1
+    } else {
+        num.magnitude().clone()
+    };
+    match (u64::try_from(&magnitude), encoding) {
+        (Ok(x), None) | (Ok(x), Some(BigIntEncoding::Int(_))) => {
+            let sz = match encoding {
+                Some(BigIntEncoding::Int(sz)) => fit_int_sz(x, *sz),
+                _ => cbor_event::Sz::canonical(x),
+            };
+            if negative {
+                serializer.write_negative_integer_sz(-(x as i128) - 1, sz)
+            } else {
+                serializer.write_unsigned_integer_sz(x, sz)
+            }
+        }
+        _ => write_bignum(serializer, num, encoding),
+    }
+}
+
+// Always a tag 2/3 bignum regardless of the value's size as biguint / bignint / bigint require.
+// Int encodings are ignored as they can't be followed.
+fn write_bignum<'se, W: Write>(
+    serializer: &'se mut Serializer<W>,
+    num: &num_bigint::BigInt,
+    encoding: Option<&BigIntEncoding>,
+) -> cbor_event::Result<&'se mut Serializer<W>> {
+    let negative = num.sign() == num_bigint::Sign::Minus;
+    // tag 3 bignums represent -1 - n
+    let magnitude = if negative {
+        (-num - 1).into_parts().1
+    } else {
+        num.magnitude().clone()
+    };
+    let tag = if negative { 3 } else { 2 };
+    let bytes = if magnitude.bits() == 0 {
+        Vec::new()
+    } else {
+        magnitude.to_bytes_be()
+    };
+    let (tag_sz, bytes_sz) = match encoding {
+        Some(BigIntEncoding::Bignum {
+            tag,
+            bytes: bytes_sz,
+        }) => {
+            let bytes_sz = match bytes_sz {
+                cbor_event::StringLenSz::Len(sz) => {
+                    cbor_event::StringLenSz::Len(fit_int_sz(bytes.len() as u64, *sz))
+                }
+                cbor_event::StringLenSz::Indefinite(chunks)
+                    if chunks.iter().map(|(len, _sz)| len).sum::<u64>()
+                        == bytes.len() as u64 =>
+                {
+                    cbor_event::StringLenSz::Indefinite(chunks.clone())
+                }
+                cbor_event::StringLenSz::Indefinite(_) => cbor_event::StringLenSz::Len(
+                    cbor_event::Sz::canonical(bytes.len() as u64),
+                ),
+            };
+            (*tag, bytes_sz)
+        }
+        _ => (
+            cbor_event::Sz::Inline,
+            cbor_event::StringLenSz::Len(cbor_event::Sz::canonical(bytes.len() as u64)),
+        ),
+    };
+    serializer.write_tag_sz(tag, tag_sz)?;
+    serializer.write_bytes_sz(&bytes, bytes_sz)
+}
+
+// Reads either a uint/nint or a tag 2/3 bignum
+fn read_integer<R: BufRead + Seek>(
+    raw: &mut Deserializer<R>,
+) -> Result<(num_bigint::BigInt, BigIntEncoding), DeserializeError> {
+    match raw.cbor_type()? {
+        cbor_event::Type::UnsignedInteger => {
+            let (x, sz) = raw.unsigned_integer_sz()?;
+            Ok((num_bigint::BigInt::from(x), BigIntEncoding::Int(sz)))
+        }
+        cbor_event::Type::NegativeInteger => {
+            let (x, sz) = raw.negative_integer_sz()?;
+            Ok((num_bigint::BigInt::from(x), BigIntEncoding::Int(sz)))
+        }
+        cbor_event::Type::Tag => read_bignum(raw),
+        other => Err(cbor_event::Error::Expected(cbor_event::Type::UnsignedInteger, other).into()),
+    }
+}
+
+// Reads a tag 2/3 bignum only
+fn read_bignum<R: BufRead + Seek>(
+    raw: &mut Deserializer<R>,
+) -> Result<(num_bigint::BigInt, BigIntEncoding), DeserializeError> {
+    let (tag, tag_sz) = raw.tag_sz()?;
+    let (bytes, bytes_sz) = raw.bytes_sz()?;
+    let magnitude = num_bigint::BigInt::from(num_bigint::BigUint::from_bytes_be(&bytes));
+    let num = match tag {
+        2 => magnitude,
+        // tag 3 bignums represent -1 - n
+        3 => -magnitude - 1,
+        _ => {
+            return Err(DeserializeFailure::TagMismatch {
+                found: tag,
+                expected: 2,
+            }
+            .into())
+        }
+    };
+    Ok((
+        num,
+        BigIntEncoding::Bignum {
+            tag: tag_sz,
+            bytes: bytes_sz,
+        },
+    ))
+}
+
+// biguint is only #6.2 and bignint only #6.3. Only tag 3 bignums are negative so the sign tells.
+fn check_bignum_sign(num: &num_bigint::BigInt, negative: bool) -> Result<(), DeserializeError> {
+    if (num.sign() == num_bigint::Sign::Minus) == negative {
+        Ok(())
+    } else {
+        let (found, expected) = if negative { (2, 3) } else { (3, 2) };
+        Err(DeserializeFailure::TagMismatch { found, expected }.into())
+    }
+}
+
+// bignint can't represent non-negative values so they can't be serialized as one
+fn bignint_sign_error(num: &num_bigint::BigInt) -> cbor_event::Error {
+    cbor_event::Error::CustomError(format!("bignint must be negative, found {}", num))
+}
+
+// decfrac = #6.4([e10: int, m: integer]) and bigfloat = #6.5([e2: int, m: integer])
+fn write_big_fraction<'se, W: Write>(
+    serializer: &'se mut Serializer<W>,
+    tag: u64,
+    exponent: i64,
+    mantissa: &num_bigint::BigInt,
+    mantissa_encoding: Option<&BigIntEncoding>,
+    encoding: Option<&BigFractionEncoding>,
+) -> cbor_event::Result<&'se mut Serializer<W>> {
+    let (tag_sz, len, exponent_sz) = match encoding {
+        Some(encoding) => (
+            encoding.tag,
+            match &encoding.len {
+                cbor_event::LenSz::Len(_len, sz) => cbor_event::LenSz::Len(2, fit_int_sz(2, *sz)),
+                cbor_event::LenSz::Indefinite => cbor_event::LenSz::Indefinite,
+            },
+            Some(encoding.exponent),
+        ),
+        None => (
+            cbor_event::Sz::Inline,
+            cbor_event::LenSz::Len(2, cbor_event::Sz::Inline),
+            None,
+        ),
+    };
+    let indefinite = matches!(len, cbor_event::LenSz::Indefinite);
+    serializer.write_tag_sz(tag, tag_sz)?;
+    serializer.write_array_sz(len)?;
+    write_integer(
+        serializer,
+        &num_bigint::BigInt::from(exponent),
+        exponent_sz.map(BigIntEncoding::Int).as_ref(),
+    )?;
+    write_integer(serializer, mantissa, mantissa_encoding)?;
+    if indefinite {
+        serializer.write_special(cbor_event::Special::Break)?;
+    }
+    Ok(serializer)
+}
+
+fn read_big_fraction<R: BufRead + Seek>(
+    raw: &mut Deserializer<R>,
+    expected_tag: u64,
+) -> Result<(i64, num_bigint::BigInt, BigIntEncoding, BigFractionEncoding), DeserializeError> {
+    let (tag, tag_sz) = raw.tag_sz()?;
+    if tag != expected_tag {
+        return Err(DeserializeFailure::TagMismatch {
+            found: tag,
+            expected: expected_tag,
+        }
+        .into());
+    }
+    let len = raw.array_sz()?;
+    if let cbor_event::LenSz::Len(n, _sz) = len {
+        if n != 2 {
+            return Err(DeserializeFailure::DefiniteLenMismatch(n, Some(2)).into());
+        }
+    }
+    let (exponent, exponent_sz) = match raw.cbor_type()? {
+        cbor_event::Type::UnsignedInteger => {
+            let (x, sz) = raw.unsigned_integer_sz()?;
+            (i64::try_from(x).ok(), sz)
+        }
+        cbor_event::Type::NegativeInteger => {
+            let (x, sz) = raw.negative_integer_sz()?;
+            (i64::try_from(x).ok(), sz)
+        }
+        other => {
+            return Err(
+                cbor_event::Error::Expected(cbor_event::Type::UnsignedInteger, other).into(),
+            )
+        }
+    };
+    let exponent = exponent.ok_or_else(|| {
+        DeserializeError::new(
+            "exponent",
+            DeserializeFailure::InvalidStructure(Box::new(BigIntError::OutOfBounds)),
+        )
+    })?;
+    let (mantissa, mantissa_encoding) = read_integer(raw).map_err(|e| e.annotate("mantissa"))?;
+    if let cbor_event::LenSz::Indefinite = len {
+        match raw.special()? {
+            cbor_event::Special::Break => (),
+            _ => return Err(DeserializeFailure::EndingBreakMissing.into()),
+        }
+    }
+    Ok((
+        exponent,
+        mantissa,
+        mantissa_encoding,
+        BigFractionEncoding {
+            tag: tag_sz,
+            len,
+            exponent: exponent_sz,
+        },
+    ))
+}
+
+// decfrac/bigfloat exponents past this (in either direction) aren't expanded into plain decimals
+// as it would take time/memory exponential in the exponent, which can come from untrusted input
+const MAX_EXPANDED_EXPONENT: u64 = 4096;
+
+// Parses [+-]digits[.digits][(e|E)[+-]digits] into (mantissa, base 10 exponent)
+fn parse_decimal(s: &str) -> Result<(num_bigint::BigInt, i64), BigIntError> {
+    let (number, exponent) = match s.find(|c: char| c == 'e' || c == 'E') {
+        Some(i) => (
+            &s[..i],
+            i64::from_str(&s[i + 1..]).map_err(|_e| BigIntError::InvalidDecimal)?,
+        ),
+        None => (s, 0),
+    };
+    let (int_part, frac_part) = number.split_once('.').unwrap_or((number, ""));
+    if !frac_part.chars().all(|c| c.is_ascii_digit()) {
+        return Err(BigIntError::InvalidDecimal);
+    }
+    let mantissa = num_bigint::BigInt::from_str(&format!("{}{}", int_part, frac_part))
+        .map_err(|_e| BigIntError::InvalidDecimal)?;
+    let exponent = exponent
+        .checked_sub(frac_part.len() as i64)
+        .ok_or(BigIntError::InvalidDecimal)?;
+    Ok((mantissa, exponent))
+}
+
+// Formats x * 10^-scale as a plain decimal e.g. (1234, 2) -> 12.34
+fn format_scaled(x: &num_bigint::BigInt, scale: u32) -> String {
+    let sign = if x.sign() == num_bigint::Sign::Minus {
+        "-"
+    } else {
+        ""
+    };
+    let digits = x.magnitude().to_string();
+    let scale = scale as usize;
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let digits = if digits.len() <= scale {
+        format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, int_part, frac_part)
+}
+
+impl BigUInt {
+    pub fn as_biguint(&self) -> &num_bigint::BigUint {
+        &self.num
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(&self.num).ok()
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        u128::try_from(&self.num).ok()
+    }
+}
+
+impl From<u64> for BigUInt {
+    fn from(x: u64) -> Self {
+        Self::from(num_bigint::BigUint::from(x))
+    }
+}
+
+impl From<u128> for BigUInt {
+    fn from(x: u128) -> Self {
+        Self::from(num_bigint::BigUint::from(x))
+    }
+}
+
+impl TryFrom<num_bigint::BigInt> for BigUInt {
+    type Error = BigIntError;
+
+    fn try_from(x: num_bigint::BigInt) -> Result<Self, Self::Error> {
+        num_bigint::BigUint::try_from(x)
+            .map(Self::from)
+            .map_err(|_e| BigIntError::OutOfBounds)
+    }
+}
+
+impl From<BigUInt> for num_bigint::BigUint {
+    fn from(x: BigUInt) -> Self {
+        x.num
+    }
+}
+
+impl From<BigUInt> for num_bigint::BigInt {
+    fn from(x: BigUInt) -> Self {
+        num_bigint::BigInt::from(x.num)
+    }
+}
+
+impl TryFrom<BigUInt> for u64 {
+    type Error = BigIntError;
+
+    fn try_from(x: BigUInt) -> Result<Self, Self::Error> {
+        x.as_u64().ok_or(BigIntError::OutOfBounds)
+    }
+}
+
+impl TryFrom<BigUInt> for u128 {
+    type Error = BigIntError;
+
+    fn try_from(x: BigUInt) -> Result<Self, Self::Error> {
+        x.as_u128().ok_or(BigIntError::OutOfBounds)
+    }
+}
+
+impl TryFrom<BigInt> for BigUInt {
+    type Error = BigIntError;
+
+    fn try_from(x: BigInt) -> Result<Self, Self::Error> {
+        num_bigint::BigUint::try_from(x.num)
+            .map(Self::from)
+            .map_err(|_e| BigIntError::OutOfBounds)
+    }
+}
+
+impl std::fmt::Display for BigUInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.num)
+    }
+}
+
+impl FromStr for BigUInt {
+    type Err = BigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        num_bigint::BigUint::from_str(s)
+            .map(Self::from)
+            .map_err(BigIntError::Parsing)
+    }
+}
+
+impl BigInt {
+    pub fn as_bigint(&self) -> &num_bigint::BigInt {
+        &self.num
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(&self.num).ok()
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        i64::try_from(&self.num).ok()
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        i128::try_from(&self.num).ok()
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(x: u64) -> Self {
+        Self::from(num_bigint::BigInt::from(x))
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(x: i64) -> Self {
+        Self::from(num_bigint::BigInt::from(x))
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(x: i128) -> Self {
+        Self::from(num_bigint::BigInt::from(x))
+    }
+}
+
+impl From<BigUInt> for BigInt {
+    fn from(x: BigUInt) -> Self {
+        Self::from(num_bigint::BigInt::from(x.num))
+    }
+}
+
+impl From<num_bigint::BigUint> for BigInt {
+    fn from(x: num_bigint::BigUint) -> Self {
+        Self::from(num_bigint::BigInt::from(x))
+    }
+}
+
+impl From<BigInt> for num_bigint::BigInt {
+    fn from(x: BigInt) -> Self {
+        x.num
+    }
+}
+
+impl TryFrom<BigInt> for num_bigint::BigUint {
+    type Error = BigIntError;
+
+    fn try_from(x: BigInt) -> Result<Self, Self::Error> {
+        num_bigint::BigUint::try_from(x.num).map_err(|_e| BigIntError::OutOfBounds)
+    }
+}
+
+impl TryFrom<BigInt> for u64 {
+    type Error = BigIntError;
+
+    fn try_from(x: BigInt) -> Result<Self, Self::Error> {
+        x.as_u64().ok_or(BigIntError::OutOfBounds)
+    }
+}
+
+impl TryFrom<BigInt> for i64 {
+    type Error = BigIntError;
+
+    fn try_from(x: BigInt) -> Result<Self, Self::Error> {
+        x.as_i64().ok_or(BigIntError::OutOfBounds)
+    }
+}
+
+impl TryFrom<BigInt> for i128 {
+    type Error = BigIntError;
+
+    fn try_from(x: BigInt) -> Result<Self, Self::Error> {
+        x.as_i128().ok_or(BigIntError::OutOfBounds)
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.num)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = BigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        num_bigint::BigInt::from_str(s)
+            .map(Self::from)
+            .map_err(BigIntError::Parsing)
+    }
+}
+
+impl DecimalFraction {
+    /// Base 10 exponent i.e. the value is `mantissa * 10^exponent`
+    pub fn exponent(&self) -> i64 {
+        self.exponent
+    }
+
+    pub fn mantissa(&self) -> &BigInt {
+        &self.mantissa
+    }
+}
+
+/// Plain decimal (e.g. `12.34`) for negative exponents and `{mantissa}e{exponent}` for positive
+/// (or very negative) ones
+impl std::fmt::Display for DecimalFraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exponent <= 0 && self.exponent.unsigned_abs() <= MAX_EXPANDED_EXPONENT {
+            let scale = self.exponent.unsigned_abs() as u32;
+            write!(f, "{}", format_scaled(&self.mantissa.num, scale))
+        } else {
+            write!(f, "{}e{}", self.mantissa, self.exponent)
+        }
+    }
+}
+
+/// Accepts decimals like `12.34`, `-0.5` or `1.5e3`.
+/// The number of decimals given is kept e.g. `1.50` is `150 * 10^-2`
+impl FromStr for DecimalFraction {
+    type Err = BigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mantissa, exponent) = parse_decimal(s)?;
+        Ok(Self::new(BigInt::from(mantissa), exponent))
+    }
+}
+
+impl BigFloat {
+    /// Base 2 exponent i.e. the value is `mantissa * 2^exponent`
+    pub fn exponent(&self) -> i64 {
+        self.exponent
+    }
+
+    pub fn mantissa(&self) -> &BigInt {
+        &self.mantissa
+    }
+}
+
+/// Exact plain decimal e.g. `0.75` for `3 * 2^-2`
+/// or `{mantissa}*2^{exponent}` for very big/small exponents
+impl std::fmt::Display for BigFloat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shift = self.exponent.unsigned_abs();
+        if shift > MAX_EXPANDED_EXPONENT {
+            write!(f, "{}*2^{}", self.mantissa, self.exponent)
+        } else if self.exponent >= 0 {
+            write!(f, "{}", &self.mantissa.num << shift as usize)
+        } else {
+            // x * 2^-k = x * 5^k / 10^k
+            let scale = shift as u32;
+            write!(
+                f,
+                "{}",
+                format_scaled(
+                    &(&self.mantissa.num * num_bigint::BigInt::from(5u8).pow(scale)),
+                    scale
+                )
+            )
+        }
+    }
+}
+
+/// Accepts decimals like `0.75` or `-1.5e3` as long as they are exactly representable in base 2.
+/// Very big/small exponents (e.g. `1e999999999`) are out of bounds as converting them is too slow.
+impl FromStr for BigFloat {
+    type Err = BigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mantissa, exponent) = parse_decimal(s)?;
+        if exponent.unsigned_abs() > MAX_EXPANDED_EXPONENT {
+            return Err(BigIntError::OutOfBounds);
+        }
+        let scale = exponent.unsigned_abs() as u32;
+        if exponent >= 0 {
+            // 10^e = 2^e * 5^e
+            Ok(Self::new(
+                BigInt::from(mantissa * num_bigint::BigInt::from(5u8).pow(scale)),
+                exponent,
+            ))
+        } else {
+            // 10^-k = 2^-k / 5^k so the mantissa must be divisible by 5^k
+            let divisor = num_bigint::BigInt::from(5u8).pow(scale);
+            if (&mantissa % &divisor).sign() != num_bigint::Sign::NoSign {
+                return Err(BigIntError::Inexact);
+            }
+            Ok(Self::new(BigInt::from(mantissa / divisor), exponent))
+        }
+    }
+}
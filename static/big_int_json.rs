@@ -0,0 +1,107 @@
+
+// JSON representation of big integers is their decimal string, as JSON numbers can't hold them losslessly
+
+impl serde::Serialize for BigUInt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for BigUInt {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let s = <String as serde::de::Deserialize>::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(|_e| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&s),
+                &"unsigned integer as a decimal string",
+            )
+        })
+    }
+}
+
+impl serde::Serialize for BigInt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for BigInt {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let s = <String as serde::de::Deserialize>::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(|_e| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&s),
+                &"integer as a decimal string",
+            )
+        })
+    }
+}
+
+// decfrac/bigfloat are kept as their parts as the exponent can't always be recovered from a decimal string
+#[derive(serde::Deserialize, serde::Serialize)]
+struct BigFractionJson {
+    mantissa: BigInt,
+    exponent: i64,
+}
+
+impl serde::Serialize for DecimalFraction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(
+            &BigFractionJson {
+                mantissa: self.mantissa.clone(),
+                exponent: self.exponent,
+            },
+            serializer,
+        )
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for DecimalFraction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let json = <BigFractionJson as serde::de::Deserialize>::deserialize(deserializer)?;
+        Ok(Self::new(json.mantissa, json.exponent))
+    }
+}
+
+impl serde::Serialize for BigFloat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(
+            &BigFractionJson {
+                mantissa: self.mantissa.clone(),
+                exponent: self.exponent,
+            },
+            serializer,
+        )
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for BigFloat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let json = <BigFractionJson as serde::de::Deserialize>::deserialize(deserializer)?;
+        Ok(Self::new(json.mantissa, json.exponent))
+    }
+}
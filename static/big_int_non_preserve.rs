@@ -0,0 +1,194 @@
+
+/// Arbitrary-precision unsigned integer used for the CDDL `biguint`/`unsigned` types.
+/// Encoded as a uint when it fits in 64 bits and as a tag 2 bignum otherwise.
+/// `biguint` is always a tag 2 bignum (see `serialize_biguint`/`deserialize_biguint`).
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigUInt {
+    num: num_bigint::BigUint,
+}
+
+impl From<num_bigint::BigUint> for BigUInt {
+    fn from(num: num_bigint::BigUint) -> Self {
+        Self { num }
+    }
+}
+
+impl cbor_event::se::Serialize for BigUInt {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_integer(
+            serializer,
+            &num_bigint::BigInt::from(self.num.clone()),
+            None,
+        )
+    }
+}
+
+impl Deserialize for BigUInt {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        let (num, _encoding) = read_integer(raw).map_err(|e| e.annotate("BigUInt"))?;
+        num_bigint::BigUint::try_from(num)
+            .map(Self::from)
+            .map_err(|_e| {
+                DeserializeError::new(
+                    "BigUInt",
+                    DeserializeFailure::InvalidStructure(Box::new(BigIntError::OutOfBounds)),
+                )
+            })
+    }
+}
+
+impl BigUInt {
+    /// Serializes as a CDDL `biguint` i.e. always as a tag 2 bignum
+    pub fn serialize_biguint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_bignum(serializer, &num_bigint::BigInt::from(x.num.clone()), None)
+    }
+
+    /// Deserializes a CDDL `biguint` i.e. only a tag 2 bignum
+    pub fn deserialize_biguint<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+    ) -> Result<Self, DeserializeError> {
+        let (num, _encoding) = read_bignum(raw).map_err(|e| e.annotate("biguint"))?;
+        check_bignum_sign(&num, false).map_err(|e| e.annotate("biguint"))?;
+        Ok(Self::from(num.into_parts().1))
+    }
+}
+
+/// Arbitrary-precision signed integer used for the CDDL `bignint`/`bigint`/`integer` types.
+/// Encoded as a uint/nint when it fits in 64 bits and as a tag 2/3 bignum otherwise.
+/// `bignint`/`bigint` are always tag 3 / tag 2 or 3 bignums (see `serialize_bigint` etc).
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigInt {
+    num: num_bigint::BigInt,
+}
+
+impl From<num_bigint::BigInt> for BigInt {
+    fn from(num: num_bigint::BigInt) -> Self {
+        Self { num }
+    }
+}
+
+impl cbor_event::se::Serialize for BigInt {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_integer(serializer, &self.num, None)
+    }
+}
+
+impl Deserialize for BigInt {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_integer(raw)
+            .map(|(num, _encoding)| Self::from(num))
+            .map_err(|e| e.annotate("BigInt"))
+    }
+}
+
+impl BigInt {
+    /// Serializes as a CDDL `bignint` i.e. always as a tag 3 bignum. Fails if not negative.
+    pub fn serialize_bignint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        if x.num.sign() != num_bigint::Sign::Minus {
+            return Err(bignint_sign_error(&x.num));
+        }
+        write_bignum(serializer, &x.num, None)
+    }
+
+    /// Deserializes a CDDL `bignint` i.e. only a tag 3 bignum
+    pub fn deserialize_bignint<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+    ) -> Result<Self, DeserializeError> {
+        let (num, _encoding) = read_bignum(raw).map_err(|e| e.annotate("bignint"))?;
+        check_bignum_sign(&num, true).map_err(|e| e.annotate("bignint"))?;
+        Ok(Self::from(num))
+    }
+
+    /// Serializes as a CDDL `bigint` i.e. always as a tag 2/3 bignum
+    pub fn serialize_bigint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_bignum(serializer, &x.num, None)
+    }
+
+    /// Deserializes a CDDL `bigint` i.e. only a tag 2/3 bignum
+    pub fn deserialize_bigint<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+    ) -> Result<Self, DeserializeError> {
+        read_bignum(raw)
+            .map(|(num, _encoding)| Self::from(num))
+            .map_err(|e| e.annotate("bigint"))
+    }
+}
+
+/// Decimal fraction used for the CDDL `decfrac` type i.e. `mantissa * 10^exponent`
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DecimalFraction {
+    exponent: i64,
+    mantissa: BigInt,
+}
+
+impl DecimalFraction {
+    pub fn new(mantissa: BigInt, exponent: i64) -> Self {
+        Self { exponent, mantissa }
+    }
+}
+
+impl cbor_event::se::Serialize for DecimalFraction {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_big_fraction(serializer, 4, self.exponent, &self.mantissa.num, None, None)
+    }
+}
+
+impl Deserialize for DecimalFraction {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_big_fraction(raw, 4)
+            .map(|(exponent, mantissa, _mantissa_encoding, _encoding)| {
+                Self::new(BigInt::from(mantissa), exponent)
+            })
+            .map_err(|e| e.annotate("DecimalFraction"))
+    }
+}
+
+/// Binary fraction used for the CDDL `bigfloat` type i.e. `mantissa * 2^exponent`
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigFloat {
+    exponent: i64,
+    mantissa: BigInt,
+}
+
+impl BigFloat {
+    pub fn new(mantissa: BigInt, exponent: i64) -> Self {
+        Self { exponent, mantissa }
+    }
+}
+
+impl cbor_event::se::Serialize for BigFloat {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_big_fraction(serializer, 5, self.exponent, &self.mantissa.num, None, None)
+    }
+}
+
+impl Deserialize for BigFloat {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_big_fraction(raw, 5)
+            .map(|(exponent, mantissa, _mantissa_encoding, _encoding)| {
+                Self::new(BigInt::from(mantissa), exponent)
+            })
+            .map_err(|e| e.annotate("BigFloat"))
+    }
+}
@@ -0,0 +1,198 @@
+
+/// Arbitrary-precision unsigned integer used for the CDDL `biguint`/`unsigned` types.
+/// Encoded as a uint when it fits in 64 bits and as a tag 2 bignum otherwise.
+/// `biguint` is always a tag 2 bignum (see `serialize_biguint`/`deserialize_biguint`).
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigUInt {
+    num: num_bigint::BigUint,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<BigIntEncoding>,
+}
+
+impl From<num_bigint::BigUint> for BigUInt {
+    fn from(num: num_bigint::BigUint) -> Self {
+        Self {
+            num,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for BigUInt {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        let (num, encoding) = read_integer(raw).map_err(|e| e.annotate("BigUInt"))?;
+        num_bigint::BigUint::try_from(num)
+            .map(|num| Self {
+                num,
+                encoding: Some(encoding),
+            })
+            .map_err(|_e| {
+                DeserializeError::new(
+                    "BigUInt",
+                    DeserializeFailure::InvalidStructure(Box::new(BigIntError::OutOfBounds)),
+                )
+            })
+    }
+}
+
+impl BigUInt {
+    /// Deserializes a CDDL `biguint` i.e. only a tag 2 bignum
+    pub fn deserialize_biguint<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+    ) -> Result<Self, DeserializeError> {
+        let (num, encoding) = read_bignum(raw).map_err(|e| e.annotate("biguint"))?;
+        check_bignum_sign(&num, false).map_err(|e| e.annotate("biguint"))?;
+        Ok(Self {
+            num: num.into_parts().1,
+            encoding: Some(encoding),
+        })
+    }
+}
+
+/// Arbitrary-precision signed integer used for the CDDL `bignint`/`bigint`/`integer` types.
+/// Encoded as a uint/nint when it fits in 64 bits and as a tag 2/3 bignum otherwise.
+/// `bignint`/`bigint` are always tag 3 / tag 2 or 3 bignums (see `serialize_bigint` etc).
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigInt {
+    num: num_bigint::BigInt,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<BigIntEncoding>,
+}
+
+impl From<num_bigint::BigInt> for BigInt {
+    fn from(num: num_bigint::BigInt) -> Self {
+        Self {
+            num,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for BigInt {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_integer(raw)
+            .map(|(num, encoding)| Self {
+                num,
+                encoding: Some(encoding),
+            })
+            .map_err(|e| e.annotate("BigInt"))
+    }
+}
+
+impl BigInt {
+    /// Deserializes a CDDL `bignint` i.e. only a tag 3 bignum
+    pub fn deserialize_bignint<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+    ) -> Result<Self, DeserializeError> {
+        let (num, encoding) = read_bignum(raw).map_err(|e| e.annotate("bignint"))?;
+        check_bignum_sign(&num, true).map_err(|e| e.annotate("bignint"))?;
+        Ok(Self {
+            num,
+            encoding: Some(encoding),
+        })
+    }
+
+    /// Deserializes a CDDL `bigint` i.e. only a tag 2/3 bignum
+    pub fn deserialize_bigint<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+    ) -> Result<Self, DeserializeError> {
+        read_bignum(raw)
+            .map(|(num, encoding)| Self {
+                num,
+                encoding: Some(encoding),
+            })
+            .map_err(|e| e.annotate("bigint"))
+    }
+}
+
+/// Decimal fraction used for the CDDL `decfrac` type i.e. `mantissa * 10^exponent`
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DecimalFraction {
+    exponent: i64,
+    mantissa: BigInt,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<BigFractionEncoding>,
+}
+
+impl DecimalFraction {
+    pub fn new(mantissa: BigInt, exponent: i64) -> Self {
+        Self {
+            exponent,
+            mantissa,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for DecimalFraction {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_big_fraction(raw, 4)
+            .map(|(exponent, mantissa, mantissa_encoding, encoding)| Self {
+                exponent,
+                mantissa: BigInt {
+                    num: mantissa,
+                    encoding: Some(mantissa_encoding),
+                },
+                encoding: Some(encoding),
+            })
+            .map_err(|e| e.annotate("DecimalFraction"))
+    }
+}
+
+/// Binary fraction used for the CDDL `bigfloat` type i.e. `mantissa * 2^exponent`
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigFloat {
+    exponent: i64,
+    mantissa: BigInt,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<BigFractionEncoding>,
+}
+
+impl BigFloat {
+    pub fn new(mantissa: BigInt, exponent: i64) -> Self {
+        Self {
+            exponent,
+            mantissa,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for BigFloat {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_big_fraction(raw, 5)
+            .map(|(exponent, mantissa, mantissa_encoding, encoding)| Self {
+                exponent,
+                mantissa: BigInt {
+                    num: mantissa,
+                    encoding: Some(mantissa_encoding),
+                },
+                encoding: Some(encoding),
+            })
+            .map_err(|e| e.annotate("BigFloat"))
+    }
+}
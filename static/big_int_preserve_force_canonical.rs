@@ -0,0 +1,98 @@
+
+impl Serialize for BigUInt {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let encoding = self.encoding.as_ref().filter(|_| !force_canonical);
+        write_integer(
+            serializer,
+            &num_bigint::BigInt::from(self.num.clone()),
+            encoding,
+        )
+    }
+}
+
+impl Serialize for BigInt {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let encoding = self.encoding.as_ref().filter(|_| !force_canonical);
+        write_integer(serializer, &self.num, encoding)
+    }
+}
+
+impl Serialize for DecimalFraction {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_big_fraction(
+            serializer,
+            4,
+            self.exponent,
+            &self.mantissa.num,
+            self.mantissa.encoding.as_ref().filter(|_| !force_canonical),
+            self.encoding.as_ref().filter(|_| !force_canonical),
+        )
+    }
+}
+
+impl Serialize for BigFloat {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_big_fraction(
+            serializer,
+            5,
+            self.exponent,
+            &self.mantissa.num,
+            self.mantissa.encoding.as_ref().filter(|_| !force_canonical),
+            self.encoding.as_ref().filter(|_| !force_canonical),
+        )
+    }
+}
+
+impl BigUInt {
+    /// Serializes as a CDDL `biguint` i.e. always as a tag 2 bignum
+    pub fn serialize_biguint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_bignum(
+            serializer,
+            &num_bigint::BigInt::from(x.num.clone()),
+            x.encoding.as_ref().filter(|_| !force_canonical),
+        )
+    }
+}
+
+impl BigInt {
+    /// Serializes as a CDDL `bignint` i.e. always as a tag 3 bignum. Fails if not negative.
+    pub fn serialize_bignint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        if x.num.sign() != num_bigint::Sign::Minus {
+            return Err(bignint_sign_error(&x.num));
+        }
+        write_bignum(serializer, &x.num, x.encoding.as_ref().filter(|_| !force_canonical))
+    }
+
+    /// Serializes as a CDDL `bigint` i.e. always as a tag 2/3 bignum
+    pub fn serialize_bigint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_bignum(serializer, &x.num, x.encoding.as_ref().filter(|_| !force_canonical))
+    }
+}
@@ -0,0 +1,89 @@
+
+impl cbor_event::se::Serialize for BigUInt {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_integer(
+            serializer,
+            &num_bigint::BigInt::from(self.num.clone()),
+            self.encoding.as_ref(),
+        )
+    }
+}
+
+impl cbor_event::se::Serialize for BigInt {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_integer(serializer, &self.num, self.encoding.as_ref())
+    }
+}
+
+impl cbor_event::se::Serialize for DecimalFraction {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_big_fraction(
+            serializer,
+            4,
+            self.exponent,
+            &self.mantissa.num,
+            self.mantissa.encoding.as_ref(),
+            self.encoding.as_ref(),
+        )
+    }
+}
+
+impl cbor_event::se::Serialize for BigFloat {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_big_fraction(
+            serializer,
+            5,
+            self.exponent,
+            &self.mantissa.num,
+            self.mantissa.encoding.as_ref(),
+            self.encoding.as_ref(),
+        )
+    }
+}
+
+impl BigUInt {
+    /// Serializes as a CDDL `biguint` i.e. always as a tag 2 bignum
+    pub fn serialize_biguint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_bignum(
+            serializer,
+            &num_bigint::BigInt::from(x.num.clone()),
+            x.encoding.as_ref(),
+        )
+    }
+}
+
+impl BigInt {
+    /// Serializes as a CDDL `bignint` i.e. always as a tag 3 bignum. Fails if not negative.
+    pub fn serialize_bignint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        if x.num.sign() != num_bigint::Sign::Minus {
+            return Err(bignint_sign_error(&x.num));
+        }
+        write_bignum(serializer, &x.num, x.encoding.as_ref())
+    }
+
+    /// Serializes as a CDDL `bigint` i.e. always as a tag 2/3 bignum
+    pub fn serialize_bigint<'se, W: Write>(
+        serializer: &'se mut Serializer<W>,
+        x: &Self,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_bignum(serializer, &x.num, x.encoding.as_ref())
+    }
+}
@@ -0,0 +1,57 @@
+
+impl schemars::JsonSchema for BigUInt {
+    fn schema_name() -> String {
+        String::from("BigUInt")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <String as schemars::JsonSchema>::json_schema(gen)
+    }
+}
+
+impl schemars::JsonSchema for BigInt {
+    fn schema_name() -> String {
+        String::from("BigInt")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <String as schemars::JsonSchema>::json_schema(gen)
+    }
+}
+
+fn big_fraction_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    let mut schema = schemars::schema::SchemaObject {
+        instance_type: Some(schemars::schema::InstanceType::Object.into()),
+        ..Default::default()
+    };
+    let object = schema.object();
+    object
+        .properties
+        .insert(String::from("mantissa"), gen.subschema_for::<BigInt>());
+    object
+        .properties
+        .insert(String::from("exponent"), gen.subschema_for::<i64>());
+    object.required.insert(String::from("mantissa"));
+    object.required.insert(String::from("exponent"));
+    schema.into()
+}
+
+impl schemars::JsonSchema for DecimalFraction {
+    fn schema_name() -> String {
+        String::from("DecimalFraction")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        big_fraction_schema(gen)
+    }
+}
+
+impl schemars::JsonSchema for BigFloat {
+    fn schema_name() -> String {
+        String::from("BigFloat")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        big_fraction_schema(gen)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::*;
+use crate::serialization::*;
+use cbor_event::de::Deserializer;
+use cbor_event::se::Serializer;
+use std::convert::{TryFrom, TryInto};
+use std::io::{BufRead, Seek, Write};
+
+/// Arrays/maps/tags in a CBORValue can't be nested deeper than this when deserializing
+/// so that untrusted input can't overflow the stack
+pub const CBOR_VALUE_MAX_DEPTH: usize = 128;
+
+/// Major type 7 values that can appear on their own (i.e. everything but Break)
+#[derive(Clone, Copy, Debug)]
+pub enum CBORSpecial {
+    Bool(bool),
+    Float(f64),
+    Unassigned(u8),
+    Null,
+    Undefined,
+}
+
+impl CBORSpecial {
+    // floats don't implement Eq/Ord/Hash so we compare on their bit representation instead
+    fn cmp_key(&self) -> (u8, u64) {
+        match self {
+            Self::Bool(b) => (0, *b as u64),
+            Self::Float(f) => (1, f.to_bits()),
+            Self::Unassigned(u) => (2, *u as u64),
+            Self::Null => (3, 0),
+            Self::Undefined => (4, 0),
+        }
+    }
+}
+
+impl PartialEq for CBORSpecial {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp_key() == other.cmp_key()
+    }
+}
+
+impl Eq for CBORSpecial {}
+
+impl PartialOrd for CBORSpecial {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CBORSpecial {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.cmp_key().cmp(&other.cmp_key())
+    }
+}
+
+impl std::hash::Hash for CBORSpecial {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.cmp_key().hash(state)
+    }
+}
+
+impl From<CBORSpecial> for cbor_event::Special {
+    fn from(special: CBORSpecial) -> Self {
+        match special {
+            CBORSpecial::Bool(b) => cbor_event::Special::Bool(b),
+            CBORSpecial::Float(f) => cbor_event::Special::Float(f),
+            CBORSpecial::Unassigned(u) => cbor_event::Special::Unassigned(u),
+            CBORSpecial::Null => cbor_event::Special::Null,
+            CBORSpecial::Undefined => cbor_event::Special::Undefined,
+        }
+    }
+}
+
+impl TryFrom<cbor_event::Special> for CBORSpecial {
+    type Error = DeserializeError;
+
+    fn try_from(special: cbor_event::Special) -> Result<Self, Self::Error> {
+        match special {
+            cbor_event::Special::Bool(b) => Ok(Self::Bool(b)),
+            cbor_event::Special::Float(f) => Ok(Self::Float(f)),
+            cbor_event::Special::Unassigned(u) => Ok(Self::Unassigned(u)),
+            cbor_event::Special::Null => Ok(Self::Null),
+            cbor_event::Special::Undefined => Ok(Self::Undefined),
+            cbor_event::Special::Break => Err(DeserializeFailure::BreakInDefiniteLen.into()),
+        }
+    }
+}
+
+impl CBORValue {
+    pub fn as_uint(&self) -> Option<u64> {
+        match self {
+            Self::Uint { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Value as encoded in CBOR - note: a negative `x` here would be `|x + 1|` due to CBOR's `nint` encoding e.g. -5 is returned as 4.
+    pub fn as_nint(&self) -> Option<u64> {
+        match self {
+            Self::Nint { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&Vec<u8>> {
+        match self {
+            Self::Bytes { bytes, .. } => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&String> {
+        match self {
+            Self::Text { text, .. } => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<CBORValue>> {
+        match self {
+            Self::Array { elements, .. } => Some(elements),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&CBORValueMap> {
+        match self {
+            Self::Map { entries, .. } => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns the tag and the value it wraps
+    pub fn as_tagged(&self) -> Option<(u64, &CBORValue)> {
+        match self {
+            Self::Tag { tag, value, .. } => Some((*tag, value)),
+            _ => None,
+        }
+    }
+
+    pub fn as_special(&self) -> Option<CBORSpecial> {
+        match self {
+            Self::Special { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    // for reporting this value when used as a map key in errors
+    fn to_key(&self) -> Key {
+        match self {
+            Self::Uint { value, .. } => Key::Uint(*value),
+            Self::Nint { value, .. } => Key::Nint(-1 - *value as i128),
+            Self::Bytes { bytes, .. } => Key::Bytes(bytes.clone()),
+            Self::Text { text, .. } => Key::Str(text.clone()),
+            Self::Tag { tag, value, .. } => Key::Tagged(*tag, Box::new(value.to_key())),
+            Self::Special { value, .. } => Key::Special((*value).into()),
+            Self::Array { .. } | Self::Map { .. } => {
+                Key::Str(String::from("some complicated/unsupported type"))
+            }
+        }
+    }
+}
+
+impl From<CBORSpecial> for CBORValue {
+    fn from(special: CBORSpecial) -> Self {
+        Self::new_special(special)
+    }
+}
+
+impl TaggedValue {
+    pub fn tag(&self) -> u64 {
+        self.tag
+    }
+
+    /// Value wrapped by the tag
+    pub fn value(&self) -> &CBORValue {
+        &self.value
+    }
+}
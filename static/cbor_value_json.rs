@@ -0,0 +1,132 @@
+
+// JSON representation of CBORValue. Encoding details are not represented in JSON.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CBORValueJson {
+    Uint(u64),
+    Nint(u64),
+    /// hex-encoded
+    Bytes(String),
+    Text(String),
+    Array(Vec<CBORValueJson>),
+    Map(Vec<(CBORValueJson, CBORValueJson)>),
+    Tag(u64, Box<CBORValueJson>),
+    Bool(bool),
+    Float(f64),
+    Unassigned(u8),
+    Null,
+    Undefined,
+}
+
+impl From<&CBORValue> for CBORValueJson {
+    fn from(value: &CBORValue) -> Self {
+        match value {
+            CBORValue::Uint { value, .. } => Self::Uint(*value),
+            CBORValue::Nint { value, .. } => Self::Nint(*value),
+            CBORValue::Bytes { bytes, .. } => Self::Bytes(hex::encode(bytes)),
+            CBORValue::Text { text, .. } => Self::Text(text.clone()),
+            CBORValue::Array { elements, .. } => {
+                Self::Array(elements.iter().map(Self::from).collect())
+            }
+            CBORValue::Map { entries, .. } => Self::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (Self::from(k), Self::from(v)))
+                    .collect(),
+            ),
+            CBORValue::Tag { tag, value, .. } => {
+                Self::Tag(*tag, Box::new(Self::from(value.as_ref())))
+            }
+            CBORValue::Special { value, .. } => match value {
+                CBORSpecial::Bool(b) => Self::Bool(*b),
+                CBORSpecial::Float(f) => Self::Float(*f),
+                CBORSpecial::Unassigned(u) => Self::Unassigned(*u),
+                CBORSpecial::Null => Self::Null,
+                CBORSpecial::Undefined => Self::Undefined,
+            },
+        }
+    }
+}
+
+impl TryFrom<CBORValueJson> for CBORValue {
+    type Error = String;
+
+    fn try_from(json: CBORValueJson) -> Result<Self, Self::Error> {
+        match json {
+            CBORValueJson::Uint(value) => Ok(Self::new_uint(value)),
+            CBORValueJson::Nint(value) => Ok(Self::new_nint(value)),
+            CBORValueJson::Bytes(hex_str) => hex::decode(&hex_str)
+                .map(Self::new_bytes)
+                .map_err(|e| format!("invalid hex bytes {hex_str}: {e}")),
+            CBORValueJson::Text(text) => Ok(Self::new_text(text)),
+            CBORValueJson::Array(elements) => elements
+                .into_iter()
+                .map(Self::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map(Self::new_array),
+            CBORValueJson::Map(json_entries) => {
+                let mut entries = CBORValueMap::new();
+                for (k, v) in json_entries {
+                    entries.insert(Self::try_from(k)?, Self::try_from(v)?);
+                }
+                Ok(Self::new_map(entries))
+            }
+            CBORValueJson::Tag(tag, value) => Ok(Self::new_tag(tag, Self::try_from(*value)?)),
+            CBORValueJson::Bool(b) => Ok(Self::new_special(CBORSpecial::Bool(b))),
+            CBORValueJson::Float(f) => Ok(Self::new_special(CBORSpecial::Float(f))),
+            CBORValueJson::Unassigned(u) => Ok(Self::new_special(CBORSpecial::Unassigned(u))),
+            CBORValueJson::Null => Ok(Self::new_special(CBORSpecial::Null)),
+            CBORValueJson::Undefined => Ok(Self::new_special(CBORSpecial::Undefined)),
+        }
+    }
+}
+
+impl serde::Serialize for CBORValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        CBORValueJson::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for CBORValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let json = <CBORValueJson as serde::de::Deserialize>::deserialize(deserializer)?;
+        Self::try_from(json).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct TaggedValueJson {
+    tag: u64,
+    value: CBORValue,
+}
+
+impl serde::Serialize for TaggedValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(
+            &TaggedValueJson {
+                tag: self.tag,
+                value: self.value.clone(),
+            },
+            serializer,
+        )
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for TaggedValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let json = <TaggedValueJson as serde::de::Deserialize>::deserialize(deserializer)?;
+        Ok(Self::new(json.tag, json.value))
+    }
+}
@@ -0,0 +1,229 @@
+use cbor_event::se::Serialize;
+use std::collections::BTreeMap;
+
+pub type CBORValueMap = BTreeMap<CBORValue, CBORValue>;
+
+/// Generic CBOR value used for the CDDL `any` type
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CBORValue {
+    Uint {
+        value: u64,
+    },
+    /// `value` is as encoded in CBOR i.e. `|x + 1|` for a negative `x`
+    Nint {
+        value: u64,
+    },
+    Bytes {
+        bytes: Vec<u8>,
+    },
+    Text {
+        text: String,
+    },
+    Array {
+        elements: Vec<CBORValue>,
+    },
+    Map {
+        entries: CBORValueMap,
+    },
+    Tag {
+        tag: u64,
+        value: Box<CBORValue>,
+    },
+    Special {
+        value: CBORSpecial,
+    },
+}
+
+impl CBORValue {
+    pub fn new_uint(value: u64) -> Self {
+        Self::Uint { value }
+    }
+
+    /// * `value` - Value as encoded in CBOR - note: a negative `x` here would be `|x + 1|` due to CBOR's `nint` encoding e.g. to represent -5, pass in 4.
+    pub fn new_nint(value: u64) -> Self {
+        Self::Nint { value }
+    }
+
+    pub fn new_bytes(bytes: Vec<u8>) -> Self {
+        Self::Bytes { bytes }
+    }
+
+    pub fn new_text(text: String) -> Self {
+        Self::Text { text }
+    }
+
+    pub fn new_array(elements: Vec<CBORValue>) -> Self {
+        Self::Array { elements }
+    }
+
+    pub fn new_map(entries: CBORValueMap) -> Self {
+        Self::Map { entries }
+    }
+
+    pub fn new_tag(tag: u64, value: CBORValue) -> Self {
+        Self::Tag {
+            tag,
+            value: Box::new(value),
+        }
+    }
+
+    pub fn new_special(value: CBORSpecial) -> Self {
+        Self::Special { value }
+    }
+}
+
+impl cbor_event::se::Serialize for CBORValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        match self {
+            Self::Uint { value } => serializer.write_unsigned_integer(*value),
+            Self::Nint { value } => serializer.write_negative_integer_sz(
+                -((*value as i128) + 1),
+                cbor_event::Sz::canonical(*value),
+            ),
+            Self::Bytes { bytes } => serializer.write_bytes(bytes),
+            Self::Text { text } => serializer.write_text(text),
+            Self::Array { elements } => {
+                serializer.write_array(cbor_event::Len::Len(elements.len() as u64))?;
+                for element in elements {
+                    element.serialize(serializer)?;
+                }
+                Ok(serializer)
+            }
+            Self::Map { entries } => {
+                serializer.write_map(cbor_event::Len::Len(entries.len() as u64))?;
+                for (key, value) in entries {
+                    key.serialize(serializer)?;
+                    value.serialize(serializer)?;
+                }
+                Ok(serializer)
+            }
+            Self::Tag { tag, value } => {
+                serializer.write_tag(*tag)?;
+                value.serialize(serializer)
+            }
+            Self::Special { value } => serializer.write_special((*value).into()),
+        }
+    }
+}
+
+impl Deserialize for CBORValue {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        Self::deserialize_nested(raw, 0).map_err(|e| e.annotate("CBORValue"))
+    }
+}
+
+impl CBORValue {
+    // depth is how many arrays/maps/tags the value is inside of
+    fn deserialize_nested<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+        depth: usize,
+    ) -> Result<Self, DeserializeError> {
+        if depth > CBOR_VALUE_MAX_DEPTH {
+            return Err(DeserializeFailure::MaxDepthExceeded(CBOR_VALUE_MAX_DEPTH).into());
+        }
+        match raw.cbor_type()? {
+            cbor_event::Type::UnsignedInteger => Ok(Self::new_uint(raw.unsigned_integer()?)),
+            cbor_event::Type::NegativeInteger => Ok(Self::new_nint(
+                (-1 - raw.negative_integer_sz().map(|(x, _enc)| x)?) as u64,
+            )),
+            cbor_event::Type::Bytes => Ok(Self::new_bytes(raw.bytes()?)),
+            cbor_event::Type::Text => Ok(Self::new_text(raw.text()?)),
+            cbor_event::Type::Array => {
+                let len = raw.array()?;
+                let mut elements = Vec::new();
+                while match len {
+                    cbor_event::Len::Len(n) => (elements.len() as u64) < n,
+                    cbor_event::Len::Indefinite => true,
+                } {
+                    if raw.cbor_type()? == cbor_event::Type::Special {
+                        let special = raw.special()?;
+                        if special == cbor_event::Special::Break {
+                            match len {
+                                cbor_event::Len::Len(_) => {
+                                    return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                                }
+                                cbor_event::Len::Indefinite => break,
+                            }
+                        }
+                        elements.push(Self::new_special(special.try_into()?));
+                    } else {
+                        elements.push(Self::deserialize_nested(raw, depth + 1)?);
+                    }
+                }
+                Ok(Self::new_array(elements))
+            }
+            cbor_event::Type::Map => {
+                let len = raw.map()?;
+                let mut entries = CBORValueMap::new();
+                while match len {
+                    cbor_event::Len::Len(n) => (entries.len() as u64) < n,
+                    cbor_event::Len::Indefinite => true,
+                } {
+                    let key = if raw.cbor_type()? == cbor_event::Type::Special {
+                        let special = raw.special()?;
+                        if special == cbor_event::Special::Break {
+                            match len {
+                                cbor_event::Len::Len(_) => {
+                                    return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                                }
+                                cbor_event::Len::Indefinite => break,
+                            }
+                        }
+                        Self::new_special(special.try_into()?)
+                    } else {
+                        Self::deserialize_nested(raw, depth + 1)?
+                    };
+                    let value = Self::deserialize_nested(raw, depth + 1)?;
+                    if entries.contains_key(&key) {
+                        return Err(DeserializeFailure::DuplicateKey(key.to_key()).into());
+                    }
+                    entries.insert(key, value);
+                }
+                Ok(Self::new_map(entries))
+            }
+            cbor_event::Type::Tag => {
+                let tag = raw.tag()?;
+                Ok(Self::new_tag(tag, Self::deserialize_nested(raw, depth + 1)?))
+            }
+            cbor_event::Type::Special => {
+                Ok(Self::new_special(raw.special()?.try_into()?))
+            }
+        }
+    }
+}
+
+/// Tagged value of any tag used for the CDDL `#6` / `#6(any)` types
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TaggedValue {
+    tag: u64,
+    value: CBORValue,
+}
+
+impl TaggedValue {
+    pub fn new(tag: u64, value: CBORValue) -> Self {
+        Self { tag, value }
+    }
+}
+
+impl cbor_event::se::Serialize for TaggedValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        serializer.write_tag(self.tag)?;
+        self.value.serialize(serializer)
+    }
+}
+
+impl Deserialize for TaggedValue {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        (|| -> Result<_, DeserializeError> {
+            let tag = raw.tag()?;
+            Ok(Self::new(tag, CBORValue::deserialize_nested(raw, 1)?))
+        })()
+        .map_err(|e| e.annotate("TaggedValue"))
+    }
+}
@@ -0,0 +1,290 @@
+use crate::ordered_hash_map::OrderedHashMap;
+
+pub type CBORValueMap = OrderedHashMap<CBORValue, CBORValue>;
+
+/// Generic CBOR value used for the CDDL `any` type
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(
+    Eq,
+    PartialEq,
+    Ord = "feature_allow_slow_enum",
+    PartialOrd = "feature_allow_slow_enum",
+    Hash
+)]
+pub enum CBORValue {
+    Uint {
+        value: u64,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: Option<cbor_event::Sz>,
+    },
+    /// `value` is as encoded in CBOR i.e. `|x + 1|` for a negative `x`
+    Nint {
+        value: u64,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: Option<cbor_event::Sz>,
+    },
+    Bytes {
+        bytes: Vec<u8>,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: StringEncoding,
+    },
+    Text {
+        text: String,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: StringEncoding,
+    },
+    Array {
+        elements: Vec<CBORValue>,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: LenEncoding,
+    },
+    Map {
+        entries: CBORValueMap,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: LenEncoding,
+    },
+    Tag {
+        tag: u64,
+        value: Box<CBORValue>,
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: Option<cbor_event::Sz>,
+    },
+    Special {
+        value: CBORSpecial,
+        /// Only used for `CBORSpecial::Float` to remember whether it was a half, single or double
+        #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore", Hash = "ignore")]
+        encoding: Option<FloatEncoding>,
+    },
+}
+
+impl CBORValue {
+    pub fn new_uint(value: u64) -> Self {
+        Self::Uint {
+            value,
+            encoding: None,
+        }
+    }
+
+    /// * `value` - Value as encoded in CBOR - note: a negative `x` here would be `|x + 1|` due to CBOR's `nint` encoding e.g. to represent -5, pass in 4.
+    pub fn new_nint(value: u64) -> Self {
+        Self::Nint {
+            value,
+            encoding: None,
+        }
+    }
+
+    pub fn new_bytes(bytes: Vec<u8>) -> Self {
+        Self::Bytes {
+            bytes,
+            encoding: StringEncoding::default(),
+        }
+    }
+
+    pub fn new_text(text: String) -> Self {
+        Self::Text {
+            text,
+            encoding: StringEncoding::default(),
+        }
+    }
+
+    pub fn new_array(elements: Vec<CBORValue>) -> Self {
+        Self::Array {
+            elements,
+            encoding: LenEncoding::default(),
+        }
+    }
+
+    pub fn new_map(entries: CBORValueMap) -> Self {
+        Self::Map {
+            entries,
+            encoding: LenEncoding::default(),
+        }
+    }
+
+    pub fn new_tag(tag: u64, value: CBORValue) -> Self {
+        Self::Tag {
+            tag,
+            value: Box::new(value),
+            encoding: None,
+        }
+    }
+
+    pub fn new_special(value: CBORSpecial) -> Self {
+        Self::Special {
+            value,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for CBORValue {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        Self::deserialize_nested(raw, 0).map_err(|e| e.annotate("CBORValue"))
+    }
+}
+
+impl CBORValue {
+    // depth is how many arrays/maps/tags the value is inside of
+    fn deserialize_nested<R: BufRead + Seek>(
+        raw: &mut Deserializer<R>,
+        depth: usize,
+    ) -> Result<Self, DeserializeError> {
+        if depth > CBOR_VALUE_MAX_DEPTH {
+            return Err(DeserializeFailure::MaxDepthExceeded(CBOR_VALUE_MAX_DEPTH).into());
+        }
+        match raw.cbor_type()? {
+            cbor_event::Type::UnsignedInteger => {
+                let (value, enc) = raw.unsigned_integer_sz()?;
+                Ok(Self::Uint {
+                    value,
+                    encoding: Some(enc),
+                })
+            }
+            cbor_event::Type::NegativeInteger => {
+                let (value, enc) = raw.negative_integer_sz()?;
+                Ok(Self::Nint {
+                    value: (-1 - value) as u64,
+                    encoding: Some(enc),
+                })
+            }
+            cbor_event::Type::Bytes => {
+                let (bytes, enc) = raw.bytes_sz()?;
+                Ok(Self::Bytes {
+                    bytes,
+                    encoding: enc.into(),
+                })
+            }
+            cbor_event::Type::Text => {
+                let (text, enc) = raw.text_sz()?;
+                Ok(Self::Text {
+                    text,
+                    encoding: enc.into(),
+                })
+            }
+            cbor_event::Type::Array => {
+                let len = raw.array_sz()?;
+                let mut elements = Vec::new();
+                while match len {
+                    cbor_event::LenSz::Len(n, _) => (elements.len() as u64) < n,
+                    cbor_event::LenSz::Indefinite => true,
+                } {
+                    if is_break(raw)? {
+                        raw.special()?;
+                        match len {
+                            cbor_event::LenSz::Len(_, _) => {
+                                return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                            }
+                            cbor_event::LenSz::Indefinite => break,
+                        }
+                    }
+                    elements.push(Self::deserialize_nested(raw, depth + 1)?);
+                }
+                Ok(Self::Array {
+                    elements,
+                    encoding: len.into(),
+                })
+            }
+            cbor_event::Type::Map => {
+                let len = raw.map_sz()?;
+                let mut entries = CBORValueMap::new();
+                while match len {
+                    cbor_event::LenSz::Len(n, _) => (entries.len() as u64) < n,
+                    cbor_event::LenSz::Indefinite => true,
+                } {
+                    if is_break(raw)? {
+                        raw.special()?;
+                        match len {
+                            cbor_event::LenSz::Len(_, _) => {
+                                return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                            }
+                            cbor_event::LenSz::Indefinite => break,
+                        }
+                    }
+                    let key = Self::deserialize_nested(raw, depth + 1)?;
+                    let value = Self::deserialize_nested(raw, depth + 1)?;
+                    if entries.contains_key(&key) {
+                        return Err(DeserializeFailure::DuplicateKey(key.to_key()).into());
+                    }
+                    entries.insert(key, value);
+                }
+                Ok(Self::Map {
+                    entries,
+                    encoding: len.into(),
+                })
+            }
+            cbor_event::Type::Tag => {
+                let (tag, enc) = raw.tag_sz()?;
+                Ok(Self::Tag {
+                    tag,
+                    value: Box::new(Self::deserialize_nested(raw, depth + 1)?),
+                    encoding: Some(enc),
+                })
+            }
+            cbor_event::Type::Special => match raw
+                .as_mut_ref()
+                .fill_buf()
+                .map_err(cbor_event::Error::from)?
+                .first()
+            {
+                // floats are read separately so their width is preserved
+                Some(0xf9..=0xfb) => {
+                    let (x, encoding) = raw.float_encoded()?;
+                    Ok(Self::Special {
+                        value: CBORSpecial::Float(x),
+                        encoding: Some(encoding),
+                    })
+                }
+                _ => Ok(Self::new_special(raw.special()?.try_into()?)),
+            },
+        }
+    }
+}
+
+fn is_break<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<bool, DeserializeError> {
+    Ok(raw.cbor_type()? == cbor_event::Type::Special
+        && raw
+            .as_mut_ref()
+            .fill_buf()
+            .map_err(cbor_event::Error::from)?
+            .first()
+            == Some(&0xff))
+}
+
+/// Tagged value of any tag used for the CDDL `#6` / `#6(any)` types
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TaggedValue {
+    tag: u64,
+    value: CBORValue,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<cbor_event::Sz>,
+}
+
+impl TaggedValue {
+    pub fn new(tag: u64, value: CBORValue) -> Self {
+        Self {
+            tag,
+            value,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for TaggedValue {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        (|| -> Result<_, DeserializeError> {
+            let (tag, enc) = raw.tag_sz()?;
+            Ok(Self {
+                tag,
+                value: CBORValue::deserialize_nested(raw, 1)?,
+                encoding: Some(enc),
+            })
+        })()
+        .map_err(|e| e.annotate("TaggedValue"))
+    }
+}
@@ -0,0 +1,85 @@
+
+impl Serialize for CBORValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        match self {
+            Self::Uint { value, encoding } => serializer
+                .write_unsigned_integer_sz(*value, fit_sz(*value, *encoding, force_canonical)),
+            Self::Nint { value, encoding } => serializer.write_negative_integer_sz(
+                -((*value as i128) + 1),
+                fit_sz(*value, *encoding, force_canonical),
+            ),
+            Self::Bytes { bytes, encoding } => serializer.write_bytes_sz(
+                bytes,
+                encoding.to_str_len_sz(bytes.len() as u64, force_canonical),
+            ),
+            Self::Text { text, encoding } => serializer.write_text_sz(
+                text,
+                encoding.to_str_len_sz(text.len() as u64, force_canonical),
+            ),
+            Self::Array { elements, encoding } => {
+                serializer
+                    .write_array_sz(encoding.to_len_sz(elements.len() as u64, force_canonical))?;
+                for element in elements {
+                    element.serialize(serializer, force_canonical)?;
+                }
+                encoding.end(serializer, force_canonical)
+            }
+            Self::Map { entries, encoding } => {
+                serializer
+                    .write_map_sz(encoding.to_len_sz(entries.len() as u64, force_canonical))?;
+                let mut key_order = entries
+                    .iter()
+                    .map(|(k, v)| {
+                        let mut buf = cbor_event::se::Serializer::new_vec();
+                        k.serialize(&mut buf, force_canonical)?;
+                        Ok((buf.finalize(), v))
+                    })
+                    .collect::<Result<Vec<(Vec<u8>, &_)>, cbor_event::Error>>()?;
+                if force_canonical {
+                    key_order.sort_by(|(lhs_bytes, _), (rhs_bytes, _)| {
+                        match lhs_bytes.len().cmp(&rhs_bytes.len()) {
+                            std::cmp::Ordering::Equal => lhs_bytes.cmp(rhs_bytes),
+                            diff_ord => diff_ord,
+                        }
+                    });
+                }
+                for (key_bytes, value) in key_order {
+                    serializer.write_raw_bytes(&key_bytes)?;
+                    value.serialize(serializer, force_canonical)?;
+                }
+                encoding.end(serializer, force_canonical)
+            }
+            Self::Tag {
+                tag,
+                value,
+                encoding,
+            } => {
+                serializer.write_tag_sz(*tag, fit_sz(*tag, *encoding, force_canonical))?;
+                value.serialize(serializer, force_canonical)
+            }
+            Self::Special {
+                value: CBORSpecial::Float(x),
+                encoding,
+            } => serializer.write_float_encoded(
+                *x,
+                fit_float(*x, *encoding, FloatEncoding::Double, force_canonical),
+            ),
+            Self::Special { value, .. } => serializer.write_special((*value).into()),
+        }
+    }
+}
+
+impl Serialize for TaggedValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        serializer.write_tag_sz(self.tag, fit_sz(self.tag, self.encoding, force_canonical))?;
+        self.value.serialize(serializer, force_canonical)
+    }
+}
@@ -0,0 +1,63 @@
+
+use cbor_event::se::Serialize;
+
+impl Serialize for CBORValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        match self {
+            Self::Uint { value, encoding } => {
+                serializer.write_unsigned_integer_sz(*value, fit_sz(*value, *encoding))
+            }
+            Self::Nint { value, encoding } => serializer
+                .write_negative_integer_sz(-((*value as i128) + 1), fit_sz(*value, *encoding)),
+            Self::Bytes { bytes, encoding } => {
+                serializer.write_bytes_sz(bytes, encoding.to_str_len_sz(bytes.len() as u64))
+            }
+            Self::Text { text, encoding } => {
+                serializer.write_text_sz(text, encoding.to_str_len_sz(text.len() as u64))
+            }
+            Self::Array { elements, encoding } => {
+                serializer.write_array_sz(encoding.to_len_sz(elements.len() as u64))?;
+                for element in elements {
+                    element.serialize(serializer)?;
+                }
+                encoding.end(serializer)
+            }
+            Self::Map { entries, encoding } => {
+                serializer.write_map_sz(encoding.to_len_sz(entries.len() as u64))?;
+                for (key, value) in entries.iter() {
+                    key.serialize(serializer)?;
+                    value.serialize(serializer)?;
+                }
+                encoding.end(serializer)
+            }
+            Self::Tag {
+                tag,
+                value,
+                encoding,
+            } => {
+                serializer.write_tag_sz(*tag, fit_sz(*tag, *encoding))?;
+                value.serialize(serializer)
+            }
+            Self::Special {
+                value: CBORSpecial::Float(x),
+                encoding,
+            } => {
+                serializer.write_float_encoded(*x, fit_float(*x, *encoding, FloatEncoding::Double))
+            }
+            Self::Special { value, .. } => serializer.write_special((*value).into()),
+        }
+    }
+}
+
+impl Serialize for TaggedValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        serializer.write_tag_sz(self.tag, fit_sz(self.tag, self.encoding))?;
+        self.value.serialize(serializer)
+    }
+}
@@ -0,0 +1,34 @@
+
+impl schemars::JsonSchema for CBORValue {
+    fn schema_name() -> String {
+        String::from("CBORValue")
+    }
+
+    // `any` can't be meaningfully constrained so we accept any JSON value
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::Schema::Bool(true)
+    }
+}
+
+impl schemars::JsonSchema for TaggedValue {
+    fn schema_name() -> String {
+        String::from("TaggedValue")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            ..Default::default()
+        };
+        let object = schema.object();
+        object
+            .properties
+            .insert(String::from("tag"), gen.subschema_for::<u64>());
+        object
+            .properties
+            .insert(String::from("value"), gen.subschema_for::<CBORValue>());
+        object.required.insert(String::from("tag"));
+        object.required.insert(String::from("value"));
+        schema.into()
+    }
+}
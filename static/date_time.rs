@@ -0,0 +1,297 @@
+use crate::error::*;
+use crate::serialization::*;
+use cbor_event::de::Deserializer;
+use cbor_event::se::Serializer;
+use std::convert::TryFrom;
+use std::io::{BufRead, Seek, Write};
+use std::str::FromStr;
+
+/// How a tdate (tag 0) was encoded
+#[derive(Clone, Debug)]
+pub struct DateTimeEncoding {
+    pub tag: cbor_event::Sz,
+    /// RFC 3339 allows several representations of the same date/time so the original is kept
+    pub text: String,
+    pub text_encoding: cbor_event::StringLenSz,
+}
+
+/// How the number inside a time (tag 1) was encoded
+#[derive(Clone, Debug)]
+pub enum EpochTimeValueEncoding {
+    Int(cbor_event::Sz),
+    /// The original value is kept as floats can be more precise than the nanoseconds stored
+    Float(FloatEncoding, f64),
+}
+
+#[derive(Clone, Debug)]
+pub struct EpochTimeEncoding {
+    pub tag: cbor_event::Sz,
+    pub value: EpochTimeValueEncoding,
+}
+
+#[derive(Clone, Debug)]
+pub enum DateTimeError {
+    /// Not a valid RFC 3339 date/time
+    Parsing(chrono::ParseError),
+    /// Timestamp not representable as a date/time
+    OutOfRange,
+}
+
+impl std::fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parsing(e) => write!(f, "invalid RFC 3339 date/time: {}", e),
+            Self::OutOfRange => write!(f, "timestamp out of range"),
+        }
+    }
+}
+
+impl std::error::Error for DateTimeError {}
+
+fn canonical_text(datetime: &chrono::DateTime<chrono::FixedOffset>) -> String {
+    datetime.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+}
+
+fn write_date_time<'se, W: Write>(
+    serializer: &'se mut Serializer<W>,
+    datetime: &chrono::DateTime<chrono::FixedOffset>,
+    encoding: Option<&DateTimeEncoding>,
+) -> cbor_event::Result<&'se mut Serializer<W>> {
+    match encoding {
+        Some(encoding) => {
+            serializer.write_tag_sz(0, encoding.tag)?;
+            serializer.write_text_sz(&encoding.text, encoding.text_encoding.clone())
+        }
+        None => {
+            serializer.write_tag(0)?;
+            serializer.write_text(canonical_text(datetime))
+        }
+    }
+}
+
+fn read_date_time<R: BufRead + Seek>(
+    raw: &mut Deserializer<R>,
+) -> Result<(chrono::DateTime<chrono::FixedOffset>, DateTimeEncoding), DeserializeError> {
+    let (tag, tag_sz) = raw.tag_sz()?;
+    if tag != 0 {
+        return Err(DeserializeFailure::TagMismatch {
+            found: tag,
+            expected: 0,
+        }
+        .into());
+    }
+    let (text, text_encoding) = raw.text_sz()?;
+    let datetime = chrono::DateTime::parse_from_rfc3339(&text)
+        .map_err(|e| DeserializeFailure::InvalidStructure(Box::new(DateTimeError::Parsing(e))))?;
+    Ok((
+        datetime,
+        DateTimeEncoding {
+            tag: tag_sz,
+            text,
+            text_encoding,
+        },
+    ))
+}
+
+// Whole seconds are written as ints and anything else as a float
+fn write_epoch_time<'se, W: Write>(
+    serializer: &'se mut Serializer<W>,
+    datetime: &chrono::DateTime<chrono::Utc>,
+    encoding: Option<&EpochTimeEncoding>,
+) -> cbor_event::Result<&'se mut Serializer<W>> {
+    let secs = datetime.timestamp();
+    let whole = datetime.timestamp_subsec_nanos() == 0;
+    match encoding {
+        Some(encoding) => serializer.write_tag_sz(1, encoding.tag)?,
+        None => serializer.write_tag(1)?,
+    };
+    match encoding.map(|encoding| &encoding.value) {
+        Some(EpochTimeValueEncoding::Float(float_encoding, original)) => {
+            // only when it's still the same time (as far as nanosecond precision can tell)
+            let x = if epoch_time_from_f64(*original).as_ref() == Some(datetime) {
+                *original
+            } else {
+                epoch_time_as_f64(datetime)
+            };
+            serializer.write_float_encoded(x, float_encoding.fit(x))
+        }
+        Some(EpochTimeValueEncoding::Int(sz)) if whole => {
+            if secs >= 0 {
+                serializer.write_unsigned_integer_sz(secs as u64, fit_epoch_sz(secs, *sz))
+            } else {
+                serializer.write_negative_integer_sz(secs as i128, fit_epoch_sz(secs, *sz))
+            }
+        }
+        _ if whole => {
+            if secs >= 0 {
+                serializer.write_unsigned_integer(secs as u64)
+            } else {
+                serializer.write_negative_integer(secs)
+            }
+        }
+        _ => serializer.write_float_encoded(epoch_time_as_f64(datetime), FloatEncoding::Double),
+    }
+}
+
+fn fit_epoch_sz(secs: i64, sz: cbor_event::Sz) -> cbor_event::Sz {
+    // as encoded in CBOR i.e. |x + 1| for a negative x
+    let value = if secs >= 0 {
+        secs as u64
+    } else {
+        (-1 - secs as i128) as u64
+    };
+    let max = match sz {
+        cbor_event::Sz::Inline => 23u64,
+        cbor_event::Sz::One => u8::MAX as u64,
+        cbor_event::Sz::Two => u16::MAX as u64,
+        cbor_event::Sz::Four => u32::MAX as u64,
+        cbor_event::Sz::Eight => u64::MAX,
+    };
+    if value <= max {
+        sz
+    } else {
+        cbor_event::Sz::canonical(value)
+    }
+}
+
+fn epoch_time_as_f64(datetime: &chrono::DateTime<chrono::Utc>) -> f64 {
+    datetime.timestamp() as f64 + datetime.timestamp_subsec_nanos() as f64 / 1e9
+}
+
+fn epoch_time_from_f64(secs: f64) -> Option<chrono::DateTime<chrono::Utc>> {
+    if !secs.is_finite() || secs < i64::MIN as f64 || secs >= i64::MAX as f64 {
+        return None;
+    }
+    let whole = secs.floor();
+    let nanos = ((secs - whole) * 1e9).round() as u32;
+    if nanos >= 1_000_000_000 {
+        chrono::DateTime::<chrono::Utc>::from_timestamp(whole as i64 + 1, 0)
+    } else {
+        chrono::DateTime::<chrono::Utc>::from_timestamp(whole as i64, nanos)
+    }
+}
+
+fn read_epoch_time<R: BufRead + Seek>(
+    raw: &mut Deserializer<R>,
+) -> Result<(chrono::DateTime<chrono::Utc>, EpochTimeEncoding), DeserializeError> {
+    let (tag, tag_sz) = raw.tag_sz()?;
+    if tag != 1 {
+        return Err(DeserializeFailure::TagMismatch {
+            found: tag,
+            expected: 1,
+        }
+        .into());
+    }
+    let (datetime, value_encoding) = match raw.cbor_type()? {
+        cbor_event::Type::UnsignedInteger => {
+            let (x, sz) = raw.unsigned_integer_sz()?;
+            let datetime = i64::try_from(x)
+                .ok()
+                .and_then(|secs| chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0));
+            (datetime, EpochTimeValueEncoding::Int(sz))
+        }
+        cbor_event::Type::NegativeInteger => {
+            let (x, sz) = raw.negative_integer_sz()?;
+            let datetime = i64::try_from(x)
+                .ok()
+                .and_then(|secs| chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0));
+            (datetime, EpochTimeValueEncoding::Int(sz))
+        }
+        _ => {
+            let (x, float_encoding) = raw.float_encoded()?;
+            (
+                epoch_time_from_f64(x),
+                EpochTimeValueEncoding::Float(float_encoding, x),
+            )
+        }
+    };
+    let datetime = datetime
+        .ok_or_else(|| DeserializeFailure::InvalidStructure(Box::new(DateTimeError::OutOfRange)))?;
+    Ok((
+        datetime,
+        EpochTimeEncoding {
+            tag: tag_sz,
+            value: value_encoding,
+        },
+    ))
+}
+
+impl DateTime {
+    /// * `text` - RFC 3339 date/time e.g. `2023-01-02T03:04:05Z`
+    pub fn new(text: &str) -> Result<Self, DateTimeError> {
+        chrono::DateTime::parse_from_rfc3339(text)
+            .map(Self::from)
+            .map_err(DateTimeError::Parsing)
+    }
+
+    pub fn as_datetime(&self) -> &chrono::DateTime<chrono::FixedOffset> {
+        &self.datetime
+    }
+}
+
+impl From<chrono::DateTime<chrono::Utc>> for DateTime {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::from(chrono::DateTime::<chrono::FixedOffset>::from(datetime))
+    }
+}
+
+impl From<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+    fn from(x: DateTime) -> Self {
+        x.datetime
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl EpochTime {
+    /// * `secs` - Whole seconds since the unix epoch
+    pub fn new(secs: i64) -> Result<Self, DateTimeError> {
+        chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0)
+            .map(Self::from)
+            .ok_or(DateTimeError::OutOfRange)
+    }
+
+    /// * `secs` - Seconds since the unix epoch (sub-second precision is kept up to nanoseconds)
+    pub fn from_f64(secs: f64) -> Result<Self, DateTimeError> {
+        epoch_time_from_f64(secs)
+            .map(Self::from)
+            .ok_or(DateTimeError::OutOfRange)
+    }
+
+    /// Whole seconds since the unix epoch (rounded down)
+    pub fn as_secs(&self) -> i64 {
+        self.datetime.timestamp()
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        epoch_time_as_f64(&self.datetime)
+    }
+
+    pub fn as_datetime(&self) -> &chrono::DateTime<chrono::Utc> {
+        &self.datetime
+    }
+}
+
+impl From<EpochTime> for chrono::DateTime<chrono::Utc> {
+    fn from(x: EpochTime) -> Self {
+        x.datetime
+    }
+}
+
+impl std::fmt::Display for EpochTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_f64())
+    }
+}
@@ -0,0 +1,72 @@
+
+// JSON representation of tdate is its RFC 3339 text and of time the (possibly fractional) seconds since the epoch
+
+impl serde::Serialize for DateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_text())
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for DateTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let s = <String as serde::de::Deserialize>::deserialize(deserializer)?;
+        Self::new(&s).map_err(|_e| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&s), &"RFC 3339 date/time")
+        })
+    }
+}
+
+impl serde::Serialize for EpochTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.datetime.timestamp_subsec_nanos() == 0 {
+            serializer.serialize_i64(self.as_secs())
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for EpochTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct EpochTimeVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for EpochTimeVisitor {
+            type Value = EpochTime;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("seconds since the unix epoch")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                EpochTime::new(v)
+                    .map_err(|_e| E::invalid_value(serde::de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                i64::try_from(v)
+                    .map_err(|_e| DateTimeError::OutOfRange)
+                    .and_then(EpochTime::new)
+                    .map_err(|_e| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                EpochTime::from_f64(v)
+                    .map_err(|_e| E::invalid_value(serde::de::Unexpected::Float(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(EpochTimeVisitor)
+    }
+}
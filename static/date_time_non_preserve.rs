@@ -0,0 +1,65 @@
+
+/// RFC 3339 date/time used for the CDDL `tdate` type (tag 0)
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DateTime {
+    datetime: chrono::DateTime<chrono::FixedOffset>,
+}
+
+impl DateTime {
+    /// RFC 3339 text this is serialized as
+    pub fn to_text(&self) -> String {
+        canonical_text(&self.datetime)
+    }
+}
+
+impl From<chrono::DateTime<chrono::FixedOffset>> for DateTime {
+    fn from(datetime: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Self { datetime }
+    }
+}
+
+impl cbor_event::se::Serialize for DateTime {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_date_time(serializer, &self.datetime, None)
+    }
+}
+
+impl Deserialize for DateTime {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_date_time(raw)
+            .map(|(datetime, _encoding)| Self::from(datetime))
+            .map_err(|e| e.annotate("DateTime"))
+    }
+}
+
+/// Timestamp since the unix epoch used for the CDDL `time` type (tag 1)
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EpochTime {
+    datetime: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<chrono::DateTime<chrono::Utc>> for EpochTime {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Self { datetime }
+    }
+}
+
+impl cbor_event::se::Serialize for EpochTime {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        write_epoch_time(serializer, &self.datetime, None)
+    }
+}
+
+impl Deserialize for EpochTime {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_epoch_time(raw)
+            .map(|(datetime, _encoding)| Self::from(datetime))
+            .map_err(|e| e.annotate("EpochTime"))
+    }
+}
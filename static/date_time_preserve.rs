@@ -0,0 +1,100 @@
+
+/// RFC 3339 date/time used for the CDDL `tdate` type (tag 0)
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DateTime {
+    datetime: chrono::DateTime<chrono::FixedOffset>,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<DateTimeEncoding>,
+}
+
+impl DateTime {
+    /// RFC 3339 text this is serialized as (the original text if it was deserialized)
+    pub fn to_text(&self) -> String {
+        match &self.encoding {
+            Some(encoding) => encoding.text.clone(),
+            None => canonical_text(&self.datetime),
+        }
+    }
+
+    // shared by both the force_canonical and non-force_canonical Serialize impls
+    fn serialize_with_encodings<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let encoding = self.encoding.as_ref().filter(|_| !force_canonical);
+        write_date_time(serializer, &self.datetime, encoding)
+    }
+}
+
+impl From<chrono::DateTime<chrono::FixedOffset>> for DateTime {
+    fn from(datetime: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Self {
+            datetime,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for DateTime {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_date_time(raw)
+            .map(|(datetime, encoding)| Self {
+                datetime,
+                encoding: Some(encoding),
+            })
+            .map_err(|e| e.annotate("DateTime"))
+    }
+}
+
+/// Timestamp since the unix epoch used for the CDDL `time` type (tag 1)
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EpochTime {
+    datetime: chrono::DateTime<chrono::Utc>,
+    #[derivative(
+        PartialEq = "ignore",
+        Ord = "ignore",
+        PartialOrd = "ignore",
+        Hash = "ignore"
+    )]
+    encoding: Option<EpochTimeEncoding>,
+}
+
+impl EpochTime {
+    // shared by both the force_canonical and non-force_canonical Serialize impls
+    fn serialize_with_encodings<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let encoding = self.encoding.as_ref().filter(|_| !force_canonical);
+        write_epoch_time(serializer, &self.datetime, encoding)
+    }
+}
+
+impl From<chrono::DateTime<chrono::Utc>> for EpochTime {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            datetime,
+            encoding: None,
+        }
+    }
+}
+
+impl Deserialize for EpochTime {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        read_epoch_time(raw)
+            .map(|(datetime, encoding)| Self {
+                datetime,
+                encoding: Some(encoding),
+            })
+            .map_err(|e| e.annotate("EpochTime"))
+    }
+}
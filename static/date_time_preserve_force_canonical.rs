@@ -0,0 +1,20 @@
+
+impl Serialize for DateTime {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        self.serialize_with_encodings(serializer, force_canonical)
+    }
+}
+
+impl Serialize for EpochTime {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        self.serialize_with_encodings(serializer, force_canonical)
+    }
+}
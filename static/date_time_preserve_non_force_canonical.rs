@@ -0,0 +1,18 @@
+
+impl cbor_event::se::Serialize for DateTime {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        self.serialize_with_encodings(serializer, false)
+    }
+}
+
+impl cbor_event::se::Serialize for EpochTime {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        self.serialize_with_encodings(serializer, false)
+    }
+}
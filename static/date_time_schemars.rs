@@ -0,0 +1,25 @@
+
+impl schemars::JsonSchema for DateTime {
+    fn schema_name() -> String {
+        String::from("DateTime")
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            format: Some(String::from("date-time")),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl schemars::JsonSchema for EpochTime {
+    fn schema_name() -> String {
+        String::from("EpochTime")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <f64 as schemars::JsonSchema>::json_schema(gen)
+    }
+}
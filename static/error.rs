@@ -5,7 +5,11 @@ use std::io::{BufRead, Seek};
 pub enum Key {
     Str(String),
     Uint(u64),
+    Nint(i128),
     Float(f64),
+    Bytes(Vec<u8>),
+    Tagged(u64, Box<Key>),
+    Special(cbor_event::Special),
 }
 
 impl std::fmt::Display for Key {
@@ -13,7 +17,24 @@ impl std::fmt::Display for Key {
         match self {
             Key::Str(x) => write!(f, "\"{}\"", x),
             Key::Uint(x) => write!(f, "{}", x),
+            Key::Nint(x) => write!(f, "{}", x),
             Key::Float(x) => write!(f, "{}", x),
+            Key::Bytes(x) => {
+                write!(f, "h'")?;
+                for byte in x {
+                    write!(f, "{:02x}", byte)?;
+                }
+                write!(f, "'")
+            },
+            Key::Tagged(tag, x) => write!(f, "#6.{}({})", tag, x),
+            Key::Special(x) => match x {
+                cbor_event::Special::Bool(b) => write!(f, "{}", b),
+                cbor_event::Special::Null => write!(f, "null"),
+                cbor_event::Special::Undefined => write!(f, "undefined"),
+                cbor_event::Special::Unassigned(n) => write!(f, "#7.{}", n),
+                cbor_event::Special::Float(x) => write!(f, "{}", x),
+                cbor_event::Special::Break => write!(f, "break"),
+            },
         }
     }
 }
@@ -26,24 +47,45 @@ pub enum DeserializeFailure {
     DuplicateKey(Key),
     EndingBreakMissing,
     ExpectedNull,
+    /// Value explicitly not allowed by a .ne control operator
+    ExcludedValue(String),
     FixedValueMismatch{
         found: Key,
         expected: Key,
     },
+    /// Float not within its range constraints (e.g. 0.5..1.5) or NaN. range is in interval notation e.g. [0.5, 1.5]
+    FloatRangeCheck{
+        found: f64,
+        range: String,
+    },
     /// Invalid internal structure imposed on top of the CBOR format
     InvalidStructure(Box<dyn std::error::Error>),
     MandatoryFieldMissing(Key),
+    /// Arrays/maps/tags nested deeper than the max depth allowed e.g. inside a CBORValue
+    MaxDepthExceeded(usize),
     NoVariantMatched,
     NoVariantMatchedWithCauses(Vec<DeserializeError>),
+    /// Number of elements in an array/map not within its occurrence bounds e.g. [1*3 uint]
+    OccurrenceCheck{
+        found: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
     RangeCheck{
         found: isize,
         min: Option<isize>,
         max: Option<isize>,
     },
+    RegexMismatch{
+        found: String,
+        regex: String,
+    },
     TagMismatch{
         found: u64,
         expected: u64,
     },
+    /// A bit was set that wasn't declared in a .bits control operator
+    UndeclaredBitSet(u64),
     UnknownKey(Key),
     UnexpectedKeyType(cbor_event::Type),
 }
@@ -92,11 +134,14 @@ impl DeserializeError {
             DeserializeFailure::DuplicateKey(key) => write!(f, "Duplicate key: {}", key),
             DeserializeFailure::EndingBreakMissing => write!(f, "Missing ending CBOR Break"),
             DeserializeFailure::ExpectedNull => write!(f, "Expected null, found other type"),
+            DeserializeFailure::ExcludedValue(value) => write!(f, "Value {} is not allowed", value),
             DeserializeFailure::FixedValueMismatch{ found, expected } => write!(f, "Expected fixed value {} found {}", expected, found),
+            DeserializeFailure::FloatRangeCheck{ found, range } => write!(f, "{} not in range {}", found, range),
             DeserializeFailure::InvalidStructure(e) => {
                 write!(f, "Invalid internal structure: {}", e)
             }
             DeserializeFailure::MandatoryFieldMissing(key) => write!(f, "Mandatory field {} not found", key),
+            DeserializeFailure::MaxDepthExceeded(max) => write!(f, "Nested deeper than the max depth of {}", max),
             DeserializeFailure::NoVariantMatched => write!(f, "No variant matched"),
             DeserializeFailure::NoVariantMatchedWithCauses(errs) => {
                 write!(f, "No variant matched. Failures:\n")?;
@@ -106,13 +151,21 @@ impl DeserializeError {
                 }
                 Ok(())
             },
+            DeserializeFailure::OccurrenceCheck{ found, min, max } => match (min, max) {
+                (Some(min), Some(max)) => write!(f, "{} elements not in range {} - {}", found, min, max),
+                (Some(min), None) => write!(f, "{} elements but expected at least {}", found, min),
+                (None, Some(max)) => write!(f, "{} elements but expected at most {}", found, max),
+                (None, None) => write!(f, "invalid occurrence (no min nor max specified)"),
+            },
             DeserializeFailure::RangeCheck{ found, min, max } => match (min, max) {
                 (Some(min), Some(max)) => write!(f, "{} not in range {} - {}", found, min, max),
                 (Some(min), None) => write!(f, "{} not at least {}", found, min),
                 (None, Some(max)) => write!(f, "{} not at most {}", found, max),
                 (None, None) => write!(f, "invalid range (no min nor max specified)"),
             },
+            DeserializeFailure::RegexMismatch{ found, regex } => write!(f, "\"{}\" does not match regex \"{}\"", found, regex),
             DeserializeFailure::TagMismatch{ found, expected } => write!(f, "Expected tag {}, found {}", expected, found),
+            DeserializeFailure::UndeclaredBitSet(bit) => write!(f, "Undeclared bit {} set", bit),
             DeserializeFailure::UnknownKey(key) => write!(f, "Found unexpected key {}", key),
             DeserializeFailure::UnexpectedKeyType(ty) => write!(f, "Found unexpected key of CBOR type {:?}", ty),
         }
@@ -15,3 +15,245 @@ impl<T: cbor_event::de::Deserialize> Deserialize for T {
         T::deserialize(raw).map_err(DeserializeError::from)
     }
 }
+
+/// Width a CBOR float is encoded with (additional info 25, 26 or 27 of major type 7)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum FloatEncoding {
+    Half,
+    Single,
+    Double,
+}
+
+impl FloatEncoding {
+    /// Smallest width that represents x exactly (RFC 8949 preferred serialization)
+    pub fn shortest(x: f64) -> Self {
+        if f64_to_f16_bits(x).is_some() {
+            Self::Half
+        } else if x.is_nan() || (x as f32) as f64 == x {
+            Self::Single
+        } else {
+            Self::Double
+        }
+    }
+
+    /// This width if it represents x exactly, otherwise the smallest one that does
+    pub fn fit(self, x: f64) -> Self {
+        let exact = match self {
+            Self::Half => f64_to_f16_bits(x).is_some(),
+            Self::Single => x.is_nan() || (x as f32) as f64 == x,
+            Self::Double => true,
+        };
+        if exact {
+            self
+        } else {
+            Self::shortest(x)
+        }
+    }
+}
+
+// None if x can't be represented exactly as a float16
+fn f64_to_f16_bits(x: f64) -> Option<u16> {
+    let sign = if x.is_sign_negative() { 0x8000u16 } else { 0 };
+    if x.is_nan() {
+        return Some(0x7e00);
+    }
+    if x.is_infinite() {
+        return Some(sign | 0x7c00);
+    }
+    let abs = x.abs();
+    if abs < 2f64.powi(-14) {
+        // zero or subnormal: a multiple of 2^-24
+        let mantissa = abs * 2f64.powi(24);
+        return if mantissa.fract() == 0.0 {
+            Some(sign | mantissa as u16)
+        } else {
+            None
+        };
+    }
+    let bits = abs.to_bits();
+    let exponent = ((bits >> 52) as i32) - 1023;
+    let mantissa = bits & ((1u64 << 52) - 1);
+    if exponent > 15 || mantissa & ((1u64 << 42) - 1) != 0 {
+        return None;
+    }
+    Some(sign | (((exponent + 15) as u16) << 10) | (mantissa >> 42) as u16)
+}
+
+fn f16_bits_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        0x1f => if mantissa == 0.0 { f64::INFINITY } else { f64::NAN },
+        _ => (1024.0 + mantissa) * 2f64.powi(exponent - 25),
+    }
+}
+
+pub trait SerializeFloat {
+    /// Writes x using exactly the given width, failing if that width can't represent x exactly
+    fn write_float_encoded(&mut self, x: f64, encoding: FloatEncoding) -> cbor_event::Result<&mut Self>;
+}
+
+impl<W: Write + Sized> SerializeFloat for Serializer<W> {
+    fn write_float_encoded(&mut self, x: f64, encoding: FloatEncoding) -> cbor_event::Result<&mut Self> {
+        let mut bytes = Vec::with_capacity(9);
+        match encoding {
+            FloatEncoding::Half => {
+                let bits = f64_to_f16_bits(x).ok_or_else(|| {
+                    cbor_event::Error::CustomError(format!("{} can't be represented exactly as a float16", x))
+                })?;
+                bytes.push(0xf9);
+                bytes.extend_from_slice(&bits.to_be_bytes());
+            },
+            FloatEncoding::Single => {
+                if !x.is_nan() && (x as f32) as f64 != x {
+                    return Err(cbor_event::Error::CustomError(format!("{} can't be represented exactly as a float32", x)));
+                }
+                bytes.push(0xfa);
+                bytes.extend_from_slice(&(x as f32).to_be_bytes());
+            },
+            FloatEncoding::Double => {
+                bytes.push(0xfb);
+                bytes.extend_from_slice(&x.to_be_bytes());
+            },
+        }
+        self.write_raw_bytes(&bytes)
+    }
+}
+
+pub trait DeserializeFloat {
+    /// Reads a float of any width, also returning which width it was encoded with
+    fn float_encoded(&mut self) -> Result<(f64, FloatEncoding), DeserializeError>;
+
+    /// Reads a float that must be encoded with exactly the given width (e.g. float16 is only #7.25)
+    fn float_with_width(&mut self, expected: FloatEncoding) -> Result<f64, DeserializeError> {
+        let (x, found) = self.float_encoded()?;
+        if found != expected {
+            return Err(cbor_event::Error::CustomError(format!("Expected {:?} float, found {:?}", expected, found)).into());
+        }
+        Ok(x)
+    }
+}
+
+impl<R: BufRead + Seek> DeserializeFloat for Deserializer<R> {
+    fn float_encoded(&mut self) -> Result<(f64, FloatEncoding), DeserializeError> {
+        let found = self.cbor_type()?;
+        if found != cbor_event::Type::Special {
+            return Err(cbor_event::Error::Expected(cbor_event::Type::Special, found).into());
+        }
+        let initial_byte = self.as_mut_ref().fill_buf().map_err(cbor_event::Error::from)?[0];
+        let (encoding, len) = match initial_byte {
+            0xf9 => (FloatEncoding::Half, 2),
+            0xfa => (FloatEncoding::Single, 4),
+            0xfb => (FloatEncoding::Double, 8),
+            _ => return Err(cbor_event::Error::CustomError(format!("Expected float, found initial byte {:#04x}", initial_byte)).into()),
+        };
+        let mut bytes = [0u8; 9];
+        std::io::Read::read_exact(self.as_mut_ref(), &mut bytes[..1 + len]).map_err(cbor_event::Error::from)?;
+        let x = match encoding {
+            FloatEncoding::Half => f16_bits_to_f64(u16::from_be_bytes([bytes[1], bytes[2]])),
+            FloatEncoding::Single => f32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as f64,
+            FloatEncoding::Double => {
+                let mut be_bytes = [0u8; 8];
+                be_bytes.copy_from_slice(&bytes[1..]);
+                f64::from_be_bytes(be_bytes)
+            },
+        };
+        Ok((x, encoding))
+    }
+}
+
+/// Splits already serialized map entries (each key directly followed by its value) into the bytes of each entry.
+/// This is for plain groups embedded into maps whose entries can be interleaved with the rest of the map's.
+pub fn split_map_entries(entries: &[u8]) -> cbor_event::Result<Vec<&[u8]>> {
+    let mut split = Vec::new();
+    let mut rest = entries;
+    while !rest.is_empty() {
+        let key_len = cbor_item_len(rest)?;
+        let value_len = cbor_item_len(&rest[key_len..])?;
+        let (entry, next) = rest.split_at(key_len + value_len);
+        split.push(entry);
+        rest = next;
+    }
+    Ok(split)
+}
+
+/// Writes already serialized map entries (each key directly followed by its value) sorted by their keys
+/// as per canonical CBOR. This is for maps whose keys aren't all known at generation time (e.g. catch-all
+/// entries or embedded plain groups) and thus can't have their canonical order decided beforehand.
+pub fn write_canonical_map_entries<'se, W: Write>(
+    serializer: &'se mut Serializer<W>,
+    entries: &[u8],
+) -> cbor_event::Result<&'se mut Serializer<W>> {
+    let mut sorted = split_map_entries(entries)?
+        .into_iter()
+        .map(|entry| cbor_item_len(entry).map(|key_len| (&entry[..key_len], entry)))
+        .collect::<cbor_event::Result<Vec<_>>>()?;
+    sorted.sort_by(
+        |(lhs_key, _), (rhs_key, _)| match lhs_key.len().cmp(&rhs_key.len()) {
+            std::cmp::Ordering::Equal => lhs_key.cmp(rhs_key),
+            diff_ord => diff_ord,
+        },
+    );
+    for (_key, entry) in sorted {
+        serializer.write_raw_bytes(entry)?;
+    }
+    Ok(serializer)
+}
+
+// size in bytes of the first (complete) CBOR item in bytes
+fn cbor_item_len(bytes: &[u8]) -> cbor_event::Result<usize> {
+    let initial_byte = *bytes
+        .first()
+        .ok_or(cbor_event::Error::NotEnough(bytes.len(), 1))?;
+    let major_type = initial_byte >> 5;
+    let additional_info = initial_byte & 0x1f;
+    let (arg, header_len) = match additional_info {
+        0..=23 => (additional_info as u64, 1),
+        24..=27 => {
+            let arg_len = 1 << (additional_info - 24);
+            let arg_bytes = bytes
+                .get(1..1 + arg_len)
+                .ok_or(cbor_event::Error::NotEnough(bytes.len(), 1 + arg_len))?;
+            (
+                arg_bytes
+                    .iter()
+                    .fold(0u64, |acc, byte| (acc << 8) | *byte as u64),
+                1 + arg_len,
+            )
+        }
+        31 if (2..=5).contains(&major_type) => {
+            // indefinite: items (or string chunks) until the break
+            let mut len = 1;
+            while *bytes
+                .get(len)
+                .ok_or(cbor_event::Error::NotEnough(bytes.len(), len + 1))?
+                != 0xff
+            {
+                len += cbor_item_len(&bytes[len..])?;
+            }
+            return Ok(len + 1);
+        }
+        _ => return Err(cbor_event::Error::UnknownLenType(additional_info)),
+    };
+    let mut len = header_len;
+    match major_type {
+        2 | 3 => len = len.saturating_add(arg as usize),
+        4 | 5 | 6 => {
+            let items = match major_type {
+                4 => arg,
+                5 => arg.saturating_mul(2),
+                _ => 1,
+            };
+            for _ in 0..items {
+                len += cbor_item_len(bytes.get(len..).unwrap_or_default())?;
+            }
+        }
+        _ => (),
+    }
+    if len > bytes.len() {
+        return Err(cbor_event::Error::NotEnough(bytes.len(), len));
+    }
+    Ok(len)
+}
@@ -80,3 +80,12 @@ pub trait SerializeEmbeddedGroup {
         force_canonical: bool,
     ) -> cbor_event::Result<&'a mut Serializer<W>>;
 }
+
+#[inline]
+pub fn fit_float(x: f64, encoding: Option<FloatEncoding>, default: FloatEncoding, force_canonical: bool) -> FloatEncoding {
+    match encoding {
+        _ if force_canonical => FloatEncoding::shortest(x),
+        Some(encoding) => encoding.fit(x),
+        None => default.fit(x),
+    }
+}
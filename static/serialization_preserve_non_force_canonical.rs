@@ -44,3 +44,8 @@ impl StringEncoding {
         }
     }
 }
+
+#[inline]
+pub fn fit_float(x: f64, encoding: Option<FloatEncoding>, default: FloatEncoding) -> FloatEncoding {
+    encoding.unwrap_or(default).fit(x)
+}
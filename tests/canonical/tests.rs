@@ -516,4 +516,150 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn floats() {
+        // float16/32/64 mandate their width so canonical form doesn't shorten them
+        let bytes = vec![
+            arr_def(3),
+                vec![0xf9, 0x3e, 0x00],
+                vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+                vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let floats = Floats::from_cbor_bytes(&bytes).unwrap();
+        assert_eq!(floats.to_canonical_cbor_bytes(), bytes);
+        deser_test_canonical(&Floats::new(1.5, 65504.0, 100000.0));
+    }
+
+    #[test]
+    fn any_floats() {
+        let doubles_bytes = vec![
+            arr_def(3),
+                vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        // canonical floats use the shortest width that represents the value exactly
+        let canonical_bytes = vec![
+            arr_def(3),
+                vec![0xf9, 0x3e, 0x00],
+                vec![0xf9, 0x3e, 0x00],
+                vec![0xf9, 0x3e, 0x00],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let from_doubles = AnyFloats::from_cbor_bytes(&doubles_bytes).unwrap();
+        assert_eq!(from_doubles.to_cbor_bytes(), doubles_bytes);
+        assert_eq!(from_doubles.to_canonical_cbor_bytes(), canonical_bytes);
+        let precise = AnyFloats::new(0.1f32 as f64, 0.1, 1.5).to_canonical_cbor_bytes();
+        assert_eq!(precise[1], 0xfa);
+        assert_eq!(precise[6], 0xfb);
+        assert_eq!(precise[15], 0xf9);
+        deser_test_canonical(&AnyFloats::new(1.5, 65504.0, 100000.0));
+    }
+
+    #[test]
+    fn big_ints() {
+        let irregular_bytes = vec![
+            arr_def(2),
+                cbor_tag_sz(2, Sz::Eight),
+                cbor_bytes_sz(vec![0x05], StringLenSz::Indefinite(vec![(1, Sz::One)])),
+                cbor_int(-6, Sz::Four),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        // canonical integers use the regular int encoding when they fit in 64 bits
+        // but biguint is always a bignum
+        let canonical_bytes = vec![
+            arr_def(2),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x05], StringLenSz::Len(Sz::Inline)),
+                cbor_int(-6, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let from_bytes = BigInts::from_cbor_bytes(&irregular_bytes).unwrap();
+        assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+        assert_eq!(from_bytes.to_canonical_cbor_bytes(), canonical_bytes);
+    }
+
+    #[test]
+    fn literal_keys() {
+        let irregular_bytes = vec![
+            map_sz(3, Sz::Two),
+                cbor_tag_sz(42, Sz::Eight),
+                    cbor_str_sz("tagged", StringLenSz::Indefinite(vec![(3, Sz::One), (3, Sz::Inline)])),
+                    cbor_int(3, Sz::Four),
+                cbor_bytes_sz(vec![0x01, 0x02], StringLenSz::Len(Sz::Two)),
+                    cbor_str_sz("two", StringLenSz::Len(Sz::One)),
+                cbor_int(-1, Sz::Eight),
+                    cbor_int(1, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        // canonical maps are sorted by their encoded keys with the tagged key being the longest
+        let canonical_bytes = vec![
+            map_def(3),
+                cbor_int(-1, Sz::Inline),
+                    cbor_int(1, Sz::Inline),
+                vec![0x42, 0x01, 0x02],
+                    cbor_string("two"),
+                cbor_tag_sz(42, Sz::One),
+                    cbor_string("tagged"),
+                    cbor_int(3, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let from_bytes = LiteralKeys::from_cbor_bytes(&irregular_bytes).unwrap();
+        assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+        assert_eq!(from_bytes.to_canonical_cbor_bytes(), canonical_bytes);
+    }
+
+    #[test]
+    fn map_with_extra() {
+        let irregular_bytes = vec![
+            map_sz(4, Sz::One),
+                cbor_str_sz("name", StringLenSz::Len(Sz::Two)),
+                    cbor_string("foo"),
+                cbor_int(1000, Sz::Eight),
+                    cbor_string("thousand"),
+                cbor_int(1, Sz::Inline),
+                    cbor_int(2, Sz::Four),
+                cbor_int(0, Sz::One),
+                    cbor_string("zero"),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        // catch-all entries are sorted in with the known fields
+        let canonical_bytes = vec![
+            map_def(4),
+                cbor_int(0, Sz::Inline),
+                    cbor_string("zero"),
+                cbor_int(1, Sz::Inline),
+                    cbor_int(2, Sz::Inline),
+                cbor_int(1000, Sz::Two),
+                    cbor_string("thousand"),
+                cbor_string("name"),
+                    cbor_string("foo"),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let from_bytes = MapWithExtra::from_cbor_bytes(&irregular_bytes).unwrap();
+        assert_eq!(from_bytes.extra.len(), 2);
+        assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+        assert_eq!(from_bytes.to_canonical_cbor_bytes(), canonical_bytes);
+    }
+
+    #[test]
+    fn map_with_embedded_group() {
+        let irregular_bytes = vec![
+            map_sz(3, Sz::Two),
+                cbor_str_sz("ccc", StringLenSz::Len(Sz::One)),
+                    cbor_string("three"),
+                cbor_string("aa"),
+                    cbor_int(1, Sz::Four),
+                cbor_str_sz("b", StringLenSz::Len(Sz::Eight)),
+                    cbor_int(2, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        // the embedded group's entries are sorted in with the rest of the map's
+        let canonical_bytes = vec![
+            map_def(3),
+                cbor_string("b"),
+                    cbor_int(2, Sz::Inline),
+                cbor_string("aa"),
+                    cbor_int(1, Sz::Inline),
+                cbor_string("ccc"),
+                    cbor_string("three"),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let from_bytes = MapWithEmbeddedGroup::from_cbor_bytes(&irregular_bytes).unwrap();
+        assert_eq!(from_bytes.embedded_group.ccc, Some(String::from("three")));
+        assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+        assert_eq!(from_bytes.to_canonical_cbor_bytes(), canonical_bytes);
+    }
 }
@@ -142,6 +142,60 @@ mod tests {
         let enums = Enums::new(CEnum::I3, TypeChoice::U64(53435364));
     }
 
+    #[test]
+    fn choice_from_group() {
+        let palette = Palette::new(Color::Blue, ShapeKind::Square, PaletteAccent::Dark);
+        let expected = vec![
+            arr_def(3),
+                cbor_int(2, cbor_event::Sz::Inline),
+                cbor_string("square"),
+                cbor_int(11, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(palette.to_cbor_bytes(), expected);
+        deser_test(&palette);
+        let not_a_color = vec![
+            arr_def(3),
+                cbor_int(3, cbor_event::Sz::Inline),
+                cbor_string("circle"),
+                cbor_int(10, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Palette::from_cbor_bytes(&not_a_color).is_err());
+    }
+
+    #[test]
+    fn simple_values() {
+        let values = SimpleValues::new(SimpleMarker::Simple32, UintOrUndefined::Undefined);
+        // undefined is #7.23, #7.16 fits in the initial byte and #7.32 needs an extra byte
+        let expected = vec![
+            arr_def(4),
+                vec![0xf7],
+                vec![0xf0],
+                vec![0xf8, 0x20],
+                vec![0xf7],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(values.to_cbor_bytes(), expected);
+        deser_test(&values);
+        for marker in [SimpleMarker::Undefined, SimpleMarker::Simple16, SimpleMarker::False] {
+            deser_test(&SimpleValues::new(marker, UintOrUndefined::U64(5)));
+        }
+        let wrong_simple = vec![
+            arr_def(4),
+                vec![0xf7],
+                vec![0xf1],
+                vec![0xf7],
+                vec![0xf7],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(SimpleValues::from_cbor_bytes(&wrong_simple).is_err());
+        let null_instead_of_undefined = vec![
+            arr_def(4),
+                vec![0xf6],
+                vec![0xf0],
+                vec![0xf7],
+                vec![0xf7],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(SimpleValues::from_cbor_bytes(&null_instead_of_undefined).is_err());
+    }
+
     #[test]
     fn group_choice_foo() {
         deser_test(&GroupChoice::new_foo(0, String::new(), vec![]));
@@ -595,4 +649,1045 @@ mod tests {
         assert!(lib_rs.contains("comments about second"));
         assert!(lib_rs.contains("type-level comment"));
     }
+
+    #[test]
+    fn anys() {
+        let mut table = BTreeMap::new();
+        table.insert(String::from("null"), CBORValue::new_special(CBORSpecial::Null));
+        table.insert(String::from("nint"), CBORValue::new_nint(4));
+        let mut map = cbor_value::CBORValueMap::new();
+        map.insert(CBORValue::new_special(CBORSpecial::Bool(false)), CBORValue::new_bytes(vec![0xBA, 0xAD]));
+        map.insert(CBORValue::new_text(String::from("float")), CBORValue::new_special(CBORSpecial::Float(1.5)));
+        let anys = Anys::new(
+            CBORValue::new_uint(5),
+            CBORValue::new_tag(42, CBORValue::new_array(vec![CBORValue::new_text(String::from("foo"))])),
+            // specials inside arrays must not be mistaken for a break
+            vec![CBORValue::new_special(CBORSpecial::Null), CBORValue::new_special(CBORSpecial::Bool(true)), CBORValue::new_map(map)],
+            table,
+            CBORValue::new_special(CBORSpecial::Undefined),
+            CBORValue::new_nint(0),
+        );
+        deser_test(&anys);
+        // indefinite arrays with specials as elements
+        let indef_bytes = vec![
+            vec![ARR_INDEF],
+                vec![NULL],
+                vec![0xf5u8],
+                arr_def(1),
+                    vec![NULL],
+            vec![BREAK],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let indef = CBORValue::from_cbor_bytes(&indef_bytes).unwrap();
+        assert_eq!(indef.as_array().unwrap().len(), 3);
+        assert_eq!(indef.as_array().unwrap()[0].as_special(), Some(CBORSpecial::Null));
+        assert_eq!(indef.as_array().unwrap()[1].as_special(), Some(CBORSpecial::Bool(true)));
+        assert_eq!(indef.as_array().unwrap()[2].as_array().unwrap().len(), 1);
+        // stray break
+        assert!(CBORValue::from_cbor_bytes(&[BREAK]).is_err());
+        // deeply nested arrays/maps/tags are rejected instead of overflowing the stack
+        let nested = |prefix: &[u8], depth: usize| {
+            prefix.repeat(depth).into_iter().chain(std::iter::once(0x00)).collect::<Vec<u8>>()
+        };
+        assert!(CBORValue::from_cbor_bytes(&nested(&[0x81], 100)).is_ok());
+        assert!(CBORValue::from_cbor_bytes(&nested(&[0x81], 10000)).is_err());
+        assert!(CBORValue::from_cbor_bytes(&nested(&[0xa1, 0x00], 10000)).is_err());
+        assert!(CBORValue::from_cbor_bytes(&nested(&[0xc6], 10000)).is_err());
+        assert!(TaggedValue::from_cbor_bytes(&nested(&[0xc6], 10000)).is_err());
+        // duplicate keys are reported as they are
+        let duplicate_keys = vec![
+            map_def(2),
+                cbor_int(-1, cbor_event::Sz::Inline),
+                vec![0x00],
+                cbor_int(-1, cbor_event::Sz::Inline),
+                vec![0x01],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let duplicate_err = CBORValue::from_cbor_bytes(&duplicate_keys).unwrap_err().to_string();
+        assert!(duplicate_err.contains("Duplicate key: -1"), "{}", duplicate_err);
+    }
+
+    #[test]
+    fn major_types() {
+        let mut map = BTreeMap::new();
+        map.insert(CBORValue::new_text(String::from("key")), CBORValue::new_uint(1));
+        let major_types = MajorTypes::new(
+            5,
+            2,
+            vec![0xBA, 0xAD],
+            String::from("text"),
+            vec![CBORValue::new_uint(1), CBORValue::new_special(CBORSpecial::Null)],
+            map,
+        );
+        let expected = vec![
+            arr_def(8),
+                vec![0x05],
+                vec![0x22],
+                vec![0x42, 0xBA, 0xAD],
+                cbor_string("text"),
+                arr_def(2),
+                    vec![0x01],
+                    vec![NULL],
+                map_def(1),
+                    cbor_string("key"),
+                    vec![0x01],
+                vec![0x05],
+                vec![0x22],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(major_types.to_cbor_bytes(), expected);
+        deser_test(&major_types);
+        // #0.5 only accepts the uint 5
+        let mut wrong_fixed = expected.clone();
+        wrong_fixed[expected.len() - 2] = 0x06;
+        assert!(MajorTypes::from_cbor_bytes(&wrong_fixed).is_err());
+        // #2 only accepts bstrs
+        let mut wrong_major_type = expected;
+        wrong_major_type[3] = 0x62;
+        assert!(MajorTypes::from_cbor_bytes(&wrong_major_type).is_err());
+    }
+
+    #[test]
+    fn major_type_lengths() {
+        let mut map = BTreeMap::new();
+        map.insert(CBORValue::new_text(String::from("key")), CBORValue::new_uint(1));
+        let pair = vec![CBORValue::new_uint(1), CBORValue::new_uint(2)];
+        let lengths = MajorTypeLengths::new(
+            MajorTypeHash::new(vec![0xBA, 0xAD]).unwrap(),
+            String::from("text"),
+            pair.clone(),
+            map.clone(),
+        ).unwrap();
+        let expected = vec![
+            arr_def(4),
+                vec![0x42, 0xBA, 0xAD],
+                cbor_string("text"),
+                arr_def(2),
+                    vec![0x01],
+                    vec![0x02],
+                map_def(1),
+                    cbor_string("key"),
+                    vec![0x01],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(lengths.to_cbor_bytes(), expected);
+        deser_test(&lengths);
+        // the lengths must be exact
+        assert!(MajorTypeHash::new(vec![0xBA]).is_err());
+        assert!(MajorTypeHash::new(vec![0xBA, 0xAD, 0x00]).is_err());
+        assert!(MajorTypeLengths::new(
+            MajorTypeHash::new(vec![0xBA, 0xAD]).unwrap(),
+            String::from("txt"),
+            pair.clone(),
+            map.clone(),
+        ).is_err());
+        assert!(MajorTypeLengths::new(
+            MajorTypeHash::new(vec![0xBA, 0xAD]).unwrap(),
+            String::from("text"),
+            pair,
+            BTreeMap::new(),
+        ).is_err());
+        let short_hash = vec![
+            arr_def(4),
+                vec![0x41, 0xBA],
+                cbor_string("text"),
+                arr_def(2),
+                    vec![0x01],
+                    vec![0x02],
+                map_def(1),
+                    cbor_string("key"),
+                    vec![0x01],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(MajorTypeLengths::from_cbor_bytes(&short_hash).is_err());
+        let long_array = vec![
+            arr_def(4),
+                vec![0x42, 0xBA, 0xAD],
+                cbor_string("text"),
+                arr_def(3),
+                    vec![0x01],
+                    vec![0x02],
+                    vec![0x03],
+                map_def(1),
+                    cbor_string("key"),
+                    vec![0x01],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(MajorTypeLengths::from_cbor_bytes(&long_array).is_err());
+    }
+
+    #[test]
+    fn any_tags() {
+        let any_tags = AnyTags::new(
+            TaggedValue::new(1, CBORValue::new_text(String::from("one"))),
+            TaggedValue::new(24, CBORValue::new_array(vec![CBORValue::new_uint(2)])),
+            CBORValue::new_nint(0),
+        );
+        let expected = vec![
+            arr_def(3),
+                cbor_tag(1),
+                    cbor_string("one"),
+                cbor_tag_sz(24, cbor_event::Sz::One),
+                    arr_def(1),
+                        vec![0x02],
+                cbor_tag_sz(100, cbor_event::Sz::One),
+                    vec![0x20],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(any_tags.to_cbor_bytes(), expected);
+        deser_test(&any_tags);
+        let deser = AnyTags::from_cbor_bytes(&expected).unwrap();
+        assert_eq!(deser.alias.tag(), 1);
+        assert_eq!(deser.alias.value(), &CBORValue::new_text(String::from("one")));
+        assert_eq!(deser.inline.tag(), 24);
+        // the value must still be tagged
+        assert!(TaggedValue::from_cbor_bytes(&cbor_string("one")).is_err());
+    }
+
+    #[test]
+    fn regexp() {
+        let email = Email::new(String::from("cddl@example.com")).unwrap();
+        deser_test(&Emails::new(email.clone(), vec![Email::new(String::from("a@b.c")).unwrap()]));
+        // regexps must match the entire string
+        assert!(Email::new(String::from("not an email: cddl@example.com")).is_err());
+        assert!(Email::new(String::from("cddl@example")).is_err());
+        let bad_bytes = cbor_string("cddl@example");
+        assert!(Email::from_cbor_bytes(&bad_bytes).is_err());
+        assert_eq!(String::from(Email::from_cbor_bytes(&email.to_cbor_bytes()).unwrap()), "cddl@example.com");
+    }
+
+    #[test]
+    fn bits() {
+        let mut permissions = Permissions::new(0b101).unwrap();
+        assert!(permissions.read());
+        assert!(!permissions.write());
+        assert!(permissions.execute());
+        permissions.set_write(true);
+        permissions.set_read(false);
+        assert_eq!(u64::from(permissions), 0b110);
+        deser_test(&permissions);
+        assert!(Permissions::new(0b1000).is_err());
+        assert!(Permissions::from_cbor_bytes(&cbor_int(0b1001, cbor_event::Sz::Inline)).is_err());
+
+        let mut tcp_flags = TcpFlags::new(vec![0x02]).unwrap();
+        assert!(tcp_flags.syn());
+        assert!(!tcp_flags.cwr());
+        tcp_flags.set_cwr(true);
+        tcp_flags.set_syn(false);
+        assert_eq!(Vec::<u8>::from(tcp_flags.clone()), vec![0x00, 0x80]);
+        deser_test(&tcp_flags);
+        assert!(TcpFlags::new(vec![0x00, 0x01]).is_err());
+        assert!(TcpFlags::new(vec![0x00, 0x00, 0x01]).is_err());
+    }
+
+    #[test]
+    fn intersections() {
+        assert!(WithinAnd::new(4).is_err());
+        assert!(WithinAnd::new(101).is_err());
+        let within_and = WithinAnd::new(100).unwrap();
+        deser_test(&within_and);
+        assert!(WithinAnd::from_cbor_bytes(&cbor_int(101, cbor_event::Sz::One)).is_err());
+        // both the size and the regexp are checked at runtime
+        assert!(LowercaseName::new(String::new()).is_err());
+        assert!(LowercaseName::new(String::from("Uppercase")).is_err());
+        assert!(LowercaseName::new(String::from("waytoolongforthisname")).is_err());
+        let name = LowercaseName::new(String::from("lowercase")).unwrap();
+        deser_test(&name);
+        deser_test(&Intersections::new(within_and, name));
+    }
+
+    #[test]
+    fn cbor_seq() {
+        let entries = vec![
+            CborseqEntry::new(1, String::from("one")),
+            CborseqEntry::new(2, String::from("two")),
+        ];
+        let cbor_seqs = CborSeqs::new(entries, vec![0, 1000]);
+        // items are written back-to-back inside the bytes without any array header
+        let entries_bytes = vec![
+            arr_def(2),
+            cbor_int(1, cbor_event::Sz::Inline),
+            cbor_string("one"),
+            arr_def(2),
+            cbor_int(2, cbor_event::Sz::Inline),
+            cbor_string("two"),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let uints_bytes = vec![
+            cbor_int(0, cbor_event::Sz::Inline),
+            cbor_int(1000, cbor_event::Sz::Two),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let expected = vec![
+            arr_def(2),
+            cbor_bytes_sz(entries_bytes, cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+            cbor_bytes_sz(uints_bytes, cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(cbor_seqs.to_cbor_bytes(), expected);
+        deser_test(&cbor_seqs);
+        // an empty sequence is just empty bytes
+        deser_test(&CborSeqs::new(vec![], vec![]));
+    }
+
+    #[test]
+    fn not_equal() {
+        assert!(NonZero::new(0).is_err());
+        assert!(NonZero::from_cbor_bytes(&cbor_int(0, cbor_event::Sz::Inline)).is_err());
+        deser_test(&NonZero::new(1).unwrap());
+        assert!(NonEmptyText::new(String::new()).is_err());
+        deser_test(&NonEmptyText::new(String::from("text")).unwrap());
+        assert!(NonNullHash::new(vec![0x00, 0x00]).is_err());
+        deser_test(&NonNullHash::new(vec![0x00]).unwrap());
+        deser_test(&NonNullHash::new(vec![0x00, 0x00, 0x00]).unwrap());
+        // combined with other constraints all are checked
+        assert!(NonZeroPercent::new(0).is_err());
+        assert!(NonZeroPercent::new(101).is_err());
+        deser_test(&NonZeroPercent::new(100).unwrap());
+        // inline .ne gets its own type named after the field
+        let inline = InlineNotEqual::new(
+            InlineNotEqualCount::new(1).unwrap(),
+            InlineNotEqualLabel::new(String::from("label")).unwrap(),
+            InlineNotEqualPercent::new(50).unwrap(),
+        );
+        let expected = vec![
+            arr_def(3),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_string("label"),
+                cbor_int(50, cbor_event::Sz::One),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(inline.to_cbor_bytes(), expected);
+        deser_test(&inline);
+        assert!(InlineNotEqualCount::new(0).is_err());
+        assert!(InlineNotEqualLabel::new(String::new()).is_err());
+        assert!(InlineNotEqualPercent::new(0).is_err());
+        assert!(InlineNotEqualPercent::new(101).is_err());
+        let zero_count = vec![
+            arr_def(3),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_string("label"),
+                cbor_int(50, cbor_event::Sz::One),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(InlineNotEqual::from_cbor_bytes(&zero_count).is_err());
+    }
+
+    #[test]
+    fn sockets() {
+        // all plugs of a socket are merged into a single choice
+        deser_test(&SocketChoice::U64(5));
+        deser_test(&SocketChoice::Text(String::from("plug")));
+        deser_test(&SocketMessage::SocketMessage0(5));
+        deser_test(&SocketMessage::SocketMessage1(String::from("plug")));
+        // sockets that are never plugged accept anything
+        let sockets = Sockets::new(
+            SocketChoice::U64(0),
+            CBORValue::new_array(vec![CBORValue::new_uint(1), CBORValue::new_text(String::from("two"))]),
+            SocketMessage::SocketMessage1(String::from("plug")),
+        );
+        deser_test(&sockets);
+    }
+
+    #[test]
+    fn unwrap() {
+        let unwrapped = Unwrapped::new(
+            UnwrapBaseFields::new(5, String::from("five")),
+            UnwrapMapBaseFields::new(6),
+            vec![0xFF],
+        );
+        // unwrapped fields are embedded directly in the outer array
+        let expected = vec![
+            arr_def(4),
+            cbor_int(5, cbor_event::Sz::Inline),
+            cbor_string("five"),
+            cbor_int(6, cbor_event::Sz::Inline),
+            cbor_bytes_sz(vec![0xFF], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(unwrapped.to_cbor_bytes(), expected);
+        deser_test(&unwrapped);
+        // the original definitions are still generated as-is
+        deser_test(&UnwrapBase::new(5, String::from("five")));
+        deser_test(&UnwrapMapBase::new(6));
+        // unwrapping into a map embeds the keys and values directly in the outer map
+        let unwrapped_map = UnwrappedMap::new(
+            UnwrapIntoMapBaseFields::new(5, String::from("five")),
+            vec![0xFF],
+        );
+        let expected = vec![
+            map_def(3),
+            cbor_string("a"),
+            cbor_int(5, cbor_event::Sz::Inline),
+            cbor_string("b"),
+            cbor_string("five"),
+            cbor_string("c"),
+            cbor_bytes_sz(vec![0xFF], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(unwrapped_map.to_cbor_bytes(), expected);
+        deser_test(&unwrapped_map);
+    }
+
+    #[test]
+    fn generic_choices() {
+        deser_test(&GenericUses::new(
+            Some(5),
+            GenericChoiceBytes::Bytes(vec![0xBE, 0xEF]),
+            GenericResultU64Text::GenericResultU64Text0(7),
+        ));
+        deser_test(&GenericUses::new(
+            None,
+            GenericChoiceBytes::Text(String::from("text")),
+            GenericResultU64Text::new_generic_result_u64_text1(String::from("err"), String::from("reason")),
+        ));
+        // every instance gets its own enum + structs for multi-field variants
+        deser_test(&NamedGenericResult::NamedGenericResult0(vec![0xFF]));
+        deser_test(&NamedGenericResult::new_named_generic_result1(404, String::from("not found")));
+    }
+
+    #[test]
+    fn nested_generics() {
+        let nested = GenericNestedBytes::new(
+            vec![GenericPairBytesU64::new(vec![0xBE, 0xEF], 2)],
+            BTreeMap::from([(String::from("three"), vec![0x03])]),
+            Some(vec![0x04]),
+        );
+        deser_test(&NestedGenerics::new(GenericPairOfU64::new(1, String::from("one")), nested));
+        deser_test(&NamedPairOf::new(true, String::from("true")));
+    }
+
+    #[test]
+    fn map_extra_entries() {
+        let mut with_extra = MapWithExtra::new(1, String::from("two"));
+        deser_test(&with_extra);
+        with_extra.extra.insert(3, CBORValue::new_text(String::from("three")));
+        with_extra.extra.insert(100, CBORValue::new_uint(100));
+        let expected = vec![
+            map_def(4),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_int(2, cbor_event::Sz::Inline),
+                cbor_string("two"),
+                cbor_int(3, cbor_event::Sz::Inline),
+                cbor_string("three"),
+                cbor_int(100, cbor_event::Sz::One),
+                cbor_int(100, cbor_event::Sz::One),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(with_extra.to_cbor_bytes(), expected);
+        deser_test(&with_extra);
+        // unknown keys are read into extra regardless of where they are
+        let interleaved_bytes = vec![
+            map_def(3),
+                cbor_int(5, cbor_event::Sz::Inline),
+                cbor_int(6, cbor_event::Sz::Inline),
+                cbor_int(2, cbor_event::Sz::Inline),
+                cbor_string("two"),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_int(1, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let interleaved = MapWithExtra::from_cbor_bytes(&interleaved_bytes).unwrap();
+        assert_eq!(interleaved.extra.get(&5), Some(&CBORValue::new_uint(6)));
+        // keys that aren't of the extra's key type are still rejected
+        let text_key_bytes = vec![
+            map_def(3),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_int(2, cbor_event::Sz::Inline),
+                cbor_string("two"),
+                cbor_string("three"),
+                cbor_int(3, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(MapWithExtra::from_cbor_bytes(&text_key_bytes).is_err());
+        // extra keys of a type with no explicit fields
+        let mut text_extra = MapWithTextExtra::new(1);
+        text_extra.extra.insert(String::from("two"), 2);
+        deser_test(&text_extra);
+    }
+
+    #[test]
+    fn map_cut_entries() {
+        let mut with_cut = MapWithCut::new();
+        with_cut.key_strict = Some(1);
+        with_cut.key_loose = Some(2);
+        with_cut.colon = Some(3);
+        deser_test(&with_cut);
+        // the value matches so it's read as the field even though the catch-all also matches
+        let loose_bytes = vec![
+            map_def(1),
+                cbor_string("loose"),
+                cbor_int(2, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let loose = MapWithCut::from_cbor_bytes(&loose_bytes).unwrap();
+        assert_eq!(loose.key_loose, Some(2));
+        assert!(loose.extra.is_empty());
+        // no cut so the mismatched value falls through to the catch-all entry instead
+        let loose_mismatch_bytes = vec![
+            map_def(2),
+                cbor_string("loose"),
+                cbor_string("two"),
+                cbor_string("other"),
+                cbor_int(4, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let loose_mismatch = MapWithCut::from_cbor_bytes(&loose_mismatch_bytes).unwrap();
+        assert_eq!(loose_mismatch.key_loose, None);
+        assert_eq!(loose_mismatch.extra.get("loose"), Some(&CBORValue::new_text(String::from("two"))));
+        assert_eq!(loose_mismatch.extra.get("other"), Some(&CBORValue::new_uint(4)));
+        // cut entries (both ^ => and :) are an error instead
+        for cut_key in ["strict", "colon"] {
+            let cut_mismatch_bytes = vec![
+                map_def(1),
+                    cbor_string(cut_key),
+                    cbor_string("two"),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            assert!(MapWithCut::from_cbor_bytes(&cut_mismatch_bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn literal_keys() {
+        use cbor_event::Sz;
+        let mut keys = LiteralKeys::new(1, String::from("two"), 3);
+        deser_test(&keys);
+        keys.key_bytes_6162 = Some(true);
+        keys.key_neg_2 = Some(String::from("five"));
+        // canonically ordered by the encoded keys: -1, -2, h'0102', 'ab' then #6.42("tagged")
+        let expected = vec![
+            map_def(5),
+                cbor_int(-1, Sz::Inline),
+                cbor_int(1, Sz::Inline),
+                cbor_int(-2, Sz::Inline),
+                cbor_string("five"),
+                vec![0x42, 0x01, 0x02],
+                cbor_string("two"),
+                vec![0x42, 0x61, 0x62],
+                vec![0xf5],
+                cbor_tag_sz(42, Sz::One),
+                cbor_string("tagged"),
+                cbor_int(3, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(keys.to_cbor_bytes(), expected);
+        deser_test(&keys);
+        let unordered_bytes = vec![
+            map_def(3),
+                cbor_tag_sz(42, Sz::One),
+                cbor_string("tagged"),
+                cbor_int(3, Sz::Inline),
+                vec![0x42, 0x01, 0x02],
+                cbor_string("two"),
+                cbor_int(-1, Sz::Inline),
+                cbor_int(1, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let unordered = LiteralKeys::from_cbor_bytes(&unordered_bytes).unwrap();
+        assert_eq!(unordered.key_neg_1, 1);
+        assert_eq!(unordered.key_bytes_0102, "two");
+        assert_eq!(unordered.key_tag42_tagged, 3);
+        assert_eq!(unordered.key_bytes_6162, None);
+        // the tag is part of the key
+        let wrong_tag_bytes = vec![
+            map_def(3),
+                cbor_tag_sz(43, Sz::One),
+                cbor_string("tagged"),
+                cbor_int(3, Sz::Inline),
+                vec![0x42, 0x01, 0x02],
+                cbor_string("two"),
+                cbor_int(-1, Sz::Inline),
+                cbor_int(1, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(LiteralKeys::from_cbor_bytes(&wrong_tag_bytes).is_err());
+        // keys in errors are shown in diagnostic notation
+        let tagged_key = error::Key::Tagged(42, Box::new(error::Key::Str(String::from("tagged"))));
+        assert_eq!(tagged_key.to_string(), "#6.42(\"tagged\")");
+        let untagged_bytes = vec![
+            map_def(3),
+                cbor_string("tagged"),
+                cbor_int(3, Sz::Inline),
+                vec![0x42, 0x01, 0x02],
+                cbor_string("two"),
+                cbor_int(-1, Sz::Inline),
+                cbor_int(1, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(LiteralKeys::from_cbor_bytes(&untagged_bytes).is_err());
+    }
+
+    #[test]
+    fn inline_group_choices() {
+        let first = InlineGroupChoices::new(
+            InlineGroupChoicesArr::U64(5),
+            InlineGroupChoicesMap::new_a(7),
+            InlineGroupChoicesOtherArr::U64(6),
+            InlineGroupChoicesSameTypes::I0U64(8),
+        );
+        let expected = vec![
+            arr_def(4),
+                arr_def(2),
+                    cbor_int(0, cbor_event::Sz::Inline),
+                    cbor_int(5, cbor_event::Sz::Inline),
+                map_def(1),
+                    cbor_string("a"),
+                    cbor_int(7, cbor_event::Sz::Inline),
+                arr_def(2),
+                    cbor_int(2, cbor_event::Sz::Inline),
+                    cbor_int(6, cbor_event::Sz::Inline),
+                arr_def(2),
+                    cbor_int(0, cbor_event::Sz::Inline),
+                    cbor_int(8, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(first.to_cbor_bytes(), expected);
+        deser_test(&first);
+        let second = InlineGroupChoices::new(
+            InlineGroupChoicesArr::Text(String::from("five")),
+            InlineGroupChoicesMap::new_b(String::from("seven")),
+            InlineGroupChoicesOtherArr::Text(String::from("six")),
+            InlineGroupChoicesSameTypes::I1U64(8),
+        );
+        let expected = vec![
+            arr_def(4),
+                arr_def(2),
+                    cbor_int(1, cbor_event::Sz::Inline),
+                    cbor_string("five"),
+                map_def(1),
+                    cbor_string("b"),
+                    cbor_string("seven"),
+                arr_def(2),
+                    cbor_int(3, cbor_event::Sz::Inline),
+                    cbor_string("six"),
+                arr_def(2),
+                    cbor_int(1, cbor_event::Sz::Inline),
+                    cbor_int(8, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(second.to_cbor_bytes(), expected);
+        deser_test(&second);
+    }
+
+    #[test]
+    fn inline_map() {
+        let mut meta = InlineMapMeta::new(String::from("foo"));
+        let nested = InlineMapNested::new(
+            InlineMapNestedKey1::new(true),
+            InlineMapNestedKeyNeg1::new(3),
+        );
+        deser_test(&InlineMap::new(meta.clone(), nested.clone()));
+        meta.size = Some(5);
+        let inline_map = InlineMap::new(meta, nested);
+        let expected = vec![
+            arr_def(2),
+                map_def(2),
+                    cbor_string("name"),
+                    cbor_string("foo"),
+                    cbor_string("size"),
+                    cbor_int(5, cbor_event::Sz::Inline),
+                map_def(2),
+                    cbor_int(1, cbor_event::Sz::Inline),
+                    map_def(1),
+                        cbor_string("flag"),
+                        vec![0xf5],
+                    cbor_int(-1, cbor_event::Sz::Inline),
+                    map_def(1),
+                        cbor_string("count"),
+                        cbor_int(3, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(inline_map.to_cbor_bytes(), expected);
+        deser_test(&inline_map);
+    }
+
+    #[test]
+    fn map_with_embedded_group() {
+        let mut group = EmbeddedGroupMap::new(2);
+        deser_test(&MapWithEmbeddedGroup::new(1, group.clone()));
+        group.c = Some(String::from("three"));
+        let with_group = MapWithEmbeddedGroup::new(1, group);
+        let expected = vec![
+            map_def(3),
+                cbor_string("a"),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_string("b"),
+                cbor_int(2, cbor_event::Sz::Inline),
+                cbor_string("c"),
+                cbor_string("three"),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(with_group.to_cbor_bytes(), expected);
+        deser_test(&with_group);
+        // the embedded group's entries can be anywhere in the map
+        let unordered_bytes = vec![
+            map_def(3),
+                cbor_string("c"),
+                cbor_string("three"),
+                cbor_string("a"),
+                cbor_int(1, cbor_event::Sz::Inline),
+                cbor_string("b"),
+                cbor_int(2, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let unordered = MapWithEmbeddedGroup::from_cbor_bytes(&unordered_bytes).unwrap();
+        assert_eq!(unordered.a, 1);
+        assert_eq!(unordered.embedded_group.b, 2);
+        assert_eq!(unordered.embedded_group.c, Some(String::from("three")));
+        // the embedded group's mandatory fields are still required
+        let missing_bytes = vec![
+            map_def(1),
+                cbor_string("a"),
+                cbor_int(1, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(MapWithEmbeddedGroup::from_cbor_bytes(&missing_bytes).is_err());
+        // the array version is a separate type
+        deser_test(&ArrWithEmbeddedGroup::new(1, EmbeddedGroup::new(2)));
+    }
+
+    #[test]
+    fn bounded_array() {
+        use cbor_event::Sz;
+        assert!(BoundedArray::new(vec![]).is_err());
+        assert!(BoundedArray::new(vec![1, 2, 3, 4]).is_err());
+        let mut arr = BoundedArray::new(vec![1, 2]).unwrap();
+        deser_test(&arr);
+        arr.add(3).unwrap();
+        assert_eq!(arr.len(), 3);
+        deser_test(&arr);
+        assert!(arr.add(4).is_err());
+        assert_eq!(Vec::<u64>::from(arr), vec![1, 2, 3]);
+        let too_long = vec![
+            arr_sz(4, Sz::Inline),
+                cbor_int(1, Sz::Inline),
+                cbor_int(2, Sz::Inline),
+                cbor_int(3, Sz::Inline),
+                cbor_int(4, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BoundedArray::from_cbor_bytes(&too_long).is_err());
+        // the bounds are on the element count, not the element values
+        let big_elems = vec![
+            arr_sz(1, Sz::Inline),
+                cbor_int(1000, Sz::Two),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BoundedArray::from_cbor_bytes(&big_elems).is_ok());
+    }
+
+    #[test]
+    fn bounded_array_field() {
+        use cbor_event::Sz;
+        assert!(BoundedArrayField::new(vec![], vec![]).is_err());
+        assert!(BoundedArrayField::new(vec![1, 2, 3], vec![]).is_err());
+        deser_test(&BoundedArrayField::new(vec![1000, 2], vec![]).unwrap());
+        let too_long = vec![
+            arr_def(2),
+                arr_sz(3, Sz::Inline),
+                    cbor_int(1, Sz::Inline),
+                    cbor_int(2, Sz::Inline),
+                    cbor_int(3, Sz::Inline),
+                arr_sz(0, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BoundedArrayField::from_cbor_bytes(&too_long).is_err());
+    }
+
+    #[test]
+    fn array_with_seq() {
+        use cbor_event::Sz;
+        deser_test(&ArrayWithSeq::new(1, vec![], vec![0xBA]));
+        let with_names = ArrayWithSeq::new(1, vec!["a".to_owned(), "b".to_owned()], vec![0xBA]);
+        let expected = vec![
+            arr_def(4),
+                cbor_int(1, Sz::Inline),
+                cbor_string("a"),
+                cbor_string("b"),
+                vec![0x41, 0xBA],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(with_names.to_cbor_bytes(), expected);
+        deser_test(&with_names);
+        let indef_bytes = vec![
+            vec![ARR_INDEF],
+                cbor_int(1, Sz::Inline),
+                cbor_string("a"),
+                cbor_string("b"),
+                vec![0x41, 0xBA],
+            vec![BREAK],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let indef = ArrayWithSeq::from_cbor_bytes(&indef_bytes).unwrap();
+        assert_eq!(indef.names, vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(indef.data, vec![0xBA]);
+        // the trailing mandatory field is still required
+        let missing_data = vec![
+            arr_def(2),
+                cbor_int(1, Sz::Inline),
+                cbor_string("a"),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(ArrayWithSeq::from_cbor_bytes(&missing_data).is_err());
+    }
+
+    #[test]
+    fn array_with_trailing_seq() {
+        assert!(ArrayWithTrailingSeq::new(1, vec![]).is_err());
+        deser_test(&ArrayWithTrailingSeq::new(1, vec![vec![]]).unwrap());
+        deser_test(&ArrayWithTrailingSeq::new(1, vec![vec![0x01], vec![0x02, 0x03]]).unwrap());
+        let no_bytes = vec![
+            arr_def(1),
+                cbor_int(1, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(ArrayWithTrailingSeq::from_cbor_bytes(&no_bytes).is_err());
+    }
+
+    #[test]
+    fn floats() {
+        let half = vec![0xf9, 0x3e, 0x00];
+        let single = vec![0xfa, 0x3f, 0xc0, 0x00, 0x00];
+        let double = vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let floats = Floats::new(1.5, 1.5, 1.5);
+        let expected = vec![
+            arr_def(3),
+                half.clone(),
+                single.clone(),
+                double.clone(),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(floats.to_cbor_bytes(), expected);
+        deser_test(&floats);
+        // other widths are rejected
+        let mixed_bytes = vec![
+            arr_def(3),
+                double,
+                half,
+                single,
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Floats::from_cbor_bytes(&mixed_bytes).is_err());
+        // values are never widened past their declared width
+        let inexact_half = Floats::new(0.1, 1.5, 1.5);
+        assert!(cbor_event::se::Serialize::serialize(&inexact_half, &mut cbor_event::se::Serializer::new_vec()).is_err());
+        let inexact = Floats::new(1.5, 0.1, 0.1);
+        let inexact_bytes = inexact.to_cbor_bytes();
+        assert_eq!(inexact_bytes[1], 0xf9);
+        assert_eq!(inexact_bytes[4], 0xfa);
+        assert_eq!(inexact_bytes[9], 0xfb);
+        deser_test(&inexact);
+    }
+
+    #[test]
+    fn float_bounds() {
+        assert!(BoundedFloat::new(10.0).is_ok());
+        assert!(BoundedFloat::new(10.5).is_err());
+        deser_test(&BoundedFloat::new(-3.25).unwrap());
+        assert!(FloatFieldBounds::new(0.5).is_err());
+        deser_test(&FloatFieldBounds::new(1.0).unwrap());
+        let too_small = vec![
+            arr_def(1),
+                vec![0xfa, 0x3f, 0x00, 0x00, 0x00],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(FloatFieldBounds::from_cbor_bytes(&too_small).is_err());
+        // fractional and exclusive bounds
+        assert!(ExclusiveFloat::new(0.5).is_ok());
+        assert!(ExclusiveFloat::new(1.25).is_ok());
+        assert!(ExclusiveFloat::new(0.25).is_err());
+        assert!(ExclusiveFloat::new(1.5).is_err());
+        deser_test(&ExclusiveFloat::new(1.25).unwrap());
+        assert!(ExclusiveFloat::from_cbor_bytes(&[0xf9, 0x38, 0x00]).is_ok());
+        assert!(ExclusiveFloat::from_cbor_bytes(&[0xf9, 0x3e, 0x00]).is_err());
+        assert!(FloatLt::new(1.25).is_ok());
+        assert!(FloatLt::new(1.5).is_err());
+        // NaN is never within bounds
+        assert!(BoundedFloat::new(f64::NAN).is_err());
+        assert!(FloatFieldBounds::new(f32::NAN).is_err());
+        assert!(BoundedFloat::from_cbor_bytes(&[0xfb, 0x7f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn big_ints() {
+        use std::convert::TryFrom;
+        use std::str::FromStr;
+        // unsigned/integer values that fit in 64 bits are encoded as regular ints
+        // but biguint/bigint are always bignums
+        let small = BigInts::new(
+            BigUInt::from(5u64),
+            BigInt::from(-5i64),
+            BigUInt::from(u64::MAX),
+            BigInt::from(i64::MIN),
+            Int::new_uint(1),
+        );
+        let small_bytes = vec![
+            arr_def(5),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(3),
+                cbor_bytes_sz(vec![0x04], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_int(u64::MAX as i128, cbor_event::Sz::Eight),
+                cbor_int(i64::MIN as i128, cbor_event::Sz::Eight),
+                cbor_int(1, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(small.to_cbor_bytes(), small_bytes);
+        deser_test(&small);
+        // and bigger ones as bignums
+        let two_pow_64 = vec![0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let big = BigInts::new(
+            BigUInt::from_str("18446744073709551616").unwrap(),
+            BigInt::from_str("-18446744073709551617").unwrap(),
+            BigUInt::from_str("18446744073709551616").unwrap(),
+            BigInt::from_str("18446744073709551616").unwrap(),
+            Int::new_nint(0),
+        );
+        let big_bytes = vec![
+            arr_def(5),
+                cbor_tag(2),
+                cbor_bytes_sz(two_pow_64.clone(), cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(3),
+                cbor_bytes_sz(two_pow_64.clone(), cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(2),
+                cbor_bytes_sz(two_pow_64.clone(), cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(2),
+                cbor_bytes_sz(two_pow_64, cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_int(-1, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(big.to_cbor_bytes(), big_bytes);
+        deser_test(&big);
+        // small bignums are accepted for unsigned/integer too
+        let small_bignums = vec![
+            arr_def(5),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(3),
+                cbor_bytes_sz(vec![0x04], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0xff; 8], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(3),
+                cbor_bytes_sz(vec![0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_int(1, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(BigInts::from_cbor_bytes(&small_bignums).unwrap().to_cbor_bytes(), small_bytes);
+        // but regular ints aren't biguint/bigint
+        let plain_ints = vec![
+            arr_def(5),
+                cbor_int(5, cbor_event::Sz::Inline),
+                cbor_int(-5, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BigInts::from_cbor_bytes(&plain_ints).is_err());
+        // negative values and other tags aren't unsigned
+        let negative_unsigned = vec![
+            arr_def(5),
+                cbor_tag(3),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BigInts::from_cbor_bytes(&negative_unsigned).is_err());
+        let wrong_tag = vec![
+            arr_def(5),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_tag(4),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+                cbor_int(0, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BigInts::from_cbor_bytes(&wrong_tag).is_err());
+        // conversions
+        assert_eq!(big.big_int.to_string(), "-18446744073709551617");
+        assert_eq!(big.big_int.as_i128(), Some(-18446744073709551617));
+        assert_eq!(big.big_int.as_i64(), None);
+        assert_eq!(small.big_int.as_i64(), Some(-5));
+        assert_eq!(small.unsigned_val.as_u64(), Some(u64::MAX));
+        assert!(BigUInt::from_str("-1").is_err());
+        assert!(BigUInt::try_from(BigInt::from(-1i64)).is_err());
+        assert_eq!(BigInt::from(Int::new_nint(4)), BigInt::from(-5i64));
+        assert_eq!(Int::try_from(BigInt::from(u64::MAX)).unwrap().to_string(), u64::MAX.to_string());
+        assert_eq!(u64::try_from(small.unsigned_val.clone()).unwrap(), u64::MAX);
+        assert_eq!(i64::try_from(small.big_int.clone()).unwrap(), -5);
+        assert!(i64::try_from(big.big_int.clone()).is_err());
+        let num: num_bigint::BigInt = big.big_int.clone().into();
+        assert_eq!(BigInt::from(num.clone()), big.big_int);
+        assert!(BigUInt::try_from(num).is_err());
+        assert_eq!(BigInt::from(num_bigint::BigUint::from(5u64)), BigInt::from(5u64));
+        assert!(num_bigint::BigUint::try_from(BigInt::from(-1i64)).is_err());
+        assert!(Int::try_from(big.big_int).is_err());
+    }
+
+    #[test]
+    fn big_nint() {
+        let big_nint = BigNint::new(BigInt::from(-5i64));
+        let expected = vec![
+            arr_def(1),
+                cbor_tag(3),
+                cbor_bytes_sz(vec![0x04], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(big_nint.to_cbor_bytes(), expected);
+        deser_test(&big_nint);
+        // non-negative values can't be represented
+        let positive = BigNint::new(BigInt::from(5u64));
+        assert!(cbor_event::se::Serialize::serialize(&positive, &mut cbor_event::se::Serializer::new_vec()).is_err());
+        let positive_bytes = vec![
+            arr_def(1),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x05], cbor_event::StringLenSz::Len(cbor_event::Sz::Inline)),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BigNint::from_cbor_bytes(&positive_bytes).is_err());
+        let plain_nint = vec![
+            arr_def(1),
+                cbor_int(-5, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(BigNint::from_cbor_bytes(&plain_nint).is_err());
+    }
+
+    #[test]
+    fn date_time() {
+        deser_test(&DateTime::new(String::from("2023-01-02"), 1672628645));
+    }
+
+    #[test]
+    fn fractions() {
+        use std::str::FromStr;
+        let fractions = Fractions::new(
+            DecimalFraction::from_str("12.34").unwrap(),
+            BigFloat::from_str("0.75").unwrap(),
+        );
+        let expected = vec![
+            arr_def(2),
+                cbor_tag(4),
+                arr_def(2),
+                    cbor_int(-2, cbor_event::Sz::Inline),
+                    cbor_int(1234, cbor_event::Sz::Two),
+                cbor_tag(5),
+                arr_def(2),
+                    cbor_int(-2, cbor_event::Sz::Inline),
+                    cbor_int(3, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(fractions.to_cbor_bytes(), expected);
+        deser_test(&fractions);
+        // decimal strings
+        assert_eq!(fractions.price.exponent(), -2);
+        assert_eq!(fractions.price.mantissa(), &BigInt::from(1234i64));
+        assert_eq!(fractions.price.to_string(), "12.34");
+        assert_eq!(DecimalFraction::from_str("-0.05").unwrap().to_string(), "-0.05");
+        assert_eq!(DecimalFraction::from_str("1.50").unwrap().to_string(), "1.50");
+        let positive_exponent = DecimalFraction::from_str("1.5e3").unwrap();
+        assert_eq!(positive_exponent.exponent(), 2);
+        assert_eq!(positive_exponent.to_string(), "15e2");
+        assert_eq!(DecimalFraction::from_str(&positive_exponent.to_string()).unwrap(), positive_exponent);
+        assert!(DecimalFraction::from_str("1.2.3").is_err());
+        assert!(DecimalFraction::from_str("abc").is_err());
+        assert!(DecimalFraction::from_str("").is_err());
+        assert_eq!(fractions.ratio.exponent(), -2);
+        assert_eq!(fractions.ratio.mantissa(), &BigInt::from(3i64));
+        assert_eq!(fractions.ratio.to_string(), "0.75");
+        assert_eq!(BigFloat::new(BigInt::from(-3i64), 4).to_string(), "-48");
+        assert_eq!(BigFloat::from_str("-48").unwrap().to_string(), "-48");
+        assert_eq!(BigFloat::from_str("1.5e3").unwrap().to_string(), "1500");
+        // 0.1 can't be represented in base 2
+        assert!(BigFloat::from_str("0.1").is_err());
+        // huge exponents are rejected when parsing and not expanded when formatting
+        assert!(BigFloat::from_str("1e999999999").is_err());
+        assert!(BigFloat::from_str("1e-999999999").is_err());
+        assert_eq!(
+            BigFloat::new(BigInt::from(3i64), -1_000_000_000).to_string(),
+            "3*2^-1000000000"
+        );
+        assert_eq!(BigFloat::new(BigInt::from(3i64), i64::MAX).to_string(), format!("3*2^{}", i64::MAX));
+        assert_eq!(
+            DecimalFraction::new(BigInt::from(3i64), i64::MIN).to_string(),
+            format!("3e{}", i64::MIN)
+        );
+        // mantissas can be bignums
+        let big_mantissa = DecimalFraction::from_str("184467440737095516.16").unwrap();
+        deser_test(&Fractions::new(big_mantissa.clone(), BigFloat::new(BigInt::from(1i64), 0)));
+        assert_eq!(big_mantissa.to_string(), "184467440737095516.16");
+        // the array must have exactly 2 elements
+        let wrong_len = vec![
+            arr_def(2),
+                cbor_tag(4),
+                arr_def(3),
+                    cbor_int(-2, cbor_event::Sz::Inline),
+                    cbor_int(1234, cbor_event::Sz::Two),
+                    cbor_int(0, cbor_event::Sz::Inline),
+                cbor_tag(5),
+                arr_def(2),
+                    cbor_int(-2, cbor_event::Sz::Inline),
+                    cbor_int(3, cbor_event::Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Fractions::from_cbor_bytes(&wrong_len).is_err());
+    }
 }
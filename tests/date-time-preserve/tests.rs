@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cbor_event::{de::Deserializer, Sz, StringLenSz};
+    use serialization::Deserialize;
+
+    #[test]
+    fn date_time() {
+        let date_time_encodings = vec![
+            (cbor_tag(0), cbor_string("2023-01-02T03:04:05Z")),
+            (cbor_tag_sz(0, Sz::One), cbor_str_sz("2023-01-02T03:04:05.000Z", StringLenSz::Len(Sz::Two))),
+            (cbor_tag_sz(0, Sz::Eight), cbor_str_sz("2023-01-02T05:04:05+02:00", StringLenSz::Indefinite(vec![(10, Sz::One), (15, Sz::Inline)]))),
+        ];
+        let epoch_time_encodings = vec![
+            cbor_int(1672628645, Sz::Eight),
+            cbor_int(-5, Sz::Two),
+            vec![0xf9, 0x3e, 0x00],
+            vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+            cbor_float(1.5),
+            // more precise than the nanoseconds kept
+            cbor_float(1e-10),
+            cbor_float(1672628645.1234567),
+        ];
+        for (tag_enc, text_enc) in date_time_encodings.iter() {
+            for (i, time_enc) in epoch_time_encodings.iter().enumerate() {
+                let irregular_bytes = vec![
+                    arr_def(3),
+                        cbor_string("launch"),
+                        tag_enc.clone(),
+                        text_enc.clone(),
+                        cbor_tag_sz(1, if i % 2 == 0 { Sz::One } else { Sz::Inline }),
+                        time_enc.clone(),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let irregular = Event::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(irregular.to_cbor_bytes(), irregular_bytes);
+                // all of them represent the same date/time
+                assert_eq!(irregular.start, DateTime::new("2023-01-02T03:04:05Z").unwrap());
+            }
+        }
+        // the original text is kept
+        let original = Event::from_cbor_bytes(&vec![
+            arr_def(3),
+                cbor_string("launch"),
+                cbor_tag(0),
+                cbor_string("2023-01-02T03:04:05.000Z"),
+                cbor_tag(1),
+                cbor_int(0, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>()).unwrap();
+        assert_eq!(original.start.to_string(), "2023-01-02T03:04:05.000Z");
+        // but newly created ones use the canonical form
+        let new = Event::new(
+            String::from("launch"),
+            DateTime::from(*original.start.as_datetime()),
+            EpochTime::new(0).unwrap(),
+        );
+        assert_eq!(new.start.to_string(), "2023-01-02T03:04:05Z");
+        let mut deserializer = Deserializer::from(std::io::Cursor::new(new.to_cbor_bytes()));
+        assert_eq!(Event::deserialize(&mut deserializer).unwrap().to_cbor_bytes(), new.to_cbor_bytes());
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cbor_event::{de::Deserializer, Sz, StringLenSz};
+    use serialization::Deserialize;
+
+    fn deser_test<T: Deserialize + ToCBORBytes>(orig: &T) {
+        let orig_bytes = orig.to_cbor_bytes();
+        print_cbor_types("orig", &orig_bytes);
+        let mut deserializer = Deserializer::from(std::io::Cursor::new(orig_bytes.clone()));
+        let deser = T::deserialize(&mut deserializer).unwrap();
+        print_cbor_types("deser", &deser.to_cbor_bytes());
+        assert_eq!(orig.to_cbor_bytes(), deser.to_cbor_bytes());
+        assert_eq!(deserializer.as_ref().position(), orig_bytes.len() as u64);
+    }
+
+    #[test]
+    fn event() {
+        let event = Event::new(
+            String::from("launch"),
+            DateTime::new("2023-01-02T03:04:05Z").unwrap(),
+            EpochTime::new(1672628645).unwrap(),
+        );
+        let expected = vec![
+            arr_def(3),
+                cbor_string("launch"),
+                cbor_tag(0),
+                cbor_string("2023-01-02T03:04:05Z"),
+                cbor_tag(1),
+                cbor_int(1672628645, Sz::Four),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(event.to_cbor_bytes(), expected);
+        deser_test(&event);
+        assert_eq!(event.start.as_datetime().timestamp(), event.end.as_secs());
+    }
+
+    #[test]
+    fn date_time() {
+        // equivalent textual forms are written in a single canonical way
+        let with_fraction = DateTime::new("2023-01-02T03:04:05.000Z").unwrap();
+        assert_eq!(with_fraction.to_string(), "2023-01-02T03:04:05Z");
+        assert_eq!(with_fraction, DateTime::new("2023-01-02T03:04:05Z").unwrap());
+        // while the offset is kept
+        let offset = DateTime::new("2023-01-02T05:04:05+02:00").unwrap();
+        assert_eq!(offset.to_string(), "2023-01-02T05:04:05+02:00");
+        assert_eq!(offset, with_fraction);
+        let offset_bytes = vec![
+            arr_def(3),
+                cbor_string("launch"),
+                cbor_tag(0),
+                cbor_str_sz("2023-01-02T05:04:05+02:00", StringLenSz::Len(Sz::One)),
+                cbor_tag(1),
+                cbor_int(0, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(Event::from_cbor_bytes(&offset_bytes).unwrap().to_cbor_bytes(), offset_bytes);
+        // invalid dates are rejected on creation and deserialization
+        assert!(DateTime::new("2023-13-02T03:04:05Z").is_err());
+        assert!(DateTime::new("yesterday").is_err());
+        let invalid_bytes = vec![
+            arr_def(3),
+                cbor_string("launch"),
+                cbor_tag(0),
+                cbor_string("2023-13-02T03:04:05Z"),
+                cbor_tag(1),
+                cbor_int(0, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Event::from_cbor_bytes(&invalid_bytes).is_err());
+        let wrong_tag_bytes = vec![
+            arr_def(3),
+                cbor_string("launch"),
+                cbor_tag(1),
+                cbor_string("2023-01-02T03:04:05Z"),
+                cbor_tag(1),
+                cbor_int(0, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Event::from_cbor_bytes(&wrong_tag_bytes).is_err());
+    }
+
+    #[test]
+    fn epoch_time() {
+        let event_with_end = |end: Vec<u8>| vec![
+            arr_def(3),
+                cbor_string("launch"),
+                cbor_tag(0),
+                cbor_string("2023-01-02T03:04:05Z"),
+                cbor_tag(1),
+                end,
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        // fractional times are floats
+        let fractional_bytes = event_with_end(cbor_float(1.5));
+        let fractional = Event::from_cbor_bytes(&fractional_bytes).unwrap();
+        assert_eq!(fractional.end.as_f64(), 1.5);
+        assert_eq!(fractional.end.as_secs(), 1);
+        assert_eq!(fractional.to_cbor_bytes(), fractional_bytes);
+        // and whole ones ints
+        let whole = Event::from_cbor_bytes(&event_with_end(cbor_float(2.0))).unwrap();
+        assert_eq!(whole.to_cbor_bytes(), event_with_end(cbor_int(2, Sz::Inline)));
+        let negative = Event::from_cbor_bytes(&event_with_end(cbor_int(-1, Sz::Inline))).unwrap();
+        assert_eq!(negative.end.as_secs(), -1);
+        deser_test(&negative);
+        assert_eq!(EpochTime::from_f64(-0.5).unwrap().as_secs(), -1);
+        // out of range timestamps are rejected
+        assert!(EpochTime::new(i64::MAX).is_err());
+        assert!(EpochTime::from_f64(f64::NAN).is_err());
+        assert!(Event::from_cbor_bytes(&event_with_end(cbor_int(i64::MAX as i128, Sz::Eight))).is_err());
+        assert!(Event::from_cbor_bytes(&event_with_end(cbor_string("1"))).is_err());
+    }
+
+    #[test]
+    fn static_type_names() {
+        deser_test(&BigInt::new(5));
+        deser_test(&TaggedValue::new(24, String::from("text")));
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cbor_event::{de::Deserializer, Sz};
+    use serialization::Deserialize;
+
+    fn deser_test<T: Deserialize + ToCBORBytes>(orig: &T) {
+        let orig_bytes = orig.to_cbor_bytes();
+        print_cbor_types("orig", &orig_bytes);
+        let mut deserializer = Deserializer::from(std::io::Cursor::new(orig_bytes.clone()));
+        let deser = T::deserialize(&mut deserializer).unwrap();
+        print_cbor_types("deser", &deser.to_cbor_bytes());
+        assert_eq!(orig.to_cbor_bytes(), deser.to_cbor_bytes());
+        assert_eq!(deserializer.as_ref().position(), orig_bytes.len() as u64);
+    }
+
+    #[test]
+    fn point() {
+        let point = Point::new([1, 2, 3]);
+        let expected = vec![
+            arr_def(3),
+                cbor_int(1, Sz::Inline),
+                cbor_int(2, Sz::Inline),
+                cbor_int(3, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(point.to_cbor_bytes(), expected);
+        deser_test(&point);
+        assert_eq!(<[u64; 3]>::from(point), [1, 2, 3]);
+        // the length is checked when converting from a Vec
+        assert_eq!(Point::try_from(vec![1u64, 2, 3]).unwrap().to_cbor_bytes(), expected);
+        assert!(Point::try_from(vec![1u64, 2]).is_err());
+        assert!(Point::try_from(vec![1u64, 2, 3, 4]).is_err());
+    }
+
+    #[test]
+    fn point_wrong_length() {
+        let too_short = vec![
+            arr_def(2),
+                cbor_int(1, Sz::Inline),
+                cbor_int(2, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Point::from_cbor_bytes(&too_short).is_err());
+        let too_long = vec![
+            arr_def(4),
+                cbor_int(1, Sz::Inline),
+                cbor_int(2, Sz::Inline),
+                cbor_int(3, Sz::Inline),
+                cbor_int(4, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Point::from_cbor_bytes(&too_long).is_err());
+    }
+
+    #[test]
+    fn shape() {
+        let shape = Shape::new(
+            String::from("line"),
+            Matrix::new([Point::new([0, 0, 0]), Point::new([1, 2, 3])]),
+        );
+        let expected = vec![
+            arr_def(2),
+                cbor_string("line"),
+                arr_def(2),
+                    arr_def(3),
+                        cbor_int(0, Sz::Inline),
+                        cbor_int(0, Sz::Inline),
+                        cbor_int(0, Sz::Inline),
+                    arr_def(3),
+                        cbor_int(1, Sz::Inline),
+                        cbor_int(2, Sz::Inline),
+                        cbor_int(3, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(shape.to_cbor_bytes(), expected);
+        deser_test(&shape);
+        let one_corner = vec![
+            arr_def(2),
+                cbor_string("line"),
+                arr_def(1),
+                    arr_def(3),
+                        cbor_int(0, Sz::Inline),
+                        cbor_int(0, Sz::Inline),
+                        cbor_int(0, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(Shape::from_cbor_bytes(&one_corner).is_err());
+    }
+
+    #[test]
+    fn bounded() {
+        assert!(Bounded::new(vec![]).is_err());
+        let mut bounded = Bounded::new(vec![1]).unwrap();
+        bounded.add(2).unwrap();
+        bounded.add(3).unwrap();
+        assert!(bounded.add(4).is_err());
+        deser_test(&bounded);
+    }
+}
@@ -89,4 +89,37 @@ mod tests {
         assert_eq!(json_str, serde_json::to_string_pretty(&from_value).unwrap());
         assert_eq!(json_str, serde_json::to_string_pretty(&from_json).unwrap());
     }
+
+    #[test]
+    fn any_wrapper() {
+        let json_str = r#"{"array":[{"uint":5},{"bytes":"baad"},{"tag":[2,"null"]}]}"#;
+        let from_value = AnyWrapper::from(CBORValue::new_array(vec![
+            CBORValue::new_uint(5),
+            CBORValue::new_bytes(vec![0xBA, 0xAD]),
+            CBORValue::new_tag(2, CBORSpecial::Null.into()),
+        ]));
+        let from_json: AnyWrapper = serde_json::from_str(json_str).unwrap();
+        assert_eq!(json_str, serde_json::to_string(&from_value).unwrap());
+        assert_eq!(json_str, serde_json::to_string(&from_json).unwrap());
+    }
+
+    #[test]
+    fn big_int_wrapper() {
+        use std::str::FromStr;
+        let json_str = "\"-18446744073709551617\"";
+        let from_value = BigIntWrapper::from(BigInt::from_str("-18446744073709551617").unwrap());
+        let from_json: BigIntWrapper = serde_json::from_str(json_str).unwrap();
+        assert_eq!(json_str, serde_json::to_string(&from_value).unwrap());
+        assert_eq!(json_str, serde_json::to_string(&from_json).unwrap());
+    }
+
+    #[test]
+    fn decfrac_wrapper() {
+        use std::str::FromStr;
+        let json_str = r#"{"mantissa":"1234","exponent":-2}"#;
+        let from_value = DecfracWrapper::from(DecimalFraction::from_str("12.34").unwrap());
+        let from_json: DecfracWrapper = serde_json::from_str(json_str).unwrap();
+        assert_eq!(json_str, serde_json::to_string(&from_value).unwrap());
+        assert_eq!(json_str, serde_json::to_string(&from_json).unwrap());
+    }
 }
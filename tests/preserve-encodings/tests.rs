@@ -1299,4 +1299,507 @@ mod tests {
             assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
         }
     }
+
+    #[test]
+    fn anys() {
+        let irregular_bytes = vec![
+            vec![ARR_INDEF],
+                cbor_int(5, Sz::Two),
+                cbor_str_sz("text", StringLenSz::Indefinite(vec![(1, Sz::One), (3, Sz::Inline)])),
+                cbor_bytes_sz(vec![0xBA, 0xAD], StringLenSz::Len(Sz::Eight)),
+                vec![MAP_INDEF],
+                    cbor_int(-5, Sz::Four),
+                        cbor_tag_sz(42, Sz::Two),
+                            vec![NULL],
+                vec![BREAK],
+                arr_sz(1, Sz::One),
+                    vec![0xf5],
+                // 1.5 as half and single precision floats
+                vec![0xf9, 0x3e, 0x00],
+                vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+            vec![BREAK],
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let from_bytes = Anys::from_cbor_bytes(&irregular_bytes).unwrap();
+        assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+        let mut map = cbor_value::CBORValueMap::new();
+        map.insert(CBORValue::new_nint(4), CBORValue::new_tag(42, CBORSpecial::Null.into()));
+        // equality ignores encoding details
+        let expected = vec![
+            CBORValue::new_uint(5),
+            CBORValue::new_text("text".to_owned()),
+            CBORValue::new_bytes(vec![0xBA, 0xAD]),
+            CBORValue::new_map(map),
+            CBORValue::new_array(vec![CBORSpecial::Bool(true).into()]),
+            CBORSpecial::Float(1.5).into(),
+            CBORSpecial::Float(1.5).into(),
+        ];
+        assert_eq!(*from_bytes.get(), expected);
+    }
+
+    #[test]
+    fn cbor_seqs() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        for def_enc in &def_encodings {
+            let uints_bytes = vec![
+                cbor_int(5, *def_enc),
+                cbor_int(0, Sz::Eight),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            let texts_bytes = vec![
+                cbor_str_sz("a", StringLenSz::Len(*def_enc)),
+                cbor_str_sz("bcd", StringLenSz::Indefinite(vec![(1, *def_enc), (2, Sz::Inline)])),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            let irregular_bytes = vec![
+                arr_sz(2, *def_enc),
+                    cbor_bytes_sz(uints_bytes, StringLenSz::Len(*def_enc)),
+                    cbor_bytes_sz(texts_bytes.clone(), StringLenSz::Indefinite(vec![(2, *def_enc), (texts_bytes.len() as u64 - 2, Sz::Inline)])),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            let from_bytes = CborSeqs::from_cbor_bytes(&irregular_bytes).unwrap();
+            assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            assert_eq!(from_bytes.uints, vec![5, 0]);
+            assert_eq!(from_bytes.texts, vec![String::from("a"), String::from("bcd")]);
+        }
+    }
+
+    #[test]
+    fn map_with_extra() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                // extra entries interleaved with the known fields
+                let irregular_bytes = vec![
+                    map_sz(4, *def_enc),
+                        cbor_int(7, *def_enc),
+                            cbor_str_sz("seven", str_enc.clone()),
+                        cbor_int(1, *def_enc),
+                            cbor_int(2, *def_enc),
+                        cbor_str_sz("name", str_enc.clone()),
+                            cbor_str_sz("foo", str_enc.clone()),
+                        cbor_int(3, *def_enc),
+                            cbor_str_sz("three", str_enc.clone()),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = MapWithExtra::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.extra.len(), 2);
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn map_with_cut() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                let irregular_bytes = vec![
+                    map_sz(2, *def_enc),
+                        cbor_str_sz("other", str_enc.clone()),
+                            cbor_str_sz("one", str_enc.clone()),
+                        cbor_str_sz("loose", str_enc.clone()),
+                            cbor_int(2, *def_enc),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = MapWithCut::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.key_loose, Some(2));
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+                // "loose" isn't cut so a text value is read into the catch-all table instead
+                let fall_through_bytes = vec![
+                    map_sz(2, *def_enc),
+                        cbor_str_sz("loose", str_enc.clone()),
+                            cbor_str_sz("two", str_enc.clone()),
+                        cbor_str_sz("other", str_enc.clone()),
+                            cbor_str_sz("one", str_enc.clone()),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = MapWithCut::from_cbor_bytes(&fall_through_bytes).unwrap();
+                assert_eq!(from_bytes.key_loose, None);
+                assert_eq!(from_bytes.extra.len(), 2);
+                assert_eq!(from_bytes.to_cbor_bytes(), fall_through_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn inline_map() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                let irregular_bytes = vec![
+                    map_sz(1, *def_enc),
+                        cbor_str_sz("meta", str_enc.clone()),
+                            map_sz(2, *def_enc),
+                                cbor_str_sz("size", str_enc.clone()),
+                                    cbor_int(5, *def_enc),
+                                cbor_str_sz("name", str_enc.clone()),
+                                    cbor_str_sz("foo", str_enc.clone()),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = InlineMap::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.meta.size, Some(5));
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn map_with_embedded_group() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                // the embedded group's entries can be interleaved with the rest of the map's
+                let irregular_bytes = vec![
+                    map_sz(3, *def_enc),
+                        cbor_str_sz("c", str_enc.clone()),
+                            cbor_str_sz("three", str_enc.clone()),
+                        cbor_str_sz("a", str_enc.clone()),
+                            cbor_int(1, *def_enc),
+                        cbor_str_sz("b", str_enc.clone()),
+                            cbor_int(2, *def_enc),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = MapWithEmbeddedGroup::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.embedded_group.b, 2);
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn array_with_seq() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                let irregular_bytes = vec![
+                    arr_sz(4, *def_enc),
+                        cbor_int(1, *def_enc),
+                        cbor_str_sz("a", str_enc.clone()),
+                        cbor_str_sz("b", str_enc.clone()),
+                        cbor_bytes_sz(vec![0xBA], str_enc.clone()),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = ArrayWithSeq::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.names.len(), 2);
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+                let irregular_indef_bytes = vec![
+                    vec![ARR_INDEF],
+                        cbor_int(1, *def_enc),
+                        cbor_str_sz("a", str_enc.clone()),
+                        cbor_bytes_sz(vec![0xBA], str_enc.clone()),
+                    vec![BREAK],
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_indef_bytes = ArrayWithSeq::from_cbor_bytes(&irregular_indef_bytes).unwrap();
+                assert_eq!(from_indef_bytes.to_cbor_bytes(), irregular_indef_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn floats() {
+        let widths = vec![
+            vec![0xf9, 0x3e, 0x00],
+            vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+            vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ];
+        for a_enc in &widths {
+            for b_enc in &widths {
+                for c_enc in &widths {
+                    let irregular_bytes = vec![
+                        arr_def(3),
+                            a_enc.clone(),
+                            b_enc.clone(),
+                            c_enc.clone(),
+                    ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                    let from_bytes = Floats::from_cbor_bytes(&irregular_bytes).unwrap();
+                    assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+                }
+            }
+        }
+        // the width read is kept when the value changes as long as it still fits exactly
+        let doubles_bytes = vec![
+            arr_def(3),
+                widths[2].clone(),
+                widths[2].clone(),
+                widths[2].clone(),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let mut from_doubles = Floats::from_cbor_bytes(&doubles_bytes).unwrap();
+        from_doubles.a = 2.0;
+        assert_eq!(from_doubles.to_cbor_bytes()[1], 0xfb);
+        let mut from_halves = Floats::from_cbor_bytes(&vec![
+            arr_def(3),
+                widths[0].clone(),
+                widths[0].clone(),
+                widths[0].clone(),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>()).unwrap();
+        from_halves.c = 0.1;
+        assert_eq!(from_halves.to_cbor_bytes()[7], 0xfb);
+    }
+
+    #[test]
+    fn strict_floats() {
+        let widths = vec![
+            vec![0xf9, 0x3e, 0x00],
+            vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+            vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ];
+        for half_enc in 0..widths.len() {
+            for single_enc in 0..widths.len() {
+                for double_enc in 0..widths.len() {
+                    let irregular_bytes = vec![
+                        arr_def(3),
+                            widths[half_enc].clone(),
+                            widths[single_enc].clone(),
+                            widths[double_enc].clone(),
+                    ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                    // only the declared widths are accepted so there is nothing lossy to round-trip
+                    if half_enc == 0 && single_enc == 1 && double_enc == 2 {
+                        let from_bytes = StrictFloats::from_cbor_bytes(&irregular_bytes).unwrap();
+                        assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+                    } else {
+                        assert!(StrictFloats::from_cbor_bytes(&irregular_bytes).is_err());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_float() {
+        let widths = vec![
+            vec![0xf9, 0x3e, 0x00],
+            vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+            vec![0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ];
+        for version_enc in &widths {
+            let irregular_bytes = vec![
+                arr_def(2),
+                    version_enc.clone(),
+                    widths[1].clone(),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            let from_bytes = FixedFloat::from_cbor_bytes(&irregular_bytes).unwrap();
+            assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+        }
+        let wrong_version = vec![
+            arr_def(2),
+                vec![0xf9, 0x3c, 0x00],
+                widths[1].clone(),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert!(FixedFloat::from_cbor_bytes(&wrong_version).is_err());
+    }
+
+    #[test]
+    fn big_ints() {
+        let small_bignum = |tag: u8, str_enc: StringLenSz| vec![
+            cbor_tag(tag),
+            cbor_bytes_sz(vec![0x05], str_enc),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        let int_encodings = vec![
+            cbor_int(5, Sz::Inline),
+            cbor_int(5, Sz::Two),
+            cbor_int(5, Sz::Eight),
+        ];
+        // biguint is always a bignum
+        let bignum_encodings = vec![
+            small_bignum(2, StringLenSz::Len(Sz::Inline)),
+            small_bignum(2, StringLenSz::Len(Sz::Four)),
+            small_bignum(2, StringLenSz::Indefinite(vec![(1, Sz::One)])),
+            vec![
+                cbor_tag_sz(2, Sz::Two),
+                cbor_bytes_sz(vec![0x05], StringLenSz::Len(Sz::One)),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>(),
+        ];
+        let negative_encodings = vec![
+            cbor_int(-6, Sz::Inline),
+            cbor_int(-6, Sz::Four),
+            small_bignum(3, StringLenSz::Len(Sz::Inline)),
+            vec![
+                cbor_tag_sz(3, Sz::Two),
+                cbor_bytes_sz(vec![0x05], StringLenSz::Len(Sz::One)),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>(),
+        ];
+        for big_uint_enc in &bignum_encodings {
+            for big_int_enc in int_encodings.iter().chain(bignum_encodings.iter()).chain(negative_encodings.iter()) {
+                let irregular_bytes = vec![
+                    arr_def(2),
+                        big_uint_enc.clone(),
+                        big_int_enc.clone(),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = BigInts::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+        for big_uint_enc in &int_encodings {
+            let plain_int_bytes = vec![
+                arr_def(2),
+                    big_uint_enc.clone(),
+                    cbor_int(5, Sz::Inline),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            assert!(BigInts::from_cbor_bytes(&plain_int_bytes).is_err());
+        }
+        // new values have no encoding details and use the preferred serialization
+        let mut from_bytes = BigInts::from_cbor_bytes(&vec![
+            arr_def(2),
+                small_bignum(2, StringLenSz::Len(Sz::Four)),
+                small_bignum(2, StringLenSz::Len(Sz::Inline)),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>()).unwrap();
+        from_bytes.big_uint = BigUInt::from(7u64);
+        from_bytes.big_int = BigInt::from(7u64);
+        let expected = vec![
+            arr_def(2),
+                cbor_tag(2),
+                cbor_bytes_sz(vec![0x07], StringLenSz::Len(Sz::Inline)),
+                cbor_int(7, Sz::Inline),
+        ].into_iter().flatten().clone().collect::<Vec<u8>>();
+        assert_eq!(from_bytes.to_cbor_bytes(), expected);
+    }
+
+    #[test]
+    fn fractions() {
+        let big_mantissa = vec![0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let fraction_encodings = |tag: u64| vec![
+            vec![
+                cbor_tag_sz(tag, Sz::Inline),
+                arr_def(2),
+                    cbor_int(-2, Sz::Inline),
+                    cbor_int(3, Sz::Inline),
+            ],
+            vec![
+                cbor_tag_sz(tag, Sz::Two),
+                arr_sz(2, Sz::Four),
+                    cbor_int(-2, Sz::Eight),
+                    cbor_int(3, Sz::One),
+            ],
+            vec![
+                cbor_tag_sz(tag, Sz::One),
+                vec![ARR_INDEF],
+                    cbor_int(-2, Sz::Inline),
+                    cbor_tag(2),
+                    cbor_bytes_sz(vec![0x03], StringLenSz::Len(Sz::One)),
+                vec![BREAK],
+            ],
+            vec![
+                cbor_tag_sz(tag, Sz::Inline),
+                arr_def(2),
+                    cbor_int(7, Sz::Two),
+                    cbor_tag_sz(3, Sz::Four),
+                    cbor_bytes_sz(big_mantissa.clone(), StringLenSz::Indefinite(vec![(4, Sz::Inline), (5, Sz::One)])),
+            ],
+        ];
+        for price_enc in fraction_encodings(4) {
+            for ratio_enc in fraction_encodings(5) {
+                let irregular_bytes = vec![
+                    vec![arr_def(2)],
+                    price_enc.clone(),
+                    ratio_enc,
+                ].into_iter().flatten().flatten().clone().collect::<Vec<u8>>();
+                let irregular = Fractions::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(irregular.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn literal_keys() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                // 42 doesn't fit in the initial byte
+                let tag_enc = match def_enc {
+                    Sz::Inline => Sz::One,
+                    sz => *sz,
+                };
+                let irregular_bytes = vec![
+                    map_sz(3, *def_enc),
+                        cbor_tag_sz(42, tag_enc),
+                            cbor_str_sz("tagged", str_enc.clone()),
+                            cbor_int(3, *def_enc),
+                        cbor_bytes_sz(vec![0x01, 0x02], str_enc.clone()),
+                            cbor_str_sz("two", str_enc.clone()),
+                        cbor_int(-1, *def_enc),
+                            cbor_int(1, *def_enc),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = LiteralKeys::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.key_neg_1, 1);
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn simple_values() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        for def_enc in &def_encodings {
+            // simple values only have one encoding so only the surrounding ones vary
+            let irregular_bytes = vec![
+                arr_sz(3, *def_enc),
+                    vec![0xf7],
+                    vec![0xf8, 0x20],
+                    cbor_int(5, *def_enc),
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            let from_bytes = SimpleValues::from_cbor_bytes(&irregular_bytes).unwrap();
+            assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            let undefined_bytes = vec![
+                arr_sz(3, *def_enc),
+                    vec![0xf7],
+                    vec![0xf8, 0x20],
+                    vec![0xf7],
+            ].into_iter().flatten().clone().collect::<Vec<u8>>();
+            let from_bytes = SimpleValues::from_cbor_bytes(&undefined_bytes).unwrap();
+            assert_eq!(from_bytes.to_cbor_bytes(), undefined_bytes);
+        }
+    }
+
+    #[test]
+    fn major_types() {
+        let def_encodings = vec![Sz::Inline, Sz::One, Sz::Two, Sz::Four, Sz::Eight];
+        let str_24_encodings = vec![
+            StringLenSz::Len(Sz::One),
+            StringLenSz::Len(Sz::Two),
+            StringLenSz::Len(Sz::Four),
+            StringLenSz::Len(Sz::Eight),
+        ];
+        for def_enc in &def_encodings {
+            for str_enc in &str_24_encodings {
+                let irregular_bytes = vec![
+                    arr_sz(3, *def_enc),
+                        cbor_tag_sz(3, *def_enc),
+                            cbor_str_sz("tagged", str_enc.clone()),
+                        cbor_bytes_sz(vec![0xBA, 0xAD], str_enc.clone()),
+                        cbor_int(-5, *def_enc),
+                ].into_iter().flatten().clone().collect::<Vec<u8>>();
+                let from_bytes = MajorTypes::from_cbor_bytes(&irregular_bytes).unwrap();
+                assert_eq!(from_bytes.tagged.tag(), 3);
+                assert_eq!(from_bytes.nint, 4);
+                assert_eq!(from_bytes.to_cbor_bytes(), irregular_bytes);
+            }
+        }
+    }
 }